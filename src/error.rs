@@ -1,12 +1,20 @@
 use thiserror::Error;
 #[cfg(feature = "rusqlite")]
 use rusqlite::{Error as RusqliteError};
-#[cfg(feature = "rusqlite")]
 use std::fmt::{Error as FmtError};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer, Deserialize};
+#[cfg(feature = "toml-config")]
+use toml::{de::Error as TomlDeError, ser::Error as TomlSerError};
 
 /// Errors for all Structs and Functions in this Crate.
+///
+/// With the `serde` feature, Errors are serialized as `{"type": "<Variant>", "detail": <fields>}`.
+/// Pass-through Errors are serialized as their message and cannot be deserialized.
 #[derive(Error, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(tag = "type", content = "detail"))]
 #[allow(clippy::enum_variant_names)]
+#[non_exhaustive]
 pub enum Error {
     /// Error used when a [ForeignKey](crate::ForeignKey) has a empty `foreign_table` Name
     #[error("Foreign Table Name cannot be Empty")]
@@ -20,6 +28,11 @@ pub enum Error {
     #[error("Column Name cannot be Empty")]
     EmptyColumnName,
 
+    /// Error used when a [Column](crate::Column) with a `AUTOINCREMENT` [PrimaryKey](crate::PrimaryKey) is not of type [Integer](crate::SQLiteType::Integer)
+    /// or the [PrimaryKey](crate::PrimaryKey) is [Descending](crate::Order::Descending) (see [here](https://www.sqlite.org/autoinc.html))
+    #[error("Autoincrement is only allowed on Integer Primary Keys")]
+    AutoincrementRequiresInteger,
+
     /// Error used when a [Column](crate::Column) has a [PrimaryKey](crate::PrimaryKey) and [ForeignKey](crate::ForeignKey) at the same time
     #[error("Column cannot be a Primary Key and a Foreign Key at the same Time")]
     PrimaryKeyAndForeignKey,
@@ -29,6 +42,22 @@ pub enum Error {
     #[error("Primary Key implies Unique")]
     PrimaryKeyAndUnique,
 
+    /// Error used when a [Check](crate::Check) has a empty `expr`
+    #[error("Check Expression cannot be Empty")]
+    EmptyCheckExpr,
+
+    /// Error used when a [DefaultValue::Real](crate::DefaultValue::Real) is NaN or infinite, as SQL has no literals for these
+    #[error("Default Value must be finite")]
+    NonFiniteDefault,
+
+    /// Error used when a Column constraint (e.g. [PrimaryKey](crate::PrimaryKey)) has a `constraint_name` that is `Some` but empty
+    #[error("Constraint Name cannot be Empty")]
+    EmptyConstraintName,
+
+    /// Error used when a [Custom Collation](crate::Collation::Custom) has a empty name
+    #[error("Collation Name cannot be Empty")]
+    EmptyCollationName,
+
     /// Error used when a [Table](crate::Table) has a empty `name`
     #[error("Table Name cannot be Empty")]
     EmptyTableName,
@@ -37,29 +66,199 @@ pub enum Error {
     #[error("Table must have Columns")]
     NoColumns,
 
-    /// Error used when a [Table](crate::Table) has multiple [Columns](crate::Column) with a [PrimaryKey](crate::PrimaryKey)
+    /// Error used when a [Table](crate::Table) has multiple [Columns](crate::Column) with the same `name`
+    #[error("Table contains multiple Columns named '{0}'")]
+    DuplicateColumnName(String),
+
+    /// Error used when a [Table](crate::Table) has no [Column](crate::Column) with the given `name`, e.g. in [Table::replace_column](crate::Table::replace_column),
+    /// a [TableConstraint](crate::TableConstraint) or a [Index](crate::Index) in a [Schema](crate::Schema)
+    #[error("Table contains no Column named '{0}'")]
+    ColumnNotFound(String),
+
+    /// Error used when a [Table](crate::Table) has multiple [Columns](crate::Column) with a [PrimaryKey](crate::PrimaryKey),
+    /// or a [PrimaryKey](crate::PrimaryKey) and a [CompositePrimaryKey](crate::TableConstraint::CompositePrimaryKey) at the same time
     #[error("Table can only have one Primary Key")]
     MultiplePrimaryKeys,
 
+    /// Error used when a [TableConstraint](crate::TableConstraint) does not name any [Columns](crate::Column)
+    #[error("Table Constraint must have Columns")]
+    EmptyConstraintColumns,
+
+    /// Error used when a [CompositeForeignKey](crate::CompositeForeignKey) has a different number of local and foreign [Columns](crate::Column)
+    #[error("Composite Foreign Key must have the same number of local and foreign Columns")]
+    ForeignKeyColumnCountMismatch,
+
     /// Error used when a table marked as `without_rowid` has no [Column](crate::Column) with a [PrimaryKey](crate::PrimaryKey)
     /// (`WITHOUT ROWID` tables need a Primary Key, see [here](https://www.sqlite.org/withoutrowid.html#differences_from_ordinary_rowid_tables))
     #[error("Tables without rowid must have one Primary Key")]
     WithoutRowidNoPrimaryKey,
 
+    /// Error used when a table marked as `without_rowid` has a `AUTOINCREMENT` [PrimaryKey](crate::PrimaryKey)
+    /// (see [here](https://www.sqlite.org/withoutrowid.html#differences_from_ordinary_rowid_tables))
+    #[error("Tables without rowid cannot use Autoincrement")]
+    AutoincrementOnWithoutRowidTable,
+
+    /// Error used when a `strict` [Table](crate::Table) has a [Column](crate::Column) of a type not allowed in `STRICT` tables, i.e. [Numeric](crate::SQLiteType::Numeric)
+    /// (see [here](https://www.sqlite.org/stricttables.html))
+    #[error("Column '{column}' of type {typ} is not allowed in Strict Tables")]
+    StrictTableInvalidType {
+        /// Name of the offending [Column](crate::Column)
+        column: String,
+        /// Type of the offending [Column](crate::Column)
+        typ: crate::SQLiteType,
+    },
+
+    /// Error used by [Schema::from_db](crate::Schema::from_db) when a `strict` Table in the DB has a Column of type `ANY`, which has no [SQLiteType](crate::SQLiteType)
+    /// (see [here](https://www.sqlite.org/stricttables.html#the_any_datatype))
+    #[error("Column '{column}' of Table '{table}' has type ANY, which is not supported")]
+    StrictTableAnyColumn {
+        /// Name of the [Table](crate::Table) in the DB
+        table: String,
+        /// Name of the offending Column
+        column: String,
+    },
+
+    /// Error used when a [Index](crate::Index) has a empty `name`
+    #[error("Index Name cannot be Empty")]
+    EmptyIndexName,
+
+    /// Error used when a [Trigger](crate::Trigger) has a empty `name`
+    #[error("Trigger Name cannot be Empty")]
+    EmptyTriggerName,
+
+    /// Error used when a [Trigger](crate::Trigger) has a empty `body`
+    #[error("Trigger Body cannot be Empty")]
+    EmptyTriggerBody,
+
+    /// Error used when a [Index](crate::Index) has a empty `table` Name
+    #[error("Index Table Name cannot be Empty")]
+    EmptyIndexTable,
+
+    /// Error used when a [Index](crate::Index) has no Columns
+    #[error("Index must have Columns")]
+    EmptyIndexColumns,
+
+    /// Error used when a [Index](crate::Index) has a empty `where_expr` (as opposed to none)
+    #[error("Index Where Clause cannot be Empty")]
+    EmptyIndexWhereClause,
+
+    /// Error used when a [Pragma](crate::Pragma) has a empty `name`
+    #[error("Pragma Name cannot be Empty")]
+    EmptyPragmaName,
+
+    /// Error used by [SQLPart::part_arr](crate::SQLPart::part_arr) and [SQLStatement::build_arr](crate::SQLStatement::build_arr) when the given buffer cannot hold the whole SQL
+    #[error("Buffer too small: {needed} bytes needed, {available} available")]
+    BufferTooSmall {
+        /// Number of bytes needed to hold the SQL
+        needed: usize,
+        /// Length of the given buffer
+        available: usize,
+    },
+
+    /// Error used when parsing a keyword (e.g. a [SQLiteType](crate::SQLiteType)) from a unknown [String]
+    #[error("Unknown Keyword '{0}'")]
+    UnknownKeyword(String),
+
+    /// Error pass though when writing to a [fmt::Write](std::fmt::Write) target fails, see [SQLStatement::write_to](crate::SQLStatement::write_to)
+    #[error(transparent)]
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_display", skip_deserializing))]
+    FmtError(#[from] FmtError),
+
+    /// Error pass though when TOML deserialization fails, see [Schema::from_toml](crate::Schema::from_toml)
+    #[cfg(feature = "toml-config")]
+    #[error(transparent)]
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_display", skip_deserializing))]
+    TomlDeError(#[from] TomlDeError),
+
+    /// Error pass though when TOML serialization fails, see [Schema::to_toml](crate::Schema::to_toml)
+    #[cfg(feature = "toml-config")]
+    #[error(transparent)]
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_display", skip_deserializing))]
+    TomlSerError(#[from] TomlSerError),
+
     /// Error used when a [Schema](crate::Schema) has no [Tables](crate::Table)
     #[error("Schema must contain Tables")]
     SchemaWithoutTables,
+
+    /// Error used when a [Schema](crate::Schema) without a `name` is attached, see [Schema::to_attach_stmt](crate::Schema::to_attach_stmt)
+    #[error("Schema must have a name to be attached")]
+    SchemaWithoutName,
+
+    /// Error used when the `name` of a [Schema](crate::Schema) is set, but empty, see [Schema::set_name](crate::Schema::set_name)
+    #[error("Schema Name cannot be Empty")]
+    EmptySchemaName,
+
+    /// Error used when a [Schema](crate::Schema) contains multiple [Tables](crate::Table) with the same `name`
+    #[error("Schema contains multiple Tables named '{0}'")]
+    DuplicateTableName(String),
+
+    /// Error used when a [Schema](crate::Schema) has no [Table](crate::Table) with the given `name`, e.g. for the `table` of a [Index](crate::Index) or in `Schema::explain_all_query_plans`
+    #[error("Schema contains no Table named '{0}'")]
+    TableNotFound(String),
+
+    /// Error used when a [AlterTableAddColumn](crate::AlterTableAddColumn) adds a [Column](crate::Column) with a [PrimaryKey](crate::PrimaryKey)
+    /// (see [here](https://www.sqlite.org/lang_altertable.html#alter_table_add_column))
+    #[error("Added Columns cannot be Primary Keys")]
+    AddColumnPrimaryKey,
+
+    /// Error used by [SchemaDiff::to_alter_statements](crate::SchemaDiff::to_alter_statements) when the changes to a [Table](crate::Table) cannot be expressed by `ALTER TABLE`
+    /// (see [here](https://www.sqlite.org/lang_altertable.html))
+    #[error("Changes to Table '{0}' cannot be expressed by ALTER TABLE")]
+    UnsupportedAlteration(String),
+
+    /// Error used by [Schema::validate](crate::Schema::validate) when a [ForeignKey](crate::ForeignKey) references a [Table](crate::Table) or [Column](crate::Column) not in the [Schema](crate::Schema)
+    #[error("Foreign Key '{from_table}.{from_column}' references unknown Column '{to_table}.{to_column}'")]
+    UnresolvedForeignKey {
+        /// Name of the [Table](crate::Table) containing the [ForeignKey](crate::ForeignKey)
+        from_table: String,
+        /// Name of the [Column](crate::Column) containing the [ForeignKey](crate::ForeignKey)
+        from_column: String,
+        /// `foreign_table` of the [ForeignKey](crate::ForeignKey)
+        to_table: String,
+        /// `foreign_column` of the [ForeignKey](crate::ForeignKey)
+        to_column: String,
+    },
+}
+
+/// Serializes pass-through Errors as their message
+#[cfg(feature = "serde")]
+fn serialize_display<T: std::fmt::Display, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+/// Errors for [SQLStatement::execute](crate::SQLStatement::execute)
+/// The Display message names the failed step, followed by the wrapped error (which is also available via [source()](std::error::Error::source))
+#[cfg(feature = "rusqlite")]
+#[derive(Error, Debug, PartialEq)]
+pub enum ExecError {
+    /// Error pass though when the SQL Statement could not be built
+    #[error("SQL build failed: {0}")]
+    Error(#[from] Error),
+
+    /// Error pass though when a [RusqliteError](rusqlite::Error) occurs
+    #[error("SQL execution failed: {0}")]
+    RusqliteError(#[from] RusqliteError),
+
+    /// Error pass though when a [CheckError] occurs, e.g. when combining [Schema::check_db](crate::Schema::check_db) and [SQLStatement::execute](crate::SQLStatement::execute)
+    #[error("Schema check failed: {0}")]
+    CheckError(#[from] CheckError),
 }
 
+/// Errors for [Schema::check_db](crate::Schema::check_db)
+/// The Display message names the failed step, followed by the wrapped error (which is also available via [source()](std::error::Error::source))
 #[cfg(feature = "rusqlite")]
 #[derive(Error, Debug, PartialEq)]
 pub enum CheckError {
+    /// Error pass though when the [Schema](crate::Schema) is invalid
+    #[error("Schema is invalid: {0}")]
+    Error(#[from] Error),
+
     /// Error pass though when a [RusqliteError](rusqlite::Error) occurs
-    #[error(transparent)]
+    #[error("Reading the Database failed: {0}")]
     RusqliteError(#[from] RusqliteError),
 
     /// Error pass though a [FmtError](std::fmt::Error) occurs
-    #[error(transparent)]
+    #[error("Formatting the Report failed: {0}")]
     FmtError(#[from] FmtError),
 }
 
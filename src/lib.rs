@@ -1,18 +1,44 @@
 //! A Library for generating SQLite-specific SQL to Initialize Databases (as in `CREATE TABLE...`).
 //! SQLite Interface agnostic, e.g. can be used with [rusqlite](https://github.com/rusqlite/rusqlite), [sqlite](https://github.com/stainless-steel/sqlite) or any other SQLite Interface.
 //!
+//! # serde
+//!
+//! All data types (and [Error]) implement serde's `Serialize` and `Deserialize`, for use with any serde format.
+//! Enabled by all of the `*-config` features below.
+//!
 //! # xml-config
 //!
-//! todo
+//! [Schema]s, [Table]s, [Column]s and [Index]es can be (de-)serialized from and to XML via the [xml] module
+//! (e.g. [xml::to_string] and [from_str]) or [Schema::from_xml_file] and [Schema::to_xml_file].
+//! The XML uses its own representation: scalar fields are attributes, e.g. `<column name="id" type="integer"/>`.
+//! A serialized [Schema] declares the sqlayout namespace, which can be required via [from_str_namespaced].
+//!
+//! # json-config
+//!
+//! [Schema]s and [Table]s can be (de-)serialized from and to JSON via [Schema::from_json], [Schema::to_json], [Table::from_json] and [Table::to_json].
+//! The JSON keys are the same as the XML names, without the `@` prefix for attributes, independent of the enabled features.
+//!
+//! # toml-config
+//!
+//! Same as json-config, via [Schema::from_toml], [Schema::to_toml], [Table::from_toml] and [Table::to_toml].
+//! The [Table]s of a [Schema] are written as `[[schema.table]]`, the [Column]s of a [Table] as `[[schema.table.column]]`.
 
 //#![warn(missing_docs)]
+// the Default impls are written out to keep them next to the SQLite reference they follow
+#![allow(clippy::derivable_impls)]
 mod error;
 
-#[cfg(feature = "xml-config")]
+#[cfg(feature = "serde")]
 use serde::{Serialize, Deserialize};
 
 #[cfg(feature = "xml-config")]
-pub use quick_xml::de::{from_str, from_reader};
+pub mod xml;
+#[cfg(feature = "xml-config")]
+pub use xml::{from_str, from_reader, from_str_namespaced};
+#[cfg(feature = "xml-config")]
+use quick_xml::DeError;
+#[cfg(feature = "xml-config")]
+use std::path::Path;
 
 #[cfg(feature = "rusqlite")]
 use rusqlite::{Connection, Rows, Statement, Row};
@@ -21,8 +47,12 @@ use std::fmt::Write;
 
 pub use error::{Error, Result};
 
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
 #[cfg(feature = "rusqlite")]
-use crate::error::CheckError;
+pub use crate::error::{CheckError, ExecError};
 
 // this cannot be in the test mod b/c it is needed for the test trait impls (SQLPart::possibilities)
 #[cfg(test)]
@@ -34,25 +64,173 @@ fn option_iter<T: Clone>(input: Vec<Box<T>>) -> Vec<Option<T>> {
 
 // region Traits
 
-trait SQLPart {
+/// The trait is sealed: it can be used (e.g. via [render_part]), but not implemented outside this crate.
+/// This way, the trait can be extended without breaking changes.
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for super::SQLiteType {}
+    impl Sealed for super::Order {}
+    impl Sealed for super::OnConflict {}
+    impl Sealed for super::FKOnAction {}
+    impl Sealed for super::FKDeferrable {}
+    impl Sealed for super::PrimaryKey {}
+    impl Sealed for super::NotNull {}
+    impl Sealed for super::Unique {}
+    impl Sealed for super::ForeignKey {}
+    impl Sealed for super::Check {}
+    impl<C: Sealed> Sealed for super::Named<C> {}
+    impl Sealed for super::DefaultValue {}
+    impl Sealed for super::Collation {}
+    impl Sealed for super::Column {}
+    impl Sealed for super::CompositeForeignKey {}
+    impl Sealed for super::TableConstraint {}
+    impl Sealed for super::Table {}
+    impl Sealed for super::IndexColumn {}
+    impl Sealed for super::Index {}
+    impl Sealed for super::DropTable {}
+    impl Sealed for super::DropIndex {}
+    impl Sealed for super::AlterTableRename {}
+    impl Sealed for super::AlterTableRenameColumn {}
+    impl Sealed for super::AlterTableAddColumn {}
+    impl Sealed for super::TriggerEvent {}
+    impl Sealed for super::Trigger {}
+    impl Sealed for super::PragmaValue {}
+    impl Sealed for super::Pragma {}
+    impl Sealed for super::Schema {}
+    impl Sealed for super::AnySqlStatement {}
+    impl<S: Sealed + ?Sized> Sealed for Box<S> {}
+
+    /// Stores the `if_exists` parameter of the [SQLStatement](super::SQLStatement) Methods for [SQLPart::part_len](super::SQLPart::part_len)
+    /// and [SQLPart::part_str](super::SQLPart::part_str), Statements without a guard ignore it.
+    /// Kept out of the public API, so `if_exists` can only be set by building a Statement.
+    pub trait SetIfExists {
+        fn set_if_exists(&mut self, _if_exists: bool) {}
+    }
+
+    impl SetIfExists for super::AlterTableRename {}
+    impl SetIfExists for super::AlterTableRenameColumn {}
+    impl SetIfExists for super::AlterTableAddColumn {}
+    impl SetIfExists for super::Pragma {}
+
+    impl SetIfExists for super::Table {
+        fn set_if_exists(&mut self, if_exists: bool) {
+            self.if_exists = if_exists;
+        }
+    }
+
+    impl SetIfExists for super::Index {
+        fn set_if_exists(&mut self, if_exists: bool) {
+            self.if_exists = if_exists;
+        }
+    }
+
+    impl SetIfExists for super::DropTable {
+        fn set_if_exists(&mut self, if_exists: bool) {
+            self.if_exists = if_exists;
+        }
+    }
+
+    impl SetIfExists for super::DropIndex {
+        fn set_if_exists(&mut self, if_exists: bool) {
+            self.if_exists = if_exists;
+        }
+    }
+
+    impl SetIfExists for super::Trigger {
+        fn set_if_exists(&mut self, if_exists: bool) {
+            self.if_exists = if_exists;
+        }
+    }
+
+    impl SetIfExists for super::Schema {
+        fn set_if_exists(&mut self, if_exists: bool) {
+            for tbl in &mut self.tables {
+                tbl.if_exists = if_exists;
+            }
+            for idx in &mut self.indexes {
+                idx.if_exists = if_exists;
+            }
+        }
+    }
+
+    impl SetIfExists for super::AnySqlStatement {
+        fn set_if_exists(&mut self, if_exists: bool) {
+            self.as_statement_mut().set_if_exists(if_exists)
+        }
+    }
+
+    impl<S: super::SQLStatement + ?Sized> SetIfExists for Box<S> {
+        fn set_if_exists(&mut self, if_exists: bool) {
+            (**self).set_if_exists(if_exists)
+        }
+    }
+}
+
+use sealed::SetIfExists;
+
+/// Any part of a SQL statement (e.g. a [Column] or a [PrimaryKey]), which can be rendered on its own.
+/// This trait is sealed and cannot be implemented outside this crate, see [render_part] for usage.
+pub trait SQLPart: sealed::Sealed {
+    /// Calculates the exact length of the part as it is currently configured.
     fn part_len(&self) -> Result<usize>;
 
+    /// Appends the part to `sql`.
     fn part_str(&self, sql: &mut String) -> Result<()>;
 
-    // todo: for no-std
-    // fn part_arr(&self, sql: &mut [u8]) -> Result<()>;
+    /// Renders the part into a new [String], see [render_part].
+    fn to_sql_part(&self) -> Result<String> {
+        let mut ret: String = String::with_capacity(self.part_len()?);
+        self.part_str(&mut ret)?;
+        Ok(ret)
+    }
+
+    /// Writes the part as UTF-8 into the beginning of `buf` and returns the number of bytes written.
+    /// Fails with [Error::BufferTooSmall] if `buf` is shorter than [SQLPart::part_len], in which case `buf` is not modified.
+    /// The part is rendered via [SQLPart::part_str] and then copied, so this still allocates.
+    fn part_arr(&self, buf: &mut [u8]) -> Result<usize> {
+        let needed: usize = self.part_len()?;
+        if buf.len() < needed {
+            return Err(Error::BufferTooSmall { needed, available: buf.len() });
+        }
+        let mut sql: String = String::with_capacity(needed);
+        self.part_str(&mut sql)?;
+        buf[..needed].copy_from_slice(sql.as_bytes());
+        Ok(needed)
+    }
 
     #[cfg(test)]
-    fn possibilities(illegal_variants: bool) -> Vec<Box<Self>>;
+    fn possibilities(illegal_variants: bool) -> Vec<Box<Self>> where Self: Sized;
+}
+
+/// Controls how [SQLStatement::build_with_mode] wraps Statements in SQL Transactions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BuildMode {
+    /// All Statements are wrapped in a single Transaction, same as [SQLStatement::build] with `transaction` set
+    Single,
+    /// Every Statement is wrapped in its own Transaction, separated by newlines
+    PerStatement,
+}
+
+impl Default for BuildMode {
+    fn default() -> Self {
+        Self::Single
+    }
 }
 
 /// Any struct Implementing this trait can be converted into a SQL statement [String].
 /// Optionally, the statement can be wrapped in a SQL Transaction and/or guarded against already existing Tables with a `...IF NOT EXISTS...` guard.
-pub trait SQLStatement {
+/// SQLite has no such guard for some Statements (e.g. `ALTER TABLE` or `PRAGMA`), these ignore the `if_exists` parameter.
+///
+/// The Statement itself is rendered by its [SQLPart] impl, the Methods here add the Transaction and the trailing `;`.
+pub trait SQLStatement: SQLPart + sealed::SetIfExists {
     /// Calculates the exact length of the statement as it is currently configured.
     /// Any change to the configuration invalidates previously calculated lengths.
     /// Parameters are the same as in [SQLStatement::build].
-    fn len(&mut self, transaction: bool, if_exists: bool) -> Result<usize>;
+    fn len(&mut self, transaction: bool, if_exists: bool) -> Result<usize> {
+        self.set_if_exists(if_exists);
+        Ok(transaction as usize * 7 + self.part_len()? + 1 + transaction as usize * 5)
+    }
 
     /// Builds the SQL Statement as a [String].
     ///
@@ -60,10 +238,303 @@ pub trait SQLStatement {
     ///
     /// * `transaction`: Weather the SQL-Statement should be wrapped in a SQL-Transaction
     /// * `if_exists`: Weather the `CREATE TABLE...` Statement should include a `...IF NOT EXISTS...` guard
-    fn build(&mut self, transaction: bool, if_exists: bool) -> Result<String>;
+    fn build(&mut self, transaction: bool, if_exists: bool) -> Result<String> {
+        let mut ret: String = String::new();
+        self.build_into(&mut ret, transaction, if_exists)?;
+        Ok(ret)
+    }
+
+    /// Calculates the exact length of the statement as built by [SQLStatement::build_with_mode].
+    fn len_with_mode(&mut self, _mode: BuildMode, if_exists: bool) -> Result<usize> {
+        // a single Statement is the same in both modes
+        self.len(true, if_exists)
+    }
+
+    /// Builds the SQL Statement as a [String], with the Transactions controlled by [BuildMode].
+    /// `if_exists` is the same as in [SQLStatement::build].
+    fn build_with_mode(&mut self, _mode: BuildMode, if_exists: bool) -> Result<String> {
+        // a single Statement is the same in both modes
+        self.build(true, if_exists)
+    }
+
+    /// Builds the SQL Statement as a formatted, multi-line [String], e.g. with every [Column] of a [Table] on its own line, indented by `indent` spaces.
+    /// Statements without a multi-line format are built like [SQLStatement::build].
+    /// `transaction` and `if_exists` are the same as in [SQLStatement::build].
+    fn build_pretty(&mut self, transaction: bool, if_exists: bool, _indent: usize) -> Result<String> {
+        self.build(transaction, if_exists)
+    }
+
+    /// Checks whether the Statement only uses features supported by the given SQLite `version`,
+    /// otherwise all unsupported features are returned. Statements without version-dependent features always succeed.
+    fn check_version_compatibility(&self, _version: SQLiteVersion) -> Result<(), Vec<CompatibilityWarning>> {
+        Ok(())
+    }
+
+    /// Builds the SQL Statement and appends it to `buf`, reusing its allocation.
+    /// If an Error occurs, `buf` may contain part of the Statement.
+    /// Parameters are the same as in [SQLStatement::build].
+    fn build_into(&mut self, buf: &mut String, transaction: bool, if_exists: bool) -> Result<()> {
+        buf.reserve(self.len(transaction, if_exists)?);
+        if transaction {
+            buf.push_str("BEGIN;\n");
+        }
+        self.part_str(buf)?;
+        buf.push(';');
+        if transaction {
+            buf.push_str("\nEND;");
+        }
+        Ok(())
+    }
+
+    /// Builds the SQL Statement and writes it to any [fmt::Write] target.
+    /// Parameters are the same as in [SQLStatement::build].
+    fn write_to<W: fmt::Write>(&mut self, w: &mut W, transaction: bool, if_exists: bool) -> Result<()> where Self: Sized {
+        w.write_str(self.build(transaction, if_exists)?.as_str())?;
+        Ok(())
+    }
+
+    /// Builds the SQL Statement and executes it on the given [Connection].
+    /// Parameters are the same as in [SQLStatement::build].
+    #[cfg(feature = "rusqlite")]
+    fn execute(&mut self, transaction: bool, if_exists: bool, conn: &Connection) -> Result<(), ExecError> {
+        let sql: String = self.build(transaction, if_exists)?;
+        conn.execute_batch(sql.as_str())?;
+        Ok(())
+    }
+
+    /// Builds the SQL Statement as UTF-8 into the beginning of `buf` and returns the number of bytes written.
+    /// Fails with [Error::BufferTooSmall] if `buf` is shorter than [SQLStatement::len], in which case `buf` is not modified.
+    /// Parameters are the same as in [SQLStatement::build].
+    /// The Statement is rendered via [SQLStatement::build] and then copied, so this still allocates.
+    fn build_arr(&mut self, buf: &mut [u8], transaction: bool, if_exists: bool) -> Result<usize> {
+        let needed: usize = self.len(transaction, if_exists)?;
+        if buf.len() < needed {
+            return Err(Error::BufferTooSmall { needed, available: buf.len() });
+        }
+        let sql: String = self.build(transaction, if_exists)?;
+        buf[..needed].copy_from_slice(sql.as_bytes());
+        Ok(needed)
+    }
+}
+
+/// Forwards to the boxed part, see the [SQLStatement] impl.
+impl<S: SQLPart + ?Sized> SQLPart for Box<S> {
+    fn part_len(&self) -> Result<usize> {
+        (**self).part_len()
+    }
+
+    fn part_str(&self, sql: &mut String) -> Result<()> {
+        (**self).part_str(sql)
+    }
+
+    #[cfg(test)]
+    fn possibilities(_: bool) -> Vec<Box<Self>> {
+        // the boxed parts are tested on their own
+        Vec::new()
+    }
+}
+
+/// Forwards to the boxed Statement, so e.g. a `Vec<Box<dyn SQLStatement>>` can hold [Table]s, [Index]es and [Schema]s at the same time.
+impl<S: SQLStatement + ?Sized> SQLStatement for Box<S> {
+    fn len(&mut self, transaction: bool, if_exists: bool) -> Result<usize> {
+        (**self).len(transaction, if_exists)
+    }
+
+    fn build(&mut self, transaction: bool, if_exists: bool) -> Result<String> {
+        (**self).build(transaction, if_exists)
+    }
+
+    fn len_with_mode(&mut self, mode: BuildMode, if_exists: bool) -> Result<usize> {
+        (**self).len_with_mode(mode, if_exists)
+    }
+
+    fn build_with_mode(&mut self, mode: BuildMode, if_exists: bool) -> Result<String> {
+        (**self).build_with_mode(mode, if_exists)
+    }
+
+    fn build_pretty(&mut self, transaction: bool, if_exists: bool, indent: usize) -> Result<String> {
+        (**self).build_pretty(transaction, if_exists, indent)
+    }
+
+    fn check_version_compatibility(&self, version: SQLiteVersion) -> Result<(), Vec<CompatibilityWarning>> {
+        (**self).check_version_compatibility(version)
+    }
+
+    fn build_into(&mut self, buf: &mut String, transaction: bool, if_exists: bool) -> Result<()> {
+        (**self).build_into(buf, transaction, if_exists)
+    }
+
+    #[cfg(feature = "rusqlite")]
+    fn execute(&mut self, transaction: bool, if_exists: bool, conn: &Connection) -> Result<(), ExecError> {
+        (**self).execute(transaction, if_exists, conn)
+    }
+
+    fn build_arr(&mut self, buf: &mut [u8], transaction: bool, if_exists: bool) -> Result<usize> {
+        (**self).build_arr(buf, transaction, if_exists)
+    }
+}
+
+/// Normalizes a keyword for the [FromStr] impls: trimmed, lowercase and without spaces or underscores.
+fn normalize_keyword(s: &str) -> String {
+    s.trim().to_lowercase().replace([' ', '_'], "")
+}
+
+/// Stores keyword enums as their bare SQL keyword (e.g. `INTEGER` or `ABORT`, see [fmt::Display] and [OnConflict::keyword]) and parses them back via [FromStr].
+#[cfg(feature = "rusqlite")]
+macro_rules! impl_sql_keyword {
+    ($($typ:ty),+) => {
+        $(
+            impl_sql_keyword!($typ => |keyword: &$typ| keyword.to_string());
+        )+
+    };
+    ($typ:ty => $to_keyword:expr) => {
+        impl rusqlite::types::ToSql for $typ {
+            fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+                Ok(rusqlite::types::ToSqlOutput::from(($to_keyword)(self)))
+            }
+        }
+
+        impl rusqlite::types::FromSql for $typ {
+            fn column_result(value: rusqlite::types::ValueRef<'_>) -> rusqlite::types::FromSqlResult<Self> {
+                Self::from_str(value.as_str()?).map_err(|err| rusqlite::types::FromSqlError::Other(Box::new(err)))
+            }
+        }
+    };
+}
+
+#[cfg(feature = "rusqlite")]
+impl_sql_keyword!(SQLiteType, Order, TriggerEvent);
+
+#[cfg(feature = "rusqlite")]
+impl_sql_keyword!(OnConflict => |keyword: &OnConflict| keyword.keyword().to_string());
+
+/// Renders a single [SQLPart] into a new [String], e.g. `PRIMARY KEY ASC ON CONFLICT ABORT` for a [PrimaryKey].
+pub fn render_part<P: SQLPart>(part: &P) -> Result<String> {
+    part.to_sql_part()
+}
+
+/// Formats a [SQLPart] exactly like [SQLPart::part_str], used to implement [fmt::Display].
+fn fmt_part<P: SQLPart>(part: &P, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.pad(render_part(part).map_err(|_| fmt::Error)?.as_str())
+}
+
+/// Appends `comment` as a SQL line comment, every line of `comment` gets its own `-- ` prefix.
+fn push_comment(sql: &mut String, comment: &str) {
+    sql.push_str("-- ");
+    sql.push_str(comment.replace('\n', "\n-- ").as_str());
+}
+
+/// Length of the `schema.` prefix qualifying a name, the schema name is quoted if needed (see [Quote::for_name]).
+fn schema_prefix_len(schema: Option<&str>) -> usize {
+    schema.map_or(0, |schema| Quote::for_name(schema).ident_len(schema) + 1)
+}
+
+/// Appends the `schema.` prefix qualifying a name, see [schema_prefix_len].
+fn push_schema_prefix(sql: &mut String, schema: Option<&str>) {
+    if let Some(schema) = schema {
+        Quote::for_name(schema).push_ident(sql, schema);
+        sql.push('.');
+    }
+}
+
+/// Returns all `requirements` not met by `version`, used to implement [SQLStatement::check_version_compatibility].
+fn check_requirements(requirements: Vec<CompatibilityWarning>, version: SQLiteVersion) -> Result<(), Vec<CompatibilityWarning>> {
+    let unmet: Vec<CompatibilityWarning> = requirements.into_iter().filter(|req| req.required > version).collect();
+    if unmet.is_empty() {
+        Ok(())
+    } else {
+        Err(unmet)
+    }
+}
+
+// endregion
+
+// region SQLiteVersion
+
+/// A SQLite library version, ordered by `major`, `minor`, then `patch`.
+/// See [Schema::min_required_version] and [SQLStatement::check_version_compatibility].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SQLiteVersion {
+    major: u8,
+    minor: u8,
+    patch: u8,
+}
+
+impl SQLiteVersion {
+    pub const fn new(major: u8, minor: u8, patch: u8) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    pub fn major(&self) -> u8 {
+        self.major
+    }
+
+    pub fn minor(&self) -> u8 {
+        self.minor
+    }
+
+    pub fn patch(&self) -> u8 {
+        self.patch
+    }
+}
+
+impl fmt::Display for SQLiteVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// A feature used by a Statement, which is only supported from the `required` [SQLiteVersion] onwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CompatibilityWarning {
+    feature: &'static str,
+    required: SQLiteVersion,
+}
+
+impl CompatibilityWarning {
+    // ref. https://www.sqlite.org/foreignkeys.html
+    const FOREIGN_KEY: Self = Self::new("FOREIGN KEY", SQLiteVersion::new(3, 6, 19));
+    // ref. https://www.sqlite.org/partialindex.html
+    const PARTIAL_INDEX: Self = Self::new("Partial Index", SQLiteVersion::new(3, 8, 0));
+    // ref. https://www.sqlite.org/withoutrowid.html
+    const WITHOUT_ROWID: Self = Self::new("WITHOUT ROWID", SQLiteVersion::new(3, 8, 2));
+    // ref. https://www.sqlite.org/stricttables.html
+    const STRICT: Self = Self::new("STRICT", SQLiteVersion::new(3, 37, 0));
+    // ref. https://www.sqlite.org/changes.html#version_3_1_0
+    const RENAME_TABLE: Self = Self::new("RENAME TO", SQLiteVersion::new(3, 1, 0));
+    // ref. https://www.sqlite.org/changes.html#version_3_2_0
+    const ADD_COLUMN: Self = Self::new("ADD COLUMN", SQLiteVersion::new(3, 2, 0));
+    // ref. https://www.sqlite.org/lang_altertable.html#alter_table_rename_column
+    const RENAME_COLUMN: Self = Self::new("RENAME COLUMN", SQLiteVersion::new(3, 25, 0));
+    // ref. https://www.sqlite.org/lang_altertable.html#alter_table_drop_column
+    const DROP_COLUMN: Self = Self::new("DROP COLUMN", SQLiteVersion::new(3, 35, 0));
+
+    const fn new(feature: &'static str, required: SQLiteVersion) -> Self {
+        Self {
+            feature,
+            required,
+        }
+    }
+
+    /// Name of the feature, e.g. `STRICT`
+    pub fn feature(&self) -> &'static str {
+        self.feature
+    }
 
-    // todo: for no-std
-    // fn build_arr(&self, arr: &mut [u8], transaction: bool) -> Result<()>;
+    /// The first [SQLiteVersion] supporting the feature
+    pub fn required(&self) -> SQLiteVersion {
+        self.required
+    }
+}
+
+impl fmt::Display for CompatibilityWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} requires SQLite {}", self.feature, self.required)
+    }
 }
 
 // endregion
@@ -71,8 +542,8 @@ pub trait SQLStatement {
 // region SQLiteType
 
 /// Encodes all Column-Datatypes available in SQLite, see [here](https://www.sqlite.org/datatype3.html#type_affinity).
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[cfg_attr(feature = "xml-config", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
 #[allow(missing_docs)]
 pub enum SQLiteType {
     // ref. https://www.sqlite.org/datatype3.html#type_affinity
@@ -90,25 +561,60 @@ impl Default for SQLiteType {
     }
 }
 
+impl SQLiteType {
+    /// The SQL keyword of the type, e.g. `"INTEGER"`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SQLiteType::Blob => { "BLOB" }
+            SQLiteType::Numeric => { "NUMERIC" }
+            SQLiteType::Integer => { "INTEGER" }
+            SQLiteType::Real => { "REAL" }
+            SQLiteType::Text => { "TEXT" }
+        }
+    }
+
+    /// The type affinity SQLite uses for Columns of this type.
+    /// Every variant is an affinity of its own, so this is always `self`.
+    pub fn affinity(&self) -> SQLiteType {
+        *self
+    }
+
+    /// The name of the affinity group of this type, e.g. `"INTEGER"` for `INT` as well as `INTEGER` Columns
+    pub fn affinity_name(&self) -> &'static str {
+        self.affinity().as_str()
+    }
+
+    /// Whether both types share the same affinity, e.g. a Column declared as `INT` in the DB is compatible with [SQLiteType::Integer]
+    pub fn compatible_with(&self, other: SQLiteType) -> bool {
+        self.affinity() == other.affinity()
+    }
+
+    /// Determines the affinity of any declared type name (e.g. `"VARCHAR(255)"` or `"DOUBLE PRECISION"`),
+    /// following the rules [here](https://www.sqlite.org/datatype3.html#determination_of_column_affinity).
+    /// The rules cover every possible name, unknown ones get [SQLiteType::Numeric] affinity.
+    pub fn from_affinity_str(s: &str) -> SQLiteType {
+        let upper: String = s.to_uppercase();
+        if upper.contains("INT") {
+            SQLiteType::Integer
+        } else if upper.contains("CHAR") || upper.contains("CLOB") || upper.contains("TEXT") {
+            SQLiteType::Text
+        } else if upper.contains("BLOB") || upper.trim().is_empty() {
+            SQLiteType::Blob
+        } else if upper.contains("REAL") || upper.contains("FLOA") || upper.contains("DOUB") {
+            SQLiteType::Real
+        } else {
+            SQLiteType::Numeric
+        }
+    }
+}
+
 impl SQLPart for SQLiteType {
     fn part_len(&self) -> Result<usize> {
-        Ok(match self {
-            SQLiteType::Blob => { 4 }
-            SQLiteType::Numeric => { 7 }
-            SQLiteType::Integer => { 7 }
-            SQLiteType::Real => { 4 }
-            SQLiteType::Text => { 4 }
-        })
+        Ok(self.as_str().len())
     }
 
     fn part_str(&self, sql: &mut String) -> Result<()> {
-        match self {
-            SQLiteType::Blob => { sql.push_str("BLOB") }
-            SQLiteType::Numeric => { sql.push_str("NUMERIC") }
-            SQLiteType::Integer => { sql.push_str("INTEGER") }
-            SQLiteType::Real => { sql.push_str("REAL") }
-            SQLiteType::Text => { sql.push_str("TEXT") }
-        };
+        sql.push_str(self.as_str());
         Ok(())
     }
 
@@ -118,13 +624,35 @@ impl SQLPart for SQLiteType {
     }
 }
 
+impl fmt::Display for SQLiteType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_part(self, f)
+    }
+}
+
+impl FromStr for SQLiteType {
+    type Err = Error;
+
+    /// Case-insensitive, surrounding whitespace is ignored
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match normalize_keyword(s).as_str() {
+            "blob" => { Self::Blob }
+            "numeric" => { Self::Numeric }
+            "integer" => { Self::Integer }
+            "real" => { Self::Real }
+            "text" => { Self::Text }
+            _ => { return Err(Error::UnknownKeyword(s.to_string())) }
+        })
+    }
+}
+
 // endregion
 
 // region Order
 
 /// [PrimaryKey] direction
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[cfg_attr(feature = "xml-config", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
 #[allow(missing_docs)]
 pub enum Order {
     Ascending,
@@ -159,14 +687,33 @@ impl SQLPart for Order {
     }
 }
 
+impl fmt::Display for Order {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_part(self, f)
+    }
+}
+
+impl FromStr for Order {
+    type Err = Error;
+
+    /// Accepts both the SQL Keyword (`ASC`) and the variant name (`Ascending`), case-insensitive, surrounding whitespace is ignored
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match normalize_keyword(s).as_str() {
+            "asc" | "ascending" => { Self::Ascending }
+            "desc" | "descending" => { Self::Descending }
+            _ => { return Err(Error::UnknownKeyword(s.to_string())) }
+        })
+    }
+}
+
 // endregion
 
 // region OnConflict
 
 /// Reaction to a violated Constraint, used by [PrimaryKey], [NotNull] and [Unique].
 /// See also [here](https://www.sqlite.org/lang_conflict.html)
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[cfg_attr(feature = "xml-config", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
 #[allow(missing_docs)]
 pub enum OnConflict {
     Rollback,
@@ -183,6 +730,19 @@ impl Default for OnConflict {
     }
 }
 
+impl OnConflict {
+    /// The bare resolution keyword without the `ON CONFLICT` prefix, e.g. `REPLACE`
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            OnConflict::Rollback => { "ROLLBACK" }
+            OnConflict::Abort => { "ABORT" }
+            OnConflict::Fail => { "FAIL" }
+            OnConflict::Ignore => { "IGNORE" }
+            OnConflict::Replace => { "REPLACE" }
+        }
+    }
+}
+
 impl SQLPart for OnConflict {
     fn part_len(&self) -> Result<usize> {
         Ok(match self {
@@ -211,14 +771,37 @@ impl SQLPart for OnConflict {
     }
 }
 
+impl fmt::Display for OnConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_part(self, f)
+    }
+}
+
+impl FromStr for OnConflict {
+    type Err = Error;
+
+    /// Accepts the resolution with or without the `ON CONFLICT` prefix, case-insensitive, surrounding whitespace is ignored
+    fn from_str(s: &str) -> Result<Self> {
+        let keyword: String = normalize_keyword(s);
+        Ok(match keyword.strip_prefix("onconflict").unwrap_or(keyword.as_str()) {
+            "rollback" => { Self::Rollback }
+            "abort" => { Self::Abort }
+            "fail" => { Self::Fail }
+            "ignore" => { Self::Ignore }
+            "replace" => { Self::Replace }
+            _ => { return Err(Error::UnknownKeyword(s.to_string())) }
+        })
+    }
+}
+
 // endregion
 
 // region FK OnAction
 
 /// Reaction to an action on a Column with a [ForeignKey]
 /// See also [here](https://www.sqlite.org/foreignkeys.html#fk_actions)
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[cfg_attr(feature = "xml-config", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[allow(missing_docs)]
 pub enum FKOnAction {
     SetNull,
@@ -263,34 +846,248 @@ impl SQLPart for FKOnAction {
     }
 }
 
+impl fmt::Display for FKOnAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_part(self, f)
+    }
+}
+
+impl FromStr for FKOnAction {
+    type Err = Error;
+
+    /// Accepts both the SQL Keywords (`SET NULL`) and the variant name (`SetNull`), case-insensitive, surrounding whitespace is ignored
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match normalize_keyword(s).as_str() {
+            "setnull" => { Self::SetNull }
+            "setdefault" => { Self::SetDefault }
+            "cascade" => { Self::Cascade }
+            "restrict" => { Self::Restrict }
+            "noaction" => { Self::NoAction }
+            _ => { return Err(Error::UnknownKeyword(s.to_string())) }
+        })
+    }
+}
+
 // endregion
 
-// region Primary Key
+// region FK Deferrable
 
-/// Marks a Column as a Primary Key.
-/// It is an Error to have more than one Primary Key per [Table] ([Error::MultiplePrimaryKeys]).
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
-#[cfg_attr(feature = "xml-config", derive(Serialize, Deserialize))]
-pub struct PrimaryKey {
-    #[cfg_attr(feature = "xml-config", serde(default, rename = "@order"))]
-    sort_order: Order,
-    #[cfg_attr(feature = "xml-config", serde(default, rename = "@on_conflict"))]
-    on_conflict: OnConflict,
-    #[cfg_attr(feature = "xml-config", serde(default, rename = "@autoincrement"))]
-    autoincrement: bool, // default false
+/// Whether the enforcement of a [ForeignKey] is deferred until the enclosing Transaction is committed.
+/// See also [here](https://www.sqlite.org/foreignkeys.html#fk_deferred)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
+#[allow(missing_docs)]
+pub enum FKDeferrable {
+    #[cfg_attr(feature = "serde", serde(alias = "false"))]
+    NotDeferrable,
+    InitiallyImmediate,
+    #[cfg_attr(feature = "serde", serde(alias = "true"))]
+    InitiallyDeferred,
 }
 
-impl PrimaryKey {
-    pub fn new(sort_order: Order, on_conflict: OnConflict, autoincrement: bool) -> Self {
-        Self {
-            sort_order,
-            on_conflict,
-            autoincrement,
-        }
+impl Default for FKDeferrable {
+    fn default() -> Self {
+        // ref. https://www.sqlite.org/foreignkeys.html#fk_deferred
+        Self::NotDeferrable
     }
+}
 
-    pub fn set_sort_order(mut self, ord: Order) -> Self {
-        self.sort_order = ord;
+impl From<bool> for FKDeferrable {
+    /// `true` maps to [FKDeferrable::InitiallyDeferred], `false` to [FKDeferrable::NotDeferrable]
+    fn from(deferrable: bool) -> Self {
+        if deferrable {
+            Self::InitiallyDeferred
+        } else {
+            Self::NotDeferrable
+        }
+    }
+}
+
+impl SQLPart for FKDeferrable {
+    fn part_len(&self) -> Result<usize> {
+        Ok(match self {
+            FKDeferrable::NotDeferrable => { 0 } // emits nothing, same as the SQLite default
+            FKDeferrable::InitiallyImmediate => { 30 }
+            FKDeferrable::InitiallyDeferred => { 29 }
+        })
+    }
+
+    fn part_str(&self, sql: &mut String) -> Result<()> {
+        match self {
+            FKDeferrable::NotDeferrable => {}
+            FKDeferrable::InitiallyImmediate => { sql.push_str("DEFERRABLE INITIALLY IMMEDIATE") }
+            FKDeferrable::InitiallyDeferred => { sql.push_str("DEFERRABLE INITIALLY DEFERRED") }
+        };
+        Ok(())
+    }
+
+    #[cfg(test)]
+    fn possibilities(_: bool) -> Vec<Box<Self>> {
+        vec![Box::new(Self::NotDeferrable), Box::new(Self::InitiallyImmediate), Box::new(Self::InitiallyDeferred)]
+    }
+}
+
+impl fmt::Display for FKDeferrable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_part(self, f)
+    }
+}
+
+// endregion
+
+// region Named
+
+/// A Column constraint ([PrimaryKey], [NotNull], [Unique], [ForeignKey] or [Check]) with an optional name, emitted as `CONSTRAINT name ...`.
+/// Every constraint converts into an unnamed one via [From], see [ColumnConstraint::with_name] for a named one.
+/// Named constraints are added to a [Column] via the `set_*_named` Methods, e.g. [Column::set_pk_named].
+/// The constraint is accessible via [Deref](std::ops::Deref). It is a Error for the name to be Empty ([Error::EmptyConstraintName]).
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Named<C> {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    constraint: C,
+    #[cfg_attr(feature = "serde", serde(rename = "constraint_name", default, skip_serializing_if = "Option::is_none"))]
+    name: Option<String>,
+    #[cfg_attr(feature = "serde", serde(rename = "constraint_quote", default))]
+    name_quote: Quote,
+}
+
+impl<C> Named<C> {
+    fn check_name(&self) -> Result<()> {
+        if self.name.as_deref() == Some("") {
+            return Err(Error::EmptyConstraintName);
+        }
+        Ok(())
+    }
+
+    pub fn new(constraint: C, name: Option<String>) -> Self {
+        Self {
+            constraint,
+            name,
+            name_quote: Quote::None,
+        }
+    }
+
+    pub fn set_name(mut self, name: Option<String>) -> Self {
+        self.name = name;
+        self
+    }
+
+    pub fn set_name_quote(mut self, quote: Quote) -> Self {
+        self.name_quote = quote;
+        self
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn name_quote(&self) -> Quote {
+        self.name_quote
+    }
+
+    pub fn constraint(&self) -> &C {
+        &self.constraint
+    }
+
+    pub fn into_constraint(self) -> C {
+        self.constraint
+    }
+}
+
+impl<C> From<C> for Named<C> {
+    fn from(constraint: C) -> Self {
+        Self::new(constraint, None)
+    }
+}
+
+impl<C> std::ops::Deref for Named<C> {
+    type Target = C;
+
+    fn deref(&self) -> &C {
+        &self.constraint
+    }
+}
+
+impl<C> std::ops::DerefMut for Named<C> {
+    fn deref_mut(&mut self) -> &mut C {
+        &mut self.constraint
+    }
+}
+
+impl<C: SQLPart + Clone> SQLPart for Named<C> {
+    fn part_len(&self) -> Result<usize> {
+        self.check_name()?;
+        let name_len: usize = self.name.as_deref().map_or(0, |name| 11 + self.name_quote.ident_len(name) + 1); // "CONSTRAINT " and ' '
+        Ok(name_len + self.constraint.part_len()?)
+    }
+
+    fn part_str(&self, sql: &mut String) -> Result<()> {
+        self.check_name()?;
+        if let Some(name) = self.name.as_deref() {
+            sql.push_str("CONSTRAINT ");
+            self.name_quote.push_ident(sql, name);
+            sql.push(' ');
+        }
+        self.constraint.part_str(sql)
+    }
+
+    #[cfg(test)]
+    fn possibilities(illegal: bool) -> Vec<Box<Self>> {
+        let mut ret: Vec<Box<Self>> = C::possibilities(illegal).into_iter().map(|constraint| Box::new(Self::from(*constraint))).collect();
+        if let Some(constraint) = C::possibilities(false).into_iter().next() {
+            ret.push(Box::new(Self::new(*constraint.clone(), Some("test_constraint".to_string()))));
+            ret.push(Box::new(Self::new(*constraint.clone(), Some("test constraint".to_string())).set_name_quote(Quote::DoubleQuote)));
+            if illegal {
+                ret.push(Box::new(Self::new(*constraint, Some("".to_string()))));
+            }
+        }
+        ret
+    }
+}
+
+/// The constraints of a single [Column], which can be named via [Named].
+pub trait ColumnConstraint: SQLPart + Sized {
+    /// Names the Constraint, emitted as `CONSTRAINT name ...`
+    fn with_name(self, name: String) -> Named<Self> {
+        Named::new(self, Some(name))
+    }
+}
+
+impl ColumnConstraint for PrimaryKey {}
+impl ColumnConstraint for NotNull {}
+impl ColumnConstraint for Unique {}
+impl ColumnConstraint for ForeignKey {}
+impl ColumnConstraint for Check {}
+
+// endregion
+
+// region Primary Key
+
+/// Marks a Column as a Primary Key.
+/// It is an Error to have more than one Primary Key per [Table] ([Error::MultiplePrimaryKeys]).
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PrimaryKey {
+    #[cfg_attr(feature = "serde", serde(default))]
+    sort_order: Order,
+    #[cfg_attr(feature = "serde", serde(default))]
+    on_conflict: OnConflict,
+    #[cfg_attr(feature = "serde", serde(default))]
+    autoincrement: bool, // default false
+}
+
+impl PrimaryKey {
+    pub fn new(sort_order: Order, on_conflict: OnConflict, autoincrement: bool) -> Self {
+        Self {
+            sort_order,
+            on_conflict,
+            autoincrement,
+        }
+    }
+
+    pub fn set_sort_order(mut self, ord: Order) -> Self {
+        self.sort_order = ord;
         self
     }
 
@@ -303,6 +1100,18 @@ impl PrimaryKey {
         self.autoincrement = autoinc;
         self
     }
+
+    pub fn sort_order(&self) -> Order {
+        self.sort_order
+    }
+
+    pub fn on_conflict(&self) -> OnConflict {
+        self.on_conflict
+    }
+
+    pub fn is_autoincrement(&self) -> bool {
+        self.autoincrement
+    }
 }
 
 impl SQLPart for PrimaryKey {
@@ -340,10 +1149,10 @@ impl SQLPart for PrimaryKey {
 // region Not Null
 
 /// Marks a [Column] as `NOT NULL`, e.g. the Column cannot contain `NULL` values and trying to insert `NULL` values is a Error.
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
-#[cfg_attr(feature = "xml-config", derive(Serialize, Deserialize))]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NotNull {
-    #[cfg_attr(feature = "xml-config", serde(default, rename = "@on_conflict"))]
+    #[cfg_attr(feature = "serde", serde(default))]
     on_conflict: OnConflict,
 }
 
@@ -358,6 +1167,10 @@ impl NotNull {
         self.on_conflict = on_conf;
         self
     }
+
+    pub fn on_conflict(&self) -> OnConflict {
+        self.on_conflict
+    }
 }
 
 impl SQLPart for NotNull {
@@ -386,10 +1199,10 @@ impl SQLPart for NotNull {
 // region Unique
 
 /// Marks a [Column] as "Unique", e.g. the Column cannot contain the same value twice and trying to insert a value for the second time is a Error.
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
-#[cfg_attr(feature = "xml-config", derive(Serialize, Deserialize))]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Unique {
-    #[cfg_attr(feature = "xml-config", serde(default, rename = "@on_conflict"))]
+    #[cfg_attr(feature = "serde", serde(default))]
     on_conflict: OnConflict,
 }
 
@@ -404,6 +1217,10 @@ impl Unique {
         self.on_conflict = on_conf;
         self
     }
+
+    pub fn on_conflict(&self) -> OnConflict {
+        self.on_conflict
+    }
 }
 
 impl SQLPart for Unique {
@@ -432,19 +1249,18 @@ impl SQLPart for Unique {
 // region Foreign Key
 
 /// Defines a Foreign Key for a [Column]. It is a Error for the `foreign_table` and `foreign_column` [String]s to be Empty ([Error::EmptyForeignTableName], [Error::EmptyForeignColumnName]).
-#[derive(Debug, Clone, PartialEq, Eq)]
-#[cfg_attr(feature = "xml-config", derive(Serialize, Deserialize))]
+/// The `quote` is used for both the `foreign_table` and the `foreign_column`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ForeignKey {
-    #[cfg_attr(feature = "xml-config", serde(rename = "@foreign_table"))]
     foreign_table: String,
-    #[cfg_attr(feature = "xml-config", serde(rename = "@foreign_column"))]
     foreign_column: String,
-    #[cfg_attr(feature = "xml-config", serde(rename = "@on_delete"))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    quote: Quote,
     on_delete: Option<FKOnAction>,
-    #[cfg_attr(feature = "xml-config", serde(rename = "@on_update"))]
     on_update: Option<FKOnAction>,
-    #[cfg_attr(feature = "xml-config", serde(rename = "@deferrable", default))]
-    deferrable: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    deferrable: FKDeferrable,
 }
 
 impl ForeignKey {
@@ -458,10 +1274,11 @@ impl ForeignKey {
         Ok(())
     }
 
-    pub fn new(foreign_table: String, foreign_column: String, on_delete: Option<FKOnAction>, on_update: Option<FKOnAction>, deferrable: bool) -> Self {
+    pub fn new(foreign_table: String, foreign_column: String, on_delete: Option<FKOnAction>, on_update: Option<FKOnAction>, deferrable: FKDeferrable) -> Self {
         Self {
             foreign_table,
             foreign_column,
+            quote: Quote::None,
             on_delete,
             on_update,
             deferrable,
@@ -472,6 +1289,7 @@ impl ForeignKey {
         Self {
             foreign_table,
             foreign_column,
+            quote: Quote::None,
             on_delete: Default::default(),
             on_update: Default::default(),
             deferrable: Default::default(),
@@ -488,6 +1306,12 @@ impl ForeignKey {
         self
     }
 
+    /// Sets how the `foreign_table` and `foreign_column` are quoted, see [Schema::auto_quote]
+    pub fn set_quote(mut self, quote: Quote) -> Self {
+        self.quote = quote;
+        self
+    }
+
     pub fn set_on_delete(mut self, on_delete: Option<FKOnAction>) -> Self {
         self.on_delete = on_delete;
         self
@@ -498,55 +1322,122 @@ impl ForeignKey {
         self
     }
 
-    pub fn set_deferrable(mut self, deferrable: bool) -> Self {
+    /// Same as [ForeignKey::new_default], accepting anything convertible into a [String], e.g. `ForeignKey::references("users", "id")`
+    pub fn references(table: impl Into<String>, column: impl Into<String>) -> Self {
+        Self::new_default(table.into(), column.into())
+    }
+
+    /// Shorthand for [ForeignKey::set_on_delete] with an action
+    pub fn with_on_delete(self, action: FKOnAction) -> Self {
+        self.set_on_delete(Some(action))
+    }
+
+    /// Shorthand for [ForeignKey::set_on_update] with an action
+    pub fn with_on_update(self, action: FKOnAction) -> Self {
+        self.set_on_update(Some(action))
+    }
+
+    pub fn set_deferrable(mut self, deferrable: FKDeferrable) -> Self {
         self.deferrable = deferrable;
         self
     }
+
+    pub fn foreign_table(&self) -> &str {
+        self.foreign_table.as_str()
+    }
+
+    pub fn foreign_column(&self) -> &str {
+        self.foreign_column.as_str()
+    }
+
+    pub fn quote(&self) -> Quote {
+        self.quote
+    }
+
+    pub fn on_delete(&self) -> Option<FKOnAction> {
+        self.on_delete
+    }
+
+    pub fn on_update(&self) -> Option<FKOnAction> {
+        self.on_update
+    }
+
+    pub fn deferrable(&self) -> FKDeferrable {
+        self.deferrable
+    }
+
+    /// Whether the Foreign Key is deferrable, regardless of the initial mode
+    pub fn is_deferrable(&self) -> bool {
+        self.deferrable != FKDeferrable::NotDeferrable
+    }
 }
 
-impl SQLPart for ForeignKey {
-    fn part_len(&self) -> Result<usize> {
-        self.check()?;
+impl ForeignKey {
+    /// Length of the ` ON DELETE ...`, ` ON UPDATE ...` and ` DEFERRABLE ...` clauses, shared with [CompositeForeignKey]
+    fn actions_len(on_delete: Option<FKOnAction>, on_update: Option<FKOnAction>, deferrable: FKDeferrable) -> Result<usize> {
+        let on_del_len: usize = if let Some(on_del) = on_delete {
+            11 + on_del.part_len()? // " ON DELETE "
+        } else {
+            0
+        };
 
-        let on_del_len: usize = if let Some(on_del) = self.on_delete.as_ref() {
-            on_del.part_len()? + 1
+        let on_upd_len: usize = if let Some(on_upd) = on_update {
+            11 + on_upd.part_len()? // " ON UPDATE "
         } else {
             0
         };
 
-        let on_upd_len: usize = if let Some(on_upd) = self.on_update.as_ref() {
-            on_upd.part_len()? + 1
+        let defer_len: usize = if deferrable != FKDeferrable::NotDeferrable {
+            deferrable.part_len()? + 1
         } else {
             0
         };
 
-        Ok(11 + self.foreign_table.len() + 2 + self.foreign_column.len() + 1 + on_del_len + on_upd_len + self.deferrable as usize * 30)
+        Ok(on_del_len + on_upd_len + defer_len)
     }
 
-    fn part_str(&self, sql: &mut String) -> Result<()> {
-        self.check()?;
-        sql.push_str("REFERENCES ");
-        sql.push_str(self.foreign_table.as_str());
-        sql.push_str(" (");
-        sql.push_str(self.foreign_column.as_str());
-        sql.push(')');
-
-        if let Some(on_del) = self.on_delete.as_ref() {
-            sql.push(' ');
+    /// Writes the ` ON DELETE ...`, ` ON UPDATE ...` and ` DEFERRABLE ...` clauses, shared with [CompositeForeignKey]
+    fn actions_str(on_delete: Option<FKOnAction>, on_update: Option<FKOnAction>, deferrable: FKDeferrable, sql: &mut String) -> Result<()> {
+        if let Some(on_del) = on_delete {
+            sql.push_str(" ON DELETE ");
             on_del.part_str(sql)?;
         }
 
-        if let Some(on_upd) = self.on_update.as_ref() {
-            sql.push(' ');
+        if let Some(on_upd) = on_update {
+            sql.push_str(" ON UPDATE ");
             on_upd.part_str(sql)?;
         }
 
-        if self.deferrable {
-            sql.push_str(" DEFERRABLE INITIALLY DEFERRED");
+        if deferrable != FKDeferrable::NotDeferrable {
+            sql.push(' ');
+            deferrable.part_str(sql)?;
         }
-
         Ok(())
     }
+}
+
+impl SQLPart for ForeignKey {
+    fn part_len(&self) -> Result<usize> {
+        self.check()?;
+        Ok(
+            11 // "REFERENCES "
+            + self.quote.ident_len(self.foreign_table.as_str())
+            + 2 // " ("
+            + self.quote.ident_len(self.foreign_column.as_str())
+            + 1 // ')'
+            + Self::actions_len(self.on_delete, self.on_update, self.deferrable)?
+        )
+    }
+
+    fn part_str(&self, sql: &mut String) -> Result<()> {
+        self.check()?;
+        sql.push_str("REFERENCES ");
+        self.quote.push_ident(sql, self.foreign_table.as_str());
+        sql.push_str(" (");
+        self.quote.push_ident(sql, self.foreign_column.as_str());
+        sql.push(')');
+        Self::actions_str(self.on_delete, self.on_update, self.deferrable, sql)
+    }
 
     #[cfg(test)]
     fn possibilities(illegal: bool) -> Vec<Box<Self>> {
@@ -555,926 +1446,7089 @@ impl SQLPart for ForeignKey {
             for col in [if illegal { "".to_string() } else { "test".to_string() } , "test".to_string()] {
                 for on_del in option_iter(FKOnAction::possibilities(false)) {
                     for on_upd in option_iter(FKOnAction::possibilities(false)) {
-                        for defer in [true, false] {
-                            ret.push(Box::new(Self::new(tbl.clone(), col.clone(), on_del, on_upd, defer)));
+                        for defer in FKDeferrable::possibilities(false) {
+                            ret.push(Box::new(Self::new(tbl.clone(), col.clone(), on_del, on_upd, *defer)));
                         }
                     }
                 }
             }
         }
+        ret.push(Box::new(Self::new_default("group".to_string(), "order".to_string()).set_quote(Quote::DoubleQuote)));
         ret
     }
 }
 
 // endregion
 
-// region Column
+// region Check
 
-/// This struct Represents a Column in a [Table]. It is a Error for the `name` to be Empty ([Error::EmptyColumnName]).
-#[derive(Debug, Clone, PartialEq, Eq)]
-#[cfg_attr(feature = "xml-config", derive(Serialize, Deserialize))]
-pub struct Column {
-    #[cfg_attr(feature = "xml-config", serde(rename = "@type"))]
-    typ: SQLiteType,
-    #[cfg_attr(feature = "xml-config", serde(rename = "@name"))]
-    name: String,
-    #[cfg_attr(feature = "xml-config", serde(skip_serializing_if = "Option::is_none"))]
-    pk: Option<PrimaryKey>,
-    #[cfg_attr(feature = "xml-config", serde(skip_serializing_if = "Option::is_none"))]
-    unique: Option<Unique>,
-    #[cfg_attr(feature = "xml-config", serde(skip_serializing_if = "Option::is_none"))]
-    fk: Option<ForeignKey>,
-    #[cfg_attr(feature = "xml-config", serde(skip_serializing_if = "Option::is_none"))]
-    not_null: Option<NotNull>,
-    // todo Generated Column
+/// Adds a `CHECK` Constraint to a [Column], e.g. every inserted value must satisfy the Expression, see [here](https://www.sqlite.org/lang_createtable.html#check_constraints).
+/// It is a Error for the `expr` to be Empty ([Error::EmptyCheckExpr]).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Check {
+    expr: String,
 }
 
-impl Column {
+impl Check {
     fn check(&self) -> Result<()> {
-        if self.name.is_empty() {
-            return Err(Error::EmptyColumnName)
-        }
-
-        if self.pk.is_some() && self.fk.is_some() {
-            return Err(Error::PrimaryKeyAndForeignKey)
-        }
-
-        if self.pk.is_some() && self.unique.is_some() {
-            return Err(Error::PrimaryKeyAndUnique)
+        if self.expr.is_empty() {
+            return Err(Error::EmptyCheckExpr);
         }
-
         Ok(())
     }
 
-    pub fn new(typ: SQLiteType, name: String, pk: Option<PrimaryKey>, unique: Option<Unique>, fk: Option<ForeignKey>, not_null: Option<NotNull>) -> Self {
+    pub fn new(expr: String) -> Self {
         Self {
-            typ,
-            name,
-            pk,
-            unique,
-            fk,
-            not_null,
+            expr,
         }
     }
 
-    pub fn new_default(name: String) -> Self {
-        Self {
-            typ: Default::default(),
-            name,
-            pk: Default::default(),
-            unique: Default::default(),
-            fk: Default::default(),
-            not_null: Default::default(),
-        }
+    pub fn set_expr(mut self, expr: String) -> Self {
+        self.expr = expr;
+        self
     }
+}
 
-    pub fn new_typed(typ: SQLiteType, name: String) -> Self {
-        Self {
-            typ,
-            name,
-            pk: Default::default(),
-            unique: Default::default(),
-            fk: Default::default(),
-            not_null: Default::default(),
-        }
+impl SQLPart for Check {
+    fn part_len(&self) -> Result<usize> {
+        self.check()?;
+        Ok(6 + self.expr.len() + 1)
     }
 
-    pub fn set_type(mut self, typ: SQLiteType) -> Self {
-        self.typ = typ;
-        self
+    fn part_str(&self, sql: &mut String) -> Result<()> {
+        self.check()?;
+        sql.push_str("CHECK(");
+        sql.push_str(self.expr.as_str());
+        sql.push(')');
+        Ok(())
     }
 
-    pub fn set_name(mut self, name: String) -> Self {
-        self.name = name;
-        self
+    #[cfg(test)]
+    fn possibilities(illegal: bool) -> Vec<Box<Self>> {
+        vec![
+            Box::new(Self::new(if illegal { "".to_string() } else { "1".to_string() })),
+            Box::new(Self::new("1 > 0".to_string())),
+        ]
     }
+}
 
-    pub fn set_pk(mut self, pk: Option<PrimaryKey>) -> Self {
-        self.pk = pk;
-        self
-    }
+// endregion
 
-    pub fn set_unique(mut self, unique: Option<Unique>) -> Self {
-        self.unique = unique;
-        self
+// region Default Value
+
+/// Default Value of a [Column], used when no value is given on insertion, see [here](https://www.sqlite.org/lang_createtable.html#the_default_clause).
+/// It is a Error for a [DefaultValue::Real] to be NaN or infinite ([Error::NonFiniteDefault]), as there are no SQL-literals for them.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
+pub enum DefaultValue {
+    /// `DEFAULT NULL`
+    Null,
+    /// A signed Integer literal
+    Integer(i64),
+    /// A signed Floating Point literal
+    Real(f64),
+    /// A String literal, single quotes are escaped
+    Text(String),
+    /// `DEFAULT CURRENT_TIMESTAMP`
+    CurrentTimestamp,
+    /// `DEFAULT CURRENT_DATE`
+    CurrentDate,
+    /// `DEFAULT CURRENT_TIME`
+    CurrentTime,
+    /// A constant Expression, will be wrapped in parentheses
+    Expr(String),
+}
+
+impl DefaultValue {
+    fn check(&self) -> Result<()> {
+        if let DefaultValue::Real(real) = self {
+            if !real.is_finite() {
+                return Err(Error::NonFiniteDefault);
+            }
+        }
+        Ok(())
     }
 
-    pub fn set_fk(mut self, fk: Option<ForeignKey>) -> Self {
-        self.fk = fk;
-        self
+    /// Parses a default value as reported by SQLite (e.g. `'text'` or `current_timestamp`), everything unknown is a [DefaultValue::Expr].
+    #[cfg(feature = "rusqlite")]
+    fn from_db(value: &str) -> Self {
+        if value.eq_ignore_ascii_case("NULL") {
+            DefaultValue::Null
+        } else if value.eq_ignore_ascii_case("CURRENT_TIMESTAMP") {
+            DefaultValue::CurrentTimestamp
+        } else if value.eq_ignore_ascii_case("CURRENT_DATE") {
+            DefaultValue::CurrentDate
+        } else if value.eq_ignore_ascii_case("CURRENT_TIME") {
+            DefaultValue::CurrentTime
+        } else if let Ok(int) = value.parse::<i64>() {
+            DefaultValue::Integer(int)
+        } else if let Some(real) = value.parse::<f64>().ok().filter(|real| real.is_finite()) {
+            DefaultValue::Real(real)
+        } else if let Some(text) = value.strip_prefix('\'').and_then(|rest| rest.strip_suffix('\''))
+            // only a single literal, e.g. not `'a' || 'b'`
+            .filter(|text| !text.replace("''", "").contains('\'')) {
+            DefaultValue::Text(text.replace("''", "'"))
+        } else {
+            DefaultValue::Expr(value.to_string())
+        }
     }
 }
 
-impl SQLPart for Column {
+impl SQLPart for DefaultValue {
     fn part_len(&self) -> Result<usize> {
         self.check()?;
-        let pk_len: usize = if let Some(pk) = self.pk.as_ref() {
-            pk.part_len()? + 1
-        } else {
-            0
-        };
+        Ok(8 + match self { // "DEFAULT "
+            DefaultValue::Null => { 4 }
+            DefaultValue::Integer(int) => { int.to_string().len() }
+            DefaultValue::Real(real) => { format!("{:?}", real).len() }
+            DefaultValue::Text(text) => { 1 + text.len() + text.matches('\'').count() + 1 }
+            DefaultValue::CurrentTimestamp => { 17 }
+            DefaultValue::CurrentDate => { 12 }
+            DefaultValue::CurrentTime => { 12 }
+            DefaultValue::Expr(expr) => { 1 + expr.len() + 1 }
+        })
+    }
 
-        let unique_len: usize = if let Some(unique) = self.unique.as_ref() {
-            unique.part_len()? + 1
-        } else {
-            0
+    fn part_str(&self, sql: &mut String) -> Result<()> {
+        self.check()?;
+        sql.push_str("DEFAULT ");
+        match self {
+            DefaultValue::Null => { sql.push_str("NULL") }
+            DefaultValue::Integer(int) => { sql.push_str(int.to_string().as_str()) }
+            DefaultValue::Real(real) => { sql.push_str(format!("{:?}", real).as_str()) }
+            DefaultValue::Text(text) => {
+                sql.push('\'');
+                sql.push_str(text.replace('\'', "''").as_str());
+                sql.push('\'');
+            }
+            DefaultValue::CurrentTimestamp => { sql.push_str("CURRENT_TIMESTAMP") }
+            DefaultValue::CurrentDate => { sql.push_str("CURRENT_DATE") }
+            DefaultValue::CurrentTime => { sql.push_str("CURRENT_TIME") }
+            DefaultValue::Expr(expr) => {
+                sql.push('(');
+                sql.push_str(expr.as_str());
+                sql.push(')');
+            }
         };
+        Ok(())
+    }
 
-        let fk_len: usize = if let Some(fk) = self.fk.as_ref() {
-            fk.part_len()? + 1
-        } else {
-            0
-        };
+    #[cfg(test)]
+    fn possibilities(illegal: bool) -> Vec<Box<Self>> {
+        let mut ret: Vec<Box<Self>> = vec![
+            Box::new(Self::Null),
+            Box::new(Self::Integer(0)),
+            Box::new(Self::Integer(i64::MIN)),
+            Box::new(Self::Real(1.5)),
+            Box::new(Self::Real(-0.25)),
+            Box::new(Self::Text("test".to_string())),
+            Box::new(Self::Text("it's".to_string())),
+            Box::new(Self::CurrentTimestamp),
+            Box::new(Self::CurrentDate),
+            Box::new(Self::CurrentTime),
+            Box::new(Self::Expr("1 + 2".to_string())),
+        ];
+        if illegal {
+            ret.push(Box::new(Self::Real(f64::NAN)));
+            ret.push(Box::new(Self::Real(f64::INFINITY)));
+            ret.push(Box::new(Self::Real(f64::NEG_INFINITY)));
+        }
+        ret
+    }
+}
 
-        Ok(self.name.len() + 1 + self.typ.part_len()? + pk_len + unique_len + fk_len)
+impl PartialEq<DefaultValue> for DefaultValue {
+    fn eq(&self, other: &DefaultValue) -> bool {
+        match (self, other) {
+            // compare bitwise, so that Eq holds for NaN
+            (DefaultValue::Real(a), DefaultValue::Real(b)) => { a.to_bits() == b.to_bits() }
+            (DefaultValue::Integer(a), DefaultValue::Integer(b)) => { a == b }
+            (DefaultValue::Text(a), DefaultValue::Text(b)) => { a == b }
+            (DefaultValue::Expr(a), DefaultValue::Expr(b)) => { a == b }
+            (a, b) => { std::mem::discriminant(a) == std::mem::discriminant(b) }
+        }
     }
+}
 
-    fn part_str(&self, sql: &mut String) -> Result<()> {
-        self.check()?;
-        sql.push_str(self.name.as_str());
-        sql.push(' ');
-        self.typ.part_str(sql)?;
+impl Eq for DefaultValue {}
 
-        if let Some(pk) = self.pk.as_ref() {
-            sql.push(' ');
-            pk.part_str(sql)?;
+impl Hash for DefaultValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            // bitwise, consistent with PartialEq
+            DefaultValue::Real(real) => { real.to_bits().hash(state) }
+            DefaultValue::Integer(int) => { int.hash(state) }
+            DefaultValue::Text(text) => { text.hash(state) }
+            DefaultValue::Expr(expr) => { expr.hash(state) }
+            _ => {}
         }
+    }
+}
 
-        if let Some(unique) = self.unique.as_ref() {
-            sql.push(' ');
-            unique.part_str(sql)?;
+// endregion
+
+// region Collation
+
+/// Collating Function used to compare the values of a [Column], see [here](https://www.sqlite.org/datatype3.html#collation).
+/// It is a Error for the name of a [Collation::Custom] to be empty ([Error::EmptyCollationName]).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "lowercase"))]
+pub enum Collation {
+    /// Compares using `memcmp()`, the SQLite default
+    Binary,
+    /// Like [Collation::Binary], but ASCII characters are case-folded
+    NoCase,
+    /// Like [Collation::Binary], but trailing spaces are ignored
+    RTrim,
+    /// Any other Collating Function registered with the SQLite Connection
+    Custom(String),
+}
+
+impl Collation {
+    fn check(&self) -> Result<()> {
+        if let Collation::Custom(name) = self {
+            if name.is_empty() {
+                return Err(Error::EmptyCollationName);
+            }
         }
+        Ok(())
+    }
 
-        if let Some(fk) = self.fk.as_ref() {
-            sql.push(' ');
-            fk.part_str(sql)?;
+    /// Maps a Collation name as reported by SQLite, [Collation::Binary] (the default) is mapped to [None].
+    #[cfg(feature = "rusqlite")]
+    fn from_db(name: &str) -> Option<Self> {
+        match name.to_uppercase().as_str() {
+            "BINARY" => None,
+            "NOCASE" => Some(Collation::NoCase),
+            "RTRIM" => Some(Collation::RTrim),
+            _ => Some(Collation::Custom(name.to_string())),
         }
+    }
+}
+
+impl SQLPart for Collation {
+    fn part_len(&self) -> Result<usize> {
+        self.check()?;
+        Ok(8 + match self { // "COLLATE "
+            Collation::Binary => { 6 }
+            Collation::NoCase => { 6 }
+            Collation::RTrim => { 5 }
+            Collation::Custom(name) => { name.len() }
+        })
+    }
+
+    fn part_str(&self, sql: &mut String) -> Result<()> {
+        self.check()?;
+        sql.push_str("COLLATE ");
+        match self {
+            Collation::Binary => { sql.push_str("BINARY") }
+            Collation::NoCase => { sql.push_str("NOCASE") }
+            Collation::RTrim => { sql.push_str("RTRIM") }
+            Collation::Custom(name) => { sql.push_str(name.as_str()) }
+        };
         Ok(())
     }
 
     #[cfg(test)]
     fn possibilities(illegal: bool) -> Vec<Box<Self>> {
-        let mut ret: Vec<Box<Self>> = Vec::new();
-        for typ in SQLiteType::possibilities(false) {
-            for name in [if illegal { "".to_string() } else { "test".to_string() } , "test".to_string()] {
-                for pk in option_iter(PrimaryKey::possibilities(false)) {
-                    for unique in option_iter(Unique::possibilities(false)) {
-                        for fk in option_iter(ForeignKey::possibilities(false)) {
-                            for nn in option_iter(NotNull::possibilities(false)) {
-                                if !illegal && pk.is_some() && (fk.is_some() || unique.is_some()) {
-                                    continue
-                                }
-                                ret.push(Box::new(Self::new(*typ.clone(), name.clone(), pk.clone(), unique, fk.clone(), nn)));
-                            }
-                        }
+        vec![
+            Box::new(Self::Binary),
+            Box::new(Self::NoCase),
+            Box::new(Self::RTrim),
+            Box::new(Self::Custom(if illegal { "".to_string() } else { "NOCASE".to_string() })),
+        ]
+    }
+}
+
+impl fmt::Display for Collation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // not via SQLPart, which fails on empty Custom Collations
+        let name: &str = match self {
+            Collation::Binary => { "BINARY" }
+            Collation::NoCase => { "NOCASE" }
+            Collation::RTrim => { "RTRIM" }
+            Collation::Custom(name) => { name.as_str() }
+        };
+        f.pad(format!("COLLATE {}", name).as_str())
+    }
+}
+
+// endregion
+
+// region Quote
+
+/// How the name of a [Column] or [Table] is quoted, see [here](https://www.sqlite.org/lang_keywords.html).
+/// Quoting is needed for names which are SQLite keywords (see [is_sqlite_keyword]) or contain special characters.
+/// Quote characters inside the name are escaped by doubling them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
+pub enum Quote {
+    /// The name is used as-is
+    None,
+    /// `"name"`, the SQL standard
+    DoubleQuote,
+    /// `` `name` ``, for MySQL compatibility
+    Backtick,
+}
+
+impl Default for Quote {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+impl Quote {
+    /// [Quote::DoubleQuote] if `name` has to be quoted (see [needs_quoting]), otherwise [Quote::None]
+    pub fn for_name(name: &str) -> Self {
+        if needs_quoting(name) {
+            Self::DoubleQuote
+        } else {
+            Self::None
+        }
+    }
+
+    fn quote_char(&self) -> Option<char> {
+        match self {
+            Quote::None => { None }
+            Quote::DoubleQuote => { Some('"') }
+            Quote::Backtick => { Some('`') }
+        }
+    }
+
+    fn ident_len(&self, ident: &str) -> usize {
+        match self.quote_char() {
+            None => { ident.len() }
+            Some(quote) => { 1 + ident.len() + ident.matches(quote).count() + 1 }
+        }
+    }
+
+    fn push_ident(&self, sql: &mut String, ident: &str) {
+        match self.quote_char() {
+            None => { sql.push_str(ident) }
+            Some(quote) => {
+                sql.push(quote);
+                for char in ident.chars() {
+                    if char == quote {
+                        sql.push(quote);
                     }
+                    sql.push(char);
                 }
+                sql.push(quote);
             }
         }
-        ret
     }
 }
 
+// ref. https://www.sqlite.org/lang_keywords.html
+const SQLITE_KEYWORDS: [&str; 147] = [
+    "ABORT", "ACTION", "ADD", "AFTER", "ALL", "ALTER", "ALWAYS", "ANALYZE", "AND", "AS", "ASC", "ATTACH", "AUTOINCREMENT",
+    "BEFORE", "BEGIN", "BETWEEN", "BY", "CASCADE", "CASE", "CAST", "CHECK", "COLLATE", "COLUMN", "COMMIT", "CONFLICT",
+    "CONSTRAINT", "CREATE", "CROSS", "CURRENT", "CURRENT_DATE", "CURRENT_TIME", "CURRENT_TIMESTAMP", "DATABASE", "DEFAULT",
+    "DEFERRABLE", "DEFERRED", "DELETE", "DESC", "DETACH", "DISTINCT", "DO", "DROP", "EACH", "ELSE", "END", "ESCAPE", "EXCEPT",
+    "EXCLUDE", "EXCLUSIVE", "EXISTS", "EXPLAIN", "FAIL", "FILTER", "FIRST", "FOLLOWING", "FOR", "FOREIGN", "FROM", "FULL",
+    "GENERATED", "GLOB", "GROUP", "GROUPS", "HAVING", "IF", "IGNORE", "IMMEDIATE", "IN", "INDEX", "INDEXED", "INITIALLY",
+    "INNER", "INSERT", "INSTEAD", "INTERSECT", "INTO", "IS", "ISNULL", "JOIN", "KEY", "LAST", "LEFT", "LIKE", "LIMIT", "MATCH",
+    "MATERIALIZED", "NATURAL", "NO", "NOT", "NOTHING", "NOTNULL", "NULL", "NULLS", "OF", "OFFSET", "ON", "OR", "ORDER",
+    "OTHERS", "OUTER", "OVER", "PARTITION", "PLAN", "PRAGMA", "PRECEDING", "PRIMARY", "QUERY", "RAISE", "RANGE", "RECURSIVE",
+    "REFERENCES", "REGEXP", "REINDEX", "RELEASE", "RENAME", "REPLACE", "RESTRICT", "RETURNING", "RIGHT", "ROLLBACK", "ROW",
+    "ROWS", "SAVEPOINT", "SELECT", "SET", "TABLE", "TEMP", "TEMPORARY", "THEN", "TIES", "TO", "TRANSACTION", "TRIGGER",
+    "UNBOUNDED", "UNION", "UNIQUE", "UPDATE", "USING", "VACUUM", "VALUES", "VIEW", "VIRTUAL", "WHEN", "WHERE", "WINDOW",
+    "WITH", "WITHOUT",
+];
+
+/// Checks (case-insensitively) whether `s` is a SQLite keyword, see [here](https://www.sqlite.org/lang_keywords.html).
+/// Such names have to be quoted, see [Quote].
+pub fn is_sqlite_keyword(s: &str) -> bool {
+    SQLITE_KEYWORDS.iter().any(|keyword| keyword.eq_ignore_ascii_case(s))
+}
+
+/// Checks whether `s` has to be quoted to be used as a name: if it is a SQLite keyword (see [is_sqlite_keyword]), starts with a digit
+/// or contains ASCII characters other than letters, digits and `_`.
+pub fn needs_quoting(s: &str) -> bool {
+    is_sqlite_keyword(s)
+        || s.starts_with(|char: char| char.is_ascii_digit())
+        || s.chars().any(|char| char.is_ascii() && !char.is_ascii_alphanumeric() && char != '_')
+}
+
 // endregion
 
-// region Table
+// region Column
 
-/// Represents an entire Table, which may be Part of a wider [Schema] or used standalone.
-/// Can be converted into an SQL Statement via the [SQLStatement] Methods.
-/// It is a Error for the `name` to be empty ([Error::EmptyTableName]) or the Table itself to be empty ([Error::NoColumns]).
-#[derive(Debug, Clone, Eq)]
-#[cfg_attr(feature = "xml-config", derive(Serialize, Deserialize))]
-pub struct Table {
-    #[cfg_attr(feature = "xml-config", serde(rename = "@name"))]
+/// This struct Represents a Column in a [Table]. It is a Error for the `name` to be Empty ([Error::EmptyColumnName]).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Column {
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    typ: SQLiteType,
     name: String,
-    #[cfg_attr(feature = "xml-config", serde(rename = "column"))]
-    columns: Vec<Column>,
-    #[cfg_attr(feature = "xml-config", serde(rename = "@without_rowid", default))]
-    without_rowid: bool,
-    #[cfg_attr(feature = "xml-config", serde(rename = "@strict", default))]
-    strict: bool,
-    #[cfg_attr(feature = "xml-config", serde(skip))]
-    pub(crate) if_exists: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    quote: Quote,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    comment: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pk: Option<Named<PrimaryKey>>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    unique: Option<Named<Unique>>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    fk: Option<Named<ForeignKey>>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    not_null: Option<Named<NotNull>>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    check: Option<Named<Check>>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    default: Option<DefaultValue>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    collation: Option<Collation>,
+    // todo Generated Column
 }
 
-impl Table {
+impl Column {
     fn check(&self) -> Result<()> {
-        let mut has_pk: bool = false;
-        for col in &self.columns {
-            if col.pk.is_some() {
-                if has_pk {
-                    return Err(Error::MultiplePrimaryKeys);
-                } else {
-                    has_pk = true;
-                }
-            }
+        if self.name.is_empty() {
+            return Err(Error::EmptyColumnName)
         }
 
-        if self.name.is_empty() {
-            return Err(Error::EmptyTableName);
+        if let Some(pk) = self.pk.as_ref() {
+            // a descending Primary Key is no alias for the rowid, see https://www.sqlite.org/lang_createtable.html#rowid
+            if pk.autoincrement && (self.typ != SQLiteType::Integer || pk.sort_order == Order::Descending) {
+                return Err(Error::AutoincrementRequiresInteger)
+            }
         }
 
-        if self.columns.is_empty() {
-            return Err(Error::NoColumns)
+        if self.pk.is_some() && self.fk.is_some() {
+            return Err(Error::PrimaryKeyAndForeignKey)
         }
 
-        if self.without_rowid && !has_pk {
-            return Err(Error::WithoutRowidNoPrimaryKey);
+        if self.pk.is_some() && self.unique.is_some() {
+            return Err(Error::PrimaryKeyAndUnique)
         }
+
         Ok(())
     }
 
-    pub fn new(name: String, columns: Vec<Column>, without_rowid: bool, strict: bool) -> Self {
+    pub fn new(typ: SQLiteType, name: String, pk: Option<PrimaryKey>, unique: Option<Unique>, fk: Option<ForeignKey>, not_null: Option<NotNull>) -> Self {
         Self {
+            typ,
             name,
-            columns,
-            without_rowid,
-            strict,
-            if_exists: false,
+            quote: Quote::None,
+            comment: None,
+            pk: pk.map(Named::from),
+            unique: unique.map(Named::from),
+            fk: fk.map(Named::from),
+            not_null: not_null.map(Named::from),
+            check: None,
+            default: None,
+            collation: None,
         }
     }
 
     pub fn new_default(name: String) -> Self {
         Self {
+            typ: Default::default(),
             name,
-            columns: Vec::new(),
-            without_rowid: false,
-            strict: false,
-            if_exists: false
+            quote: Default::default(),
+            comment: None,
+            pk: Default::default(),
+            unique: Default::default(),
+            fk: Default::default(),
+            not_null: Default::default(),
+            check: Default::default(),
+            default: Default::default(),
+            collation: Default::default(),
+        }
+    }
+
+    pub fn new_typed(typ: SQLiteType, name: String) -> Self {
+        Self {
+            typ,
+            name,
+            quote: Default::default(),
+            comment: None,
+            pk: Default::default(),
+            unique: Default::default(),
+            fk: Default::default(),
+            not_null: Default::default(),
+            check: Default::default(),
+            default: Default::default(),
+            collation: Default::default(),
         }
     }
 
+    pub fn set_type(mut self, typ: SQLiteType) -> Self {
+        self.typ = typ;
+        self
+    }
+
     pub fn set_name(mut self, name: String) -> Self {
         self.name = name;
         self
     }
 
-    pub fn add_column(mut self, col: Column) -> Self {
-        self.columns.push(col);
+    pub fn set_pk(mut self, pk: Option<PrimaryKey>) -> Self {
+        self.pk = pk.map(Named::from);
         self
     }
 
-    pub fn set_without_rowid(mut self, without_rowid: bool) -> Self {
-        self.without_rowid = without_rowid;
+    /// Same as [Column::set_pk], with a named constraint (see [ColumnConstraint::with_name])
+    pub fn set_pk_named(mut self, pk: Option<Named<PrimaryKey>>) -> Self {
+        self.pk = pk;
         self
     }
 
-    pub fn set_strict(mut self, strict: bool) -> Self {
-        self.strict = strict;
+    /// Shorthand for [Column::set_pk] with [PrimaryKey::new]
+    pub fn as_primary_key(self, order: Order, on_conflict: OnConflict, autoincrement: bool) -> Self {
+        self.set_pk(Some(PrimaryKey::new(order, on_conflict, autoincrement)))
+    }
+
+    /// Makes this Column an `INTEGER PRIMARY KEY`, e.g. an alias for the rowid, see [here](https://www.sqlite.org/lang_createtable.html#rowid)
+    pub fn as_integer_pk(self) -> Self {
+        self.set_type(SQLiteType::Integer).as_primary_key(Order::Ascending, OnConflict::Abort, false)
+    }
+
+    /// Same as [Column::as_integer_pk], with `AUTOINCREMENT`
+    pub fn as_autoincrement(self) -> Self {
+        self.set_type(SQLiteType::Integer).as_primary_key(Order::Ascending, OnConflict::Abort, true)
+    }
+
+    pub fn set_unique(mut self, unique: Option<Unique>) -> Self {
+        self.unique = unique.map(Named::from);
         self
     }
-}
 
-impl SQLPart for Table {
-    fn part_len(&self) -> Result<usize> {
-        self.check()?;
-        let mut cols_len: usize = 0;
-        for col in &self.columns {
-            cols_len += col.part_len()?;
-        }
-        Ok(
-            13  // "CREATE TABLE "
-            + self.if_exists as usize * 14 // "IF NOT EXISTS "
-            + self.name.len()
-            + 2 // " ("
-            + cols_len
-            + self.columns.len() - 1 // commas for cols, -1 b/c the last doesn't have a comma
-            + 1 // ')'
-            + self.without_rowid as usize * 14 // " WITHOUT ROWID"
-            + (self.without_rowid && self.strict) as usize * 1 // ','
-            + self.strict as usize * 7 // " STRICT"
-        )
+    /// Same as [Column::set_unique], with a named constraint (see [ColumnConstraint::with_name])
+    pub fn set_unique_named(mut self, unique: Option<Named<Unique>>) -> Self {
+        self.unique = unique;
+        self
     }
 
-    fn part_str(&self, sql: &mut String) -> Result<()> {
-        self.check()?;
+    pub fn set_fk(mut self, fk: Option<ForeignKey>) -> Self {
+        self.fk = fk.map(Named::from);
+        self
+    }
 
-        sql.push_str("CREATE TABLE ");
-        if self.if_exists {
-            sql.push_str("IF NOT EXISTS ");
-        }
-        sql.push_str(self.name.as_str());
-        sql.push_str(" (");
+    /// Same as [Column::set_fk], with a named constraint (see [ColumnConstraint::with_name])
+    pub fn set_fk_named(mut self, fk: Option<Named<ForeignKey>>) -> Self {
+        self.fk = fk;
+        self
+    }
 
-        let mut needs_comma = false;
-        for coll in &self.columns {
-            if needs_comma {
-                sql.push(',');
-            }
-            coll.part_str(sql)?;
-            needs_comma = true;
-        }
-        sql.push(')');
+    pub fn set_not_null(mut self, not_null: Option<NotNull>) -> Self {
+        self.not_null = not_null.map(Named::from);
+        self
+    }
 
+    /// Same as [Column::set_not_null], with a named constraint (see [ColumnConstraint::with_name])
+    pub fn set_not_null_named(mut self, not_null: Option<Named<NotNull>>) -> Self {
+        self.not_null = not_null;
+        self
+    }
 
-        if self.without_rowid {
-            sql.push_str(" WITHOUT ROWID");
-        }
-        if self.without_rowid && self.strict  {
-            sql.push(',');
-        }
-        if self.strict {
-            sql.push_str(" STRICT");
-        }
-        Ok(())
+    pub fn set_check(mut self, check: Option<Check>) -> Self {
+        self.check = check.map(Named::from);
+        self
     }
 
-    #[cfg(test)]
-    fn possibilities(illegal: bool) -> Vec<Box<Self>> {
-        let mut ret: Vec<Box<Self>> = Vec::new();
-        for name in [if illegal { "".to_string() } else { "test".to_string() } , "test".to_string()] {
-            for wo_rowid in [true, false] {
-                for col_num in [if illegal { 0 } else { 3 }, 1, 2] {
-                    let mut cols: Vec<Column> = Vec::new();
-                    for n in 0..col_num {
-                        cols.push(Column::new_default(format!("test{}", n)))
-                        // todo not all column possibilities
-                    }
-                    if !illegal && wo_rowid {
-                        cols[0].pk = Some(Default::default());
-                    }
-
-                    for strict in [true, false] {
-                        ret.push(Box::new(Self::new(name.clone(), cols.clone(), wo_rowid, strict)));
-                    }
-                }
-            }
-        }
-        ret
-    }
-}
-
-impl SQLStatement for Table {
-    fn len(&mut self, transaction: bool, if_exists: bool) -> Result<usize> {
-        self.if_exists = if_exists;
-        Ok(transaction as usize * 7 + self.part_len()? + 1 + transaction as usize * 5)
+    /// Same as [Column::set_check], with a named constraint (see [ColumnConstraint::with_name])
+    pub fn set_check_named(mut self, check: Option<Named<Check>>) -> Self {
+        self.check = check;
+        self
     }
 
-    fn build(&mut self, transaction: bool, if_exist: bool) -> Result<String> {
-        let mut str = String::with_capacity(self.len(transaction, if_exist)?);
-        if transaction {
-            str.push_str("BEGIN;\n");
-        }
-        self.part_str(&mut str)?;
-        str.push(';');
-        if transaction {
-            str.push_str("\nEND;");
-        }
-        Ok(str)
+    pub fn set_default(mut self, default: Option<DefaultValue>) -> Self {
+        self.default = default;
+        self
     }
-}
 
-impl PartialEq<Table> for Table {
-    fn eq(&self, other: &Table) -> bool {
-        if self.name != other.name {
-            return false;
-        }
-        if self.without_rowid != other.without_rowid {
-            return false;
-        }
-        if self.strict != other.strict {
-            return false;
-        }
-        if self.columns.len() != other.columns.len() {
-            return false;
-        }
-        for columns in self.columns.iter().zip(other.columns.iter()) {
-            if columns.0 != columns.1 {
-                return false;
-            }
-        }
-        true
+    pub fn set_collation(mut self, collation: Option<Collation>) -> Self {
+        self.collation = collation;
+        self
     }
-}
-
-// endregion
-
-// region Schema
-
-/// A Schema (or Layout, hence the crate name) encompasses one or more [Table]s.
-/// Can be converted into an SQL Statement via the [SQLStatement] Methods.
-/// It is a Error for the Schema to be empty ([Error::SchemaWithoutTables]).
-#[derive(Debug, Clone, Default, Eq)]
-#[cfg_attr(feature = "xml-config", derive(Serialize, Deserialize), serde(rename = "schema"))]
-pub struct Schema {
-    #[cfg_attr(feature = "xml-config", serde(rename = "table"))]
-    tables: Vec<Table>,
-    #[cfg(feature = "xml-config")]
-    #[cfg_attr(feature = "xml-config", serde(rename = "@xmlns"))]
-    xmlns: &'static str,
-}
 
-impl Schema {
-    fn check(&self) -> Result<()> {
-        if self.tables.is_empty() {
-            return Err(Error::SchemaWithoutTables);
-        }
-        Ok(())
+    pub fn set_quote(mut self, quote: Quote) -> Self {
+        self.quote = quote;
+        self
     }
 
-    pub fn new() -> Self {
-        Self {
-            tables: Vec::new(),
-            #[cfg(feature = "xml-config")]
-            xmlns: "https://crates.io/crates/sqlayout"
-        }
+    /// Removes all constraints ([PrimaryKey], [Unique], [ForeignKey], [NotNull] and [Check]), leaving only name and type.
+    /// The [DefaultValue] and [Collation] are kept, as they never reject a value.
+    pub fn strip_constraints(mut self) -> Self {
+        self.pk = None;
+        self.unique = None;
+        self.fk = None;
+        self.not_null = None;
+        self.check = None;
+        self
     }
 
-    pub fn add_table(mut self, new_table: Table) -> Self {
-        self.tables.push(new_table);
+    /// Sets the comment emitted by [SQLStatement::build_pretty], see [Column::comment]
+    pub fn set_comment(mut self, comment: Option<String>) -> Self {
+        self.comment = comment;
         self
     }
 
-    /// Checks the given DB for deviations from the given Schema
-    /// todo: document return
-    #[cfg(feature = "rusqlite")]
-    pub fn check_db(&mut self, conn: &Connection) -> Result<Option<String>, CheckError> {
-        self.tables.sort_unstable_by_key(| table: &Table | table.name.clone()); // todo ugly :(
-
-        let mut ret: String = String::new();
-
-        let mut stmt: Statement = conn.prepare(r#"SELECT name, ncol, wr, strict FROM pragma_table_list() WHERE (schema == "main") AND (type == "table") AND name NOT LIKE "%schema" ORDER BY name;"#)?;
-        let mut rows: Rows = stmt.query(())?;
+    /// Description of the Column, only emitted as a SQL comment by [SQLStatement::build_pretty] (after the Column definition)
+    pub fn comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
 
+    pub fn quote(&self) -> Quote {
+        self.quote
+    }
 
-        for( num, table) in self.tables.iter().enumerate() {
-            let row: &Row = {
-                let raw_row = rows.next()?;
-                match raw_row {
-                    None => {
-                        write!(ret, "Table {}: expected table '{}', got nothing; ", num, table.name)?;
-                        break
-                    }
-                    Some(row) => { row }
-                }
-            };
-            if table.name != row.get::<&str, String>("name")? {
-                write!(ret, "Table {}: expected name '{}', got '{}'; ", num, table.name, row.get::<&str, String>("name")?)?;
-            }
-            if table.without_rowid != row.get::<&str, bool>("wr")? {
-                write!(ret, "Table {}: expected without_rowid {}, got {}; ", num, table.without_rowid, row.get::<&str, bool>("wr")?)?;
-            }
-            if table.strict != row.get::<&str, bool>("strict")? {
-                write!(ret, "Table {}: expected strict {}, got {}; ", num, table.strict, row.get::<&str, bool>("strict")?)?;
-            }
-            if table.columns.len() != row.get::<&str, usize>("ncol")? {
-                write!(ret, "Table {}: expected number of columns {}, got {}; ", num, table.columns.len(), row.get::<&str, usize>("ncol")?)?;
-            }
-        }
+    /// The name as emitted in SQL, quoted according to [Column::quote]
+    pub fn quoted_name(&self) -> String {
+        let mut ret: String = String::with_capacity(self.quote.ident_len(self.name.as_str()));
+        self.quote.push_ident(&mut ret, self.name.as_str());
+        ret
+    }
 
-        let mut i: usize = self.tables.len();
-        while let Some(row) = rows.next()? {
-            write!(ret, "Table {}: expected nothing, got table '{}'; ", i, row.get::<&str, String>("name")?)?;
-            i += 1;
-        }
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
 
-        if ret.is_empty() {
-            Ok(None)
-        } else {
-            Ok(Some(ret))
-        }
+    pub fn col_type(&self) -> SQLiteType {
+        self.typ
     }
-}
 
-impl SQLStatement for Schema {
-    fn len(&mut self, transaction: bool, if_exists: bool) -> Result<usize> {
-        self.check()?;
-        let mut tbls_len: usize = 0;
-        for tbl in &mut self.tables {
-            tbl.if_exists = if_exists;
-            tbls_len += tbl.part_len()?;
-        }
-        Ok(transaction as usize * 7 + tbls_len + self.tables.len() + transaction as usize * 5)
+    pub fn primary_key(&self) -> Option<&PrimaryKey> {
+        self.pk.as_deref()
     }
 
-    fn build(&mut self, transaction: bool, if_exists: bool) -> Result<String> {
-        self.check()?;
-        let mut ret: String = String::with_capacity(self.len(transaction, if_exists)?);
-        if transaction {
-            ret.push_str("BEGIN;\n");
-        }
+    /// Same as [Column::primary_key], including the constraint name
+    pub fn primary_key_named(&self) -> Option<&Named<PrimaryKey>> {
+        self.pk.as_ref()
+    }
 
-        for tbl in &self.tables {
-            tbl.part_str(&mut ret)?;
-            ret.push(';');
-        }
+    pub fn unique(&self) -> Option<&Unique> {
+        self.unique.as_deref()
+    }
 
-        if transaction {
-            ret.push_str("\nEND;")
-        }
-        Ok(ret)
+    /// Same as [Column::unique], including the constraint name
+    pub fn unique_named(&self) -> Option<&Named<Unique>> {
+        self.unique.as_ref()
     }
-}
 
-impl PartialEq<Schema> for Schema {
-    fn eq(&self, other: &Schema) -> bool {
-        if self.tables.len() != other.tables.len() {
-            return false;
-        }
-        for tables in self.tables.iter().zip(other.tables.iter()) {
-            if tables.0 != tables.1 {
-                return false;
-            }
-        }
-        true
+    pub fn foreign_key(&self) -> Option<&ForeignKey> {
+        self.fk.as_deref()
     }
-}
 
-// endregion Schema
+    /// Same as [Column::foreign_key], including the constraint name
+    pub fn foreign_key_named(&self) -> Option<&Named<ForeignKey>> {
+        self.fk.as_ref()
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use anyhow::Result;
+    pub fn not_null(&self) -> Option<&NotNull> {
+        self.not_null.as_deref()
+    }
 
-    #[cfg(feature = "rusqlite")]
-    fn test_sql<S: SQLStatement>(stmt: &mut S) -> Result<()> {
-        for if_exists in [true, false] {
-            for transaction in [true, false] {
-                let sql: String = stmt.build(transaction, if_exists)?;
+    /// Same as [Column::not_null], including the constraint name
+    pub fn not_null_named(&self) -> Option<&Named<NotNull>> {
+        self.not_null.as_ref()
+    }
 
-                assert_eq!(sql.len(), stmt.len(transaction, if_exists)?);
+    pub fn check_constraint(&self) -> Option<&Check> {
+        self.check.as_deref()
+    }
 
-                let conn: Connection = Connection::open_in_memory()?;
-                let ret = conn.execute_batch(&sql);
-                if ret.is_err() {
-                    println!("Error SQL: '{}'", sql)
-                }
-                ret?
-            }
-        }
+    /// Same as [Column::check_constraint], including the constraint name
+    pub fn check_constraint_named(&self) -> Option<&Named<Check>> {
+        self.check.as_ref()
+    }
 
-        Ok(())
+    pub fn default_value(&self) -> Option<&DefaultValue> {
+        self.default.as_ref()
     }
 
-    #[cfg(not(feature = "rusqlite"))]
-    fn test_sql<S: SQLStatement>(_stmt: &mut S) -> Result<()> {
-        // todo
-        Ok(())
+    pub fn collation(&self) -> Option<&Collation> {
+        self.collation.as_ref()
     }
 
-    fn test_sql_part<P: SQLPart>(part: &P) -> Result<()> {
-        let mut str: String = String::with_capacity(part.part_len()?);
+    /// Whether the Column may contain `NULL`, e.g. it has neither a [NotNull] nor a [PrimaryKey].
+    /// Note that SQLite only enforces `NOT NULL` for a Primary Key in `INTEGER PRIMARY KEY` Columns and `WITHOUT ROWID` or `STRICT` Tables,
+    /// see [here](https://www.sqlite.org/lang_createtable.html#the_primary_key).
+    pub fn is_nullable(&self) -> bool {
+        self.not_null.is_none() && self.pk.is_none()
+    }
 
-        part.part_str(&mut str)?;
-        assert_eq!(str.len(), part.part_len()?);
+    /// Whether a value must be given for the Column, the opposite of [Column::is_nullable]
+    pub fn is_required(&self) -> bool {
+        !self.is_nullable()
+    }
 
-        Ok(())
+    /// Whether the Column has a [PrimaryKey], [Unique] or [ForeignKey].
+    /// The first two are backed by an automatic Index, SQLite does not create one for a ForeignKey.
+    pub fn is_indexed(&self) -> bool {
+        self.pk.is_some() || self.unique.is_some() || self.fk.is_some()
     }
+}
 
-    #[test]
-    fn test_sqlite_type() -> Result<()> {
-        let mut str: String;
+impl SQLPart for Column {
+    fn part_len(&self) -> Result<usize> {
+        self.check()?;
+        let pk_len: usize = if let Some(pk) = self.pk.as_ref() {
+            pk.part_len()? + 1
+        } else {
+            0
+        };
 
-        str = String::new();
-        SQLiteType::Blob.part_str(&mut str)?;
-        assert_eq!(str, "BLOB");
-        assert_eq!(str.len(), SQLiteType::Blob.part_len()?);
+        let unique_len: usize = if let Some(unique) = self.unique.as_ref() {
+            unique.part_len()? + 1
+        } else {
+            0
+        };
 
-        str = String::new();
-        SQLiteType::Numeric.part_str(&mut str)?;
-        assert_eq!(str, "NUMERIC");
-        assert_eq!(str.len(), SQLiteType::Numeric.part_len()?);
+        let not_null_len: usize = if let Some(not_null) = self.not_null.as_ref() {
+            not_null.part_len()? + 1
+        } else {
+            0
+        };
 
-        str = String::new();
-        SQLiteType::Integer.part_str(&mut str)?;
-        assert_eq!(str, "INTEGER");
-        assert_eq!(str.len(), SQLiteType::Integer.part_len()?);
+        let fk_len: usize = if let Some(fk) = self.fk.as_ref() {
+            fk.part_len()? + 1
+        } else {
+            0
+        };
 
-        str = String::new();
-        SQLiteType::Real.part_str(&mut str)?;
-        assert_eq!(str, "REAL");
-        assert_eq!(str.len(), SQLiteType::Real.part_len()?);
+        let check_len: usize = if let Some(check) = self.check.as_ref() {
+            check.part_len()? + 1
+        } else {
+            0
+        };
 
-        str = String::new();
-        SQLiteType::Text.part_str(&mut str)?;
-        assert_eq!(str, "TEXT");
-        assert_eq!(str.len(), SQLiteType::Text.part_len()?);
+        let default_len: usize = if let Some(default) = self.default.as_ref() {
+            default.part_len()? + 1
+        } else {
+            0
+        };
 
-        Ok(())
+        let collation_len: usize = if let Some(collation) = self.collation.as_ref() {
+            collation.part_len()? + 1
+        } else {
+            0
+        };
+
+        Ok(self.quote.ident_len(self.name.as_str()) + 1 + self.typ.part_len()? + pk_len + unique_len + not_null_len + fk_len + check_len + default_len + collation_len)
     }
 
-    #[test]
-    fn test_order() -> Result<()> {
-        let mut str: String;
+    fn part_str(&self, sql: &mut String) -> Result<()> {
+        self.check()?;
+        self.quote.push_ident(sql, self.name.as_str());
+        sql.push(' ');
+        self.typ.part_str(sql)?;
 
-        str = String::new();
-        Order::Ascending.part_str(&mut str)?;
-        assert_eq!(str, "ASC");
-        assert_eq!(str.len(), Order::Ascending.part_len()?);
+        if let Some(pk) = self.pk.as_ref() {
+            sql.push(' ');
+            pk.part_str(sql)?;
+        }
 
-        str = String::new();
-        Order::Descending.part_str(&mut str)?;
-        assert_eq!(str, "DESC");
-        assert_eq!(str.len(), Order::Descending.part_len()?);
+        if let Some(unique) = self.unique.as_ref() {
+            sql.push(' ');
+            unique.part_str(sql)?;
+        }
+
+        if let Some(not_null) = self.not_null.as_ref() {
+            sql.push(' ');
+            not_null.part_str(sql)?;
+        }
+
+        if let Some(fk) = self.fk.as_ref() {
+            sql.push(' ');
+            fk.part_str(sql)?;
+        }
+
+        if let Some(check) = self.check.as_ref() {
+            sql.push(' ');
+            check.part_str(sql)?;
+        }
+
+        if let Some(default) = self.default.as_ref() {
+            sql.push(' ');
+            default.part_str(sql)?;
+        }
 
+        if let Some(collation) = self.collation.as_ref() {
+            sql.push(' ');
+            collation.part_str(sql)?;
+        }
         Ok(())
     }
 
-    #[test]
-    fn test_on_conflict() -> Result<()> {
-        let mut str: String;
+    #[cfg(test)]
+    fn possibilities(illegal: bool) -> Vec<Box<Self>> {
+        let mut ret: Vec<Box<Self>> = Vec::new();
+        for typ in SQLiteType::possibilities(false) {
+            for name in [if illegal { "".to_string() } else { "test".to_string() } , "test".to_string()] {
+                for pk in option_iter(PrimaryKey::possibilities(false)) {
+                    for unique in option_iter(Unique::possibilities(false)) {
+                        for fk in option_iter(ForeignKey::possibilities(false)) {
+                            for nn in option_iter(NotNull::possibilities(false)) {
+                                for check in option_iter(Check::possibilities(illegal)) {
+                                    for default in option_iter(DefaultValue::possibilities(false)) {
+                                        for collation in option_iter(Collation::possibilities(illegal)) {
+                                            if !illegal && pk.is_some() && (fk.is_some() || unique.is_some()) {
+                                                continue
+                                            }
+                                            if !illegal && pk.as_ref().is_some_and(|pk| pk.autoincrement) && *typ != SQLiteType::Integer {
+                                                continue
+                                            }
+                                            ret.push(Box::new(Self::new(*typ.clone(), name.clone(), pk.clone(), unique, fk.clone(), nn).set_check(check.clone()).set_default(default.clone()).set_collation(collation)));
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        ret
+    }
+}
 
-        str = String::new();
-        OnConflict::Rollback.part_str(&mut str)?;
-        assert_eq!(str, "ON CONFLICT ROLLBACK");
-        assert_eq!(str.len(), OnConflict::Rollback.part_len()?);
+// endregion
 
-        str = String::new();
-        OnConflict::Abort.part_str(&mut str)?;
-        assert_eq!(str, "ON CONFLICT ABORT");
-        assert_eq!(str.len(), OnConflict::Abort.part_len()?);
+// region Typed Column
 
-        str = String::new();
-        OnConflict::Fail.part_str(&mut str)?;
-        assert_eq!(str, "ON CONFLICT FAIL");
-        assert_eq!(str.len(), OnConflict::Fail.part_len()?);
+/// Maps a Rust type to the [SQLiteType] of the [Column] it is stored in, see [TypedColumn].
+/// [Option]s map to the type of their content.
+pub trait SQLiteTyped {
+    const TYPE: SQLiteType;
+}
 
-        str = String::new();
-        OnConflict::Ignore.part_str(&mut str)?;
-        assert_eq!(str, "ON CONFLICT IGNORE");
-        assert_eq!(str.len(), OnConflict::Ignore.part_len()?);
+macro_rules! impl_sqlite_typed {
+    ($typ:expr => $($rust:ty),+) => {
+        $(
+            impl SQLiteTyped for $rust {
+                const TYPE: SQLiteType = $typ;
+            }
+        )+
+    };
+}
 
-        str = String::new();
-        OnConflict::Replace.part_str(&mut str)?;
-        assert_eq!(str, "ON CONFLICT REPLACE");
-        assert_eq!(str.len(), OnConflict::Replace.part_len()?);
+impl_sqlite_typed!(SQLiteType::Text => String, str);
+impl_sqlite_typed!(SQLiteType::Integer => i8, i16, i32, i64, u8, u16, u32, bool);
+impl_sqlite_typed!(SQLiteType::Real => f32, f64);
+impl_sqlite_typed!(SQLiteType::Blob => Vec<u8>, [u8]);
+impl_sqlite_typed!(SQLiteType::Numeric => Numeric);
 
-        Ok(())
-    }
+impl<T: SQLiteTyped> SQLiteTyped for Option<T> {
+    const TYPE: SQLiteType = T::TYPE;
+}
 
-    #[test]
-    fn test_fk_on_action() -> Result<()> {
-        let mut str: String;
+/// Marker for [SQLiteType::Numeric] Columns, which have no single Rust counterpart (SQLite stores integers, reals or text in them).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Numeric {}
 
-        str = String::new();
-        FKOnAction::SetNull.part_str(&mut str)?;
-        assert_eq!(str, "SET NULL");
-        assert_eq!(str.len(), FKOnAction::SetNull.part_len()?);
+/// Constructs a [Column] from a type alone, see [TypedColumn].
+pub trait IntoColumn {
+    fn into_column(name: String) -> Column;
+}
 
-        str = String::new();
-        FKOnAction::SetDefault.part_str(&mut str)?;
-        assert_eq!(str, "SET DEFAULT");
-        assert_eq!(str.len(), FKOnAction::SetDefault.part_len()?);
+/// Zero-sized constructor for [Column]s whose [SQLiteType] is determined by the Rust type `T` (see [SQLiteTyped]),
+/// e.g. `TypedColumn::<i64>::new("id".to_string())` is a [SQLiteType::Integer] Column.
+pub struct TypedColumn<T: SQLiteTyped + ?Sized>(std::marker::PhantomData<T>);
 
-        str = String::new();
-        FKOnAction::Cascade.part_str(&mut str)?;
-        assert_eq!(str, "CASCADE");
-        assert_eq!(str.len(), FKOnAction::Cascade.part_len()?);
+impl<T: SQLiteTyped + ?Sized> TypedColumn<T> {
+    pub const TYPE: SQLiteType = T::TYPE;
 
-        str = String::new();
-        FKOnAction::Restrict.part_str(&mut str)?;
-        assert_eq!(str, "RESTRICT");
-        assert_eq!(str.len(), FKOnAction::Restrict.part_len()?);
+    /// Creates a [Column] like [Column::new_typed], with the type of `T`
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(name: String) -> Column {
+        Column::new_typed(Self::TYPE, name)
+    }
+}
 
-        str = String::new();
-        FKOnAction::NoAction.part_str(&mut str)?;
-        assert_eq!(str, "NO ACTION");
-        assert_eq!(str.len(), FKOnAction::NoAction.part_len()?);
+impl<T: SQLiteTyped + ?Sized> IntoColumn for TypedColumn<T> {
+    fn into_column(name: String) -> Column {
+        Self::new(name)
+    }
+}
+
+pub type TextColumn = TypedColumn<String>;
+pub type IntegerColumn = TypedColumn<i64>;
+pub type RealColumn = TypedColumn<f64>;
+pub type BlobColumn = TypedColumn<Vec<u8>>;
+pub type NumericColumn = TypedColumn<Numeric>;
+
+// endregion
+
+// region Table Constraint
+
+/// A Foreign Key spanning multiple [Column]s, used via [TableConstraint::CompositeForeignKey], see [here](https://www.sqlite.org/foreignkeys.html#fk_composite).
+/// It is a Error for the `local_columns` ([Error::EmptyConstraintColumns]), `foreign_table` ([Error::EmptyForeignTableName]) or `foreign_columns` ([Error::EmptyForeignColumnName]) to be empty,
+/// or for `local_columns` and `foreign_columns` to differ in length ([Error::ForeignKeyColumnCountMismatch]).
+/// The `quote` is used for the `foreign_table` and `foreign_columns`, the `local_columns` are quoted like the [Column]s of the [Table].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CompositeForeignKey {
+    local_columns: Vec<String>,
+    foreign_table: String,
+    foreign_columns: Vec<String>,
+    quote: Quote,
+    on_delete: Option<FKOnAction>,
+    on_update: Option<FKOnAction>,
+    deferrable: FKDeferrable,
+}
 
+impl CompositeForeignKey {
+    fn check(&self) -> Result<()> {
+        if self.local_columns.is_empty() {
+            return Err(Error::EmptyConstraintColumns);
+        }
+        if self.foreign_table.is_empty() {
+            return Err(Error::EmptyForeignTableName);
+        }
+        if self.foreign_columns.is_empty() {
+            return Err(Error::EmptyForeignColumnName);
+        }
+        if self.local_columns.len() != self.foreign_columns.len() {
+            return Err(Error::ForeignKeyColumnCountMismatch);
+        }
         Ok(())
     }
 
-    #[test]
-    fn test_not_null() -> Result<()> {
-        let mut str: String;
-
-        str = String::new();
-        NotNull::new(OnConflict::Rollback).part_str(&mut str)?;
-        assert_eq!(str, "NOT NULL ON CONFLICT ROLLBACK");
-        assert_eq!(str.len(), NotNull::new(OnConflict::Rollback).part_len()?);
+    pub fn new(local_columns: Vec<String>, foreign_table: String, foreign_columns: Vec<String>, on_delete: Option<FKOnAction>, on_update: Option<FKOnAction>, deferrable: FKDeferrable) -> Self {
+        Self {
+            local_columns,
+            foreign_table,
+            foreign_columns,
+            quote: Quote::None,
+            on_delete,
+            on_update,
+            deferrable,
+        }
+    }
 
-        str = String::new();
-        NotNull::new(OnConflict::Abort).part_str(&mut str)?;
-        assert_eq!(str, "NOT NULL ON CONFLICT ABORT");
-        assert_eq!(str.len(), NotNull::new(OnConflict::Abort).part_len()?);
+    pub fn new_default(local_columns: Vec<String>, foreign_table: String, foreign_columns: Vec<String>) -> Self {
+        Self {
+            local_columns,
+            foreign_table,
+            foreign_columns,
+            quote: Quote::None,
+            on_delete: Default::default(),
+            on_update: Default::default(),
+            deferrable: Default::default(),
+        }
+    }
 
-        str = String::new();
-        NotNull::new(OnConflict::Fail).part_str(&mut str)?;
-        assert_eq!(str, "NOT NULL ON CONFLICT FAIL");
-        assert_eq!(str.len(), NotNull::new(OnConflict::Fail).part_len()?);
+    pub fn set_on_delete(mut self, on_delete: Option<FKOnAction>) -> Self {
+        self.on_delete = on_delete;
+        self
+    }
 
-        str = String::new();
-        NotNull::new(OnConflict::Ignore).part_str(&mut str)?;
-        assert_eq!(str, "NOT NULL ON CONFLICT IGNORE");
-        assert_eq!(str.len(), NotNull::new(OnConflict::Ignore).part_len()?);
+    pub fn set_on_update(mut self, on_update: Option<FKOnAction>) -> Self {
+        self.on_update = on_update;
+        self
+    }
 
-        str = String::new();
-        NotNull::new(OnConflict::Replace).part_str(&mut str)?;
-        assert_eq!(str, "NOT NULL ON CONFLICT REPLACE");
-        assert_eq!(str.len(), NotNull::new(OnConflict::Replace).part_len()?);
+    pub fn set_deferrable(mut self, deferrable: FKDeferrable) -> Self {
+        self.deferrable = deferrable;
+        self
+    }
 
-        Ok(())
+    /// Sets how the `foreign_table` and `foreign_columns` are quoted, see [Schema::auto_quote]
+    pub fn set_quote(mut self, quote: Quote) -> Self {
+        self.quote = quote;
+        self
     }
 
-    #[test]
-    fn test_unique() -> Result<()> {
-        let mut str: String;
+    pub fn local_columns(&self) -> &[String] {
+        self.local_columns.as_slice()
+    }
 
-        str = String::new();
-        Unique::new(OnConflict::Rollback).part_str(&mut str)?;
-        assert_eq!(str, "UNIQUE ON CONFLICT ROLLBACK");
-        assert_eq!(str.len(), Unique::new(OnConflict::Rollback).part_len()?);
+    pub fn foreign_table(&self) -> &str {
+        self.foreign_table.as_str()
+    }
 
-        str = String::new();
-        Unique::new(OnConflict::Abort).part_str(&mut str)?;
-        assert_eq!(str, "UNIQUE ON CONFLICT ABORT");
-        assert_eq!(str.len(), Unique::new(OnConflict::Abort).part_len()?);
+    pub fn foreign_columns(&self) -> &[String] {
+        self.foreign_columns.as_slice()
+    }
 
-        str = String::new();
-        Unique::new(OnConflict::Fail).part_str(&mut str)?;
-        assert_eq!(str, "UNIQUE ON CONFLICT FAIL");
-        assert_eq!(str.len(), Unique::new(OnConflict::Fail).part_len()?);
+    pub fn quote(&self) -> Quote {
+        self.quote
+    }
 
-        str = String::new();
-        Unique::new(OnConflict::Ignore).part_str(&mut str)?;
-        assert_eq!(str, "UNIQUE ON CONFLICT IGNORE");
-        assert_eq!(str.len(), Unique::new(OnConflict::Ignore).part_len()?);
+    /// Length of the Constraint, the `local_columns` are quoted like the matching `table_columns`
+    fn write_len(&self, table_columns: &[Column]) -> Result<usize> {
+        self.check()?;
+        Ok(
+            13 // "FOREIGN KEY ("
+            + TableConstraint::columns_len(&self.local_columns, |name| TableConstraint::column_quote(table_columns, name))
+            + 13 // ") REFERENCES "
+            + self.quote.ident_len(self.foreign_table.as_str())
+            + 2 // " ("
+            + TableConstraint::columns_len(&self.foreign_columns, |_| self.quote)
+            + 1 // ')'
+            + ForeignKey::actions_len(self.on_delete, self.on_update, self.deferrable)?
+        )
+    }
 
-        str = String::new();
-        Unique::new(OnConflict::Replace).part_str(&mut str)?;
-        assert_eq!(str, "UNIQUE ON CONFLICT REPLACE");
-        assert_eq!(str.len(), Unique::new(OnConflict::Replace).part_len()?);
+    /// Writes the Constraint, see [CompositeForeignKey::write_len]
+    fn write_str(&self, sql: &mut String, table_columns: &[Column]) -> Result<()> {
+        self.check()?;
+        sql.push_str("FOREIGN KEY (");
+        TableConstraint::columns_str(&self.local_columns, |name| TableConstraint::column_quote(table_columns, name), sql);
+        sql.push_str(") REFERENCES ");
+        self.quote.push_ident(sql, self.foreign_table.as_str());
+        sql.push_str(" (");
+        TableConstraint::columns_str(&self.foreign_columns, |_| self.quote, sql);
+        sql.push(')');
+        ForeignKey::actions_str(self.on_delete, self.on_update, self.deferrable, sql)
+    }
+}
 
-        Ok(())
+impl SQLPart for CompositeForeignKey {
+    fn part_len(&self) -> Result<usize> {
+        self.write_len(&[])
+    }
 
+    fn part_str(&self, sql: &mut String) -> Result<()> {
+        self.write_str(sql, &[])
     }
 
-    #[test]
-    fn test_primary_key() -> Result<()> {
-        for so in [Order::Ascending, Order::Descending] {
-            for conf in [OnConflict::Rollback, OnConflict::Abort, OnConflict::Fail, OnConflict::Ignore, OnConflict::Replace] {
-                for autoinc in [true, false] {
-                    test_sql_part(&PrimaryKey::new(so, conf, autoinc))?;
+    #[cfg(test)]
+    fn possibilities(illegal: bool) -> Vec<Box<Self>> {
+        let mut ret: Vec<Box<Self>> = Vec::new();
+        for local_num in [if illegal { 0 } else { 2 }, 1, 2] {
+            for foreign_num in [if illegal { 1 } else { 2 }, 2] {
+                for tbl in [if illegal { "".to_string() } else { "test".to_string() }, "test".to_string()] {
+                    for on_del in option_iter(FKOnAction::possibilities(false)) {
+                        for defer in FKDeferrable::possibilities(false) {
+                            let local: Vec<String> = (0..local_num).map(|n| format!("test{}", n)).collect();
+                            let foreign: Vec<String> = (0..foreign_num).map(|n| format!("other{}", n)).collect();
+                            if !illegal && local.len() != foreign.len() {
+                                continue;
+                            }
+                            ret.push(Box::new(Self::new(local, tbl.clone(), foreign, on_del, on_del, *defer)));
+                        }
+                    }
                 }
             }
         }
-        Ok(())
+        ret.push(Box::new(Self::new_default(vec!["test".to_string()], "group".to_string(), vec!["order".to_string()]).set_quote(Quote::Backtick)));
+        ret
     }
+}
 
-    #[test]
-    fn test_foreign_key() -> Result<()> {
-        for defer in [true, false] {
-            for on_del in [None, Some(FKOnAction::SetNull), Some(FKOnAction::SetDefault), Some(FKOnAction::Cascade), Some(FKOnAction::Restrict), Some(FKOnAction::NoAction)] {
-                for on_upd in [None, Some(FKOnAction::SetNull), Some(FKOnAction::SetDefault), Some(FKOnAction::Cascade), Some(FKOnAction::Restrict), Some(FKOnAction::NoAction)] {
-                    // todo: test string params
-                    assert_eq!(ForeignKey::new("".to_string(), "test".to_string(), on_del, on_upd, defer).part_len(), Err(Error::EmptyForeignTableName));
-                    assert_eq!(ForeignKey::new("test".to_string(), "".to_string(), on_del, on_upd, defer).part_len(), Err(Error::EmptyForeignColumnName));
+/// A Constraint spanning one or more [Column]s of a [Table], see [here](https://www.sqlite.org/lang_createtable.html#constraints).
+/// The [String]s are the names of the constrained [Column]s, in a [Table] they are quoted like the [Column] itself (see [Column::set_quote]).
+/// With serde, it is (de-)serialized flat, with a `type` of `primary_key`, `unique` or `foreign_key`, the `column` names and the options of that type.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(into = "TableConstraintRepr", try_from = "TableConstraintRepr"))]
+pub enum TableConstraint {
+    /// Composite Primary Key, it is an Error to also have a [PrimaryKey] on a [Column] of the same [Table] ([Error::MultiplePrimaryKeys]).
+    CompositePrimaryKey(Vec<String>, OnConflict),
+    /// Composite Unique Constraint, e.g. the combination of the values in the [Column]s cannot appear twice.
+    CompositeUnique(Vec<String>, OnConflict),
+    /// Composite Foreign Key, see [CompositeForeignKey].
+    CompositeForeignKey(CompositeForeignKey),
+}
 
-                    test_sql_part(&ForeignKey::new("test".to_string(), "test".to_string(), on_del, on_upd, defer))?;
+impl TableConstraint {
+    fn check(&self) -> Result<()> {
+        match self {
+            TableConstraint::CompositePrimaryKey(cols, _) | TableConstraint::CompositeUnique(cols, _) => {
+                if cols.is_empty() {
+                    return Err(Error::EmptyConstraintColumns);
                 }
             }
+            TableConstraint::CompositeForeignKey(fk) => { fk.check()?; }
         }
         Ok(())
     }
 
-    #[test]
-    fn test_column() -> Result<()> {
-        for typ in [SQLiteType::Blob, SQLiteType::Numeric, SQLiteType::Integer, SQLiteType::Real, SQLiteType::Text] {
-            for pk in [None, Some(PrimaryKey::default())] {
-                for uniq in [None, Some(Unique::default())] {
-                    for fk in [None, Some(ForeignKey::new_default("test".to_string(), "test".to_string()))] {
-                        for nn in [None, Some(NotNull::default())] {
-                            assert_eq!(Column::new(typ, "".to_string(),Clone::clone(&pk), uniq, Clone::clone(&fk), nn).part_len(), Err(Error::EmptyColumnName));
+    /// The [Quote] of the [Column] named `name` in `table_columns`, [Quote::None] if there is none
+    fn column_quote(table_columns: &[Column], name: &str) -> Quote {
+        table_columns.iter()
+            .find(|col| col.name.eq_ignore_ascii_case(name))
+            .map_or(Quote::None, |col| col.quote)
+    }
 
-                            let col: Column = Column::new(typ, "test".to_string(), Clone::clone(&pk), uniq, Clone::clone(&fk), nn);
+    fn columns_len(columns: &[String], quote_of: impl Fn(&str) -> Quote) -> usize {
+        columns.iter().map(|col| quote_of(col).ident_len(col)).sum::<usize>() + columns.len().saturating_sub(1) * 2 // ", "
+    }
 
-                            if col.pk.is_some() && col.fk.is_some() {
-                                assert_eq!(col.part_len(), Err(Error::PrimaryKeyAndForeignKey));
-                            } else if col.pk.is_some() && col.unique.is_some() {
-                                assert_eq!(col.part_len(), Err(Error::PrimaryKeyAndUnique));
-                            } else {
-                                test_sql_part(&col)?;
-                            }
-                        }
-                    }
-                }
+    fn columns_str(columns: &[String], quote_of: impl Fn(&str) -> Quote, sql: &mut String) {
+        let mut needs_comma = false;
+        for col in columns {
+            if needs_comma {
+                sql.push_str(", ");
+            }
+            quote_of(col).push_ident(sql, col.as_str());
+            needs_comma = true;
+        }
+    }
+
+    /// Length of the Constraint, the [Column] names are quoted like the matching `table_columns`
+    fn write_len(&self, table_columns: &[Column]) -> Result<usize> {
+        self.check()?;
+        let quote_of = |name: &str| Self::column_quote(table_columns, name);
+        Ok(match self {
+            TableConstraint::CompositePrimaryKey(cols, on_conflict) => {
+                13 // "PRIMARY KEY ("
+                + Self::columns_len(cols, quote_of)
+                + 2 // ") "
+                + on_conflict.part_len()?
+            }
+            TableConstraint::CompositeUnique(cols, on_conflict) => {
+                8 // "UNIQUE ("
+                + Self::columns_len(cols, quote_of)
+                + 2 // ") "
+                + on_conflict.part_len()?
+            }
+            TableConstraint::CompositeForeignKey(fk) => { fk.write_len(table_columns)? }
+        })
+    }
+
+    /// Writes the Constraint, see [TableConstraint::write_len]
+    fn write_str(&self, sql: &mut String, table_columns: &[Column]) -> Result<()> {
+        self.check()?;
+        let quote_of = |name: &str| Self::column_quote(table_columns, name);
+        match self {
+            TableConstraint::CompositePrimaryKey(cols, on_conflict) => {
+                sql.push_str("PRIMARY KEY (");
+                Self::columns_str(cols, quote_of, sql);
+                sql.push_str(") ");
+                on_conflict.part_str(sql)?;
             }
+            TableConstraint::CompositeUnique(cols, on_conflict) => {
+                sql.push_str("UNIQUE (");
+                Self::columns_str(cols, quote_of, sql);
+                sql.push_str(") ");
+                on_conflict.part_str(sql)?;
+            }
+            TableConstraint::CompositeForeignKey(fk) => { fk.write_str(sql, table_columns)?; }
         }
         Ok(())
     }
+}
 
-    #[test]
-    fn test_table() -> Result<()> {
-        'poss: for mut possible in Table::possibilities(false).into_iter().map(|boxed| *boxed) {
-            let mut has_pk: bool = false;
+impl SQLPart for TableConstraint {
+    fn part_len(&self) -> Result<usize> {
+        self.write_len(&[])
+    }
 
-            for col in &possible.columns {
-                if col.pk.is_some() && col.unique.is_some() {
-                    assert_eq!(col.part_len(), Err(Error::PrimaryKeyAndUnique));
-                    continue 'poss;
-                }
-                if col.pk.is_some() && col.fk.is_some() {
-                    assert_eq!(col.part_len(), Err(Error::PrimaryKeyAndForeignKey));
-                    continue 'poss;
-                }
-                if col.pk.is_some() {
+    fn part_str(&self, sql: &mut String) -> Result<()> {
+        self.write_str(sql, &[])
+    }
+
+    #[cfg(test)]
+    fn possibilities(illegal: bool) -> Vec<Box<Self>> {
+        let mut ret: Vec<Box<Self>> = Vec::new();
+        for conf in OnConflict::possibilities(false) {
+            for col_num in [if illegal { 0 } else { 3 }, 1, 2] {
+                let cols: Vec<String> = (0..col_num).map(|n| format!("test{}", n)).collect();
+                ret.push(Box::new(Self::CompositePrimaryKey(cols.clone(), *conf)));
+                ret.push(Box::new(Self::CompositeUnique(cols, *conf)));
+            }
+        }
+        for fk in CompositeForeignKey::possibilities(illegal) {
+            ret.push(Box::new(Self::CompositeForeignKey(*fk)));
+        }
+        ret
+    }
+}
+
+/// The `type` of a [TableConstraintRepr]
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum TableConstraintKind {
+    PrimaryKey,
+    Unique,
+    ForeignKey,
+}
+
+/// Flat serde representation of a [TableConstraint], the `foreign_*`, `on_*` and `deferrable` fields are only used by Foreign Keys
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct TableConstraintRepr {
+    #[serde(rename = "type")]
+    kind: TableConstraintKind,
+    #[serde(rename = "column")]
+    columns: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    on_conflict: Option<OnConflict>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    foreign_table: Option<String>,
+    #[serde(rename = "foreign_column", default, skip_serializing_if = "Vec::is_empty")]
+    foreign_columns: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    quote: Option<Quote>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    on_delete: Option<FKOnAction>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    on_update: Option<FKOnAction>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    deferrable: Option<FKDeferrable>,
+}
+
+#[cfg(feature = "serde")]
+impl From<TableConstraint> for TableConstraintRepr {
+    fn from(constraint: TableConstraint) -> Self {
+        let (kind, columns, on_conflict) = match constraint {
+            TableConstraint::CompositePrimaryKey(cols, on_conflict) => (TableConstraintKind::PrimaryKey, cols, on_conflict),
+            TableConstraint::CompositeUnique(cols, on_conflict) => (TableConstraintKind::Unique, cols, on_conflict),
+            TableConstraint::CompositeForeignKey(fk) => {
+                return Self {
+                    kind: TableConstraintKind::ForeignKey,
+                    columns: fk.local_columns,
+                    on_conflict: None,
+                    foreign_table: Some(fk.foreign_table),
+                    foreign_columns: fk.foreign_columns,
+                    quote: Some(fk.quote),
+                    on_delete: fk.on_delete,
+                    on_update: fk.on_update,
+                    deferrable: Some(fk.deferrable),
+                };
+            }
+        };
+        Self {
+            kind,
+            columns,
+            on_conflict: Some(on_conflict),
+            foreign_table: None,
+            foreign_columns: Vec::new(),
+            quote: None,
+            on_delete: None,
+            on_update: None,
+            deferrable: None,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<TableConstraintRepr> for TableConstraint {
+    type Error = Error;
+
+    fn try_from(repr: TableConstraintRepr) -> Result<Self> {
+        Ok(match repr.kind {
+            TableConstraintKind::PrimaryKey => TableConstraint::CompositePrimaryKey(repr.columns, repr.on_conflict.unwrap_or_default()),
+            TableConstraintKind::Unique => TableConstraint::CompositeUnique(repr.columns, repr.on_conflict.unwrap_or_default()),
+            TableConstraintKind::ForeignKey => TableConstraint::CompositeForeignKey(CompositeForeignKey {
+                local_columns: repr.columns,
+                foreign_table: repr.foreign_table.ok_or(Error::EmptyForeignTableName)?,
+                foreign_columns: repr.foreign_columns,
+                quote: repr.quote.unwrap_or_default(),
+                on_delete: repr.on_delete,
+                on_update: repr.on_update,
+                deferrable: repr.deferrable.unwrap_or_default(),
+            }),
+        })
+    }
+}
+
+// endregion
+
+// region Table
+
+/// Represents an entire Table, which may be Part of a wider [Schema] or used standalone.
+/// Can be converted into an SQL Statement via the [SQLStatement] Methods.
+/// It is a Error for the `name` to be empty ([Error::EmptyTableName]) or the Table itself to be empty ([Error::NoColumns]).
+#[derive(Debug, Clone, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Table {
+    name: String,
+    #[cfg_attr(feature = "serde", serde(rename = "column"))]
+    columns: Vec<Column>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    without_rowid: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    strict: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    temp: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    quote: Quote,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    comment: Option<String>,
+    #[cfg_attr(feature = "serde", serde(rename = "constraint", default, skip_serializing_if = "Vec::is_empty"))]
+    constraints: Vec<TableConstraint>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) if_exists: bool,
+}
+
+impl Table {
+    fn check(&self) -> Result<()> {
+        let mut has_pk: bool = false;
+        for col in &self.columns {
+            if col.pk.is_some() {
+                if has_pk {
+                    return Err(Error::MultiplePrimaryKeys);
+                } else {
                     has_pk = true;
                 }
             }
-            if !possible.without_rowid && has_pk {
-                assert_eq!(possible.part_len(), Err(Error::WithoutRowidNoPrimaryKey));
-                continue;
+        }
+        for constraint in &self.constraints {
+            constraint.check()?;
+            if let TableConstraint::CompositePrimaryKey(..) = constraint {
+                if has_pk {
+                    return Err(Error::MultiplePrimaryKeys);
+                } else {
+                    has_pk = true;
+                }
             }
+        }
 
-            if possible.name.is_empty() {
-                assert_eq!(possible.part_len(), Err(Error::EmptyTableName));
-                continue;
+        if self.name.is_empty() {
+            return Err(Error::EmptyTableName);
+        }
+
+        if self.columns.is_empty() {
+            return Err(Error::NoColumns)
+        }
+
+        for (num, col) in self.columns.iter().enumerate() {
+            // SQLite identifiers are case-insensitive (for ASCII), so `id` and `ID` clash
+            if self.columns[..num].iter().any(|other| other.name.eq_ignore_ascii_case(&col.name)) {
+                return Err(Error::DuplicateColumnName(col.name.clone()));
             }
+        }
 
-            if possible.columns.is_empty() {
-                assert_eq!(possible.part_len(), Err(Error::NoColumns));
-                continue;
+        for constraint in &self.constraints {
+            let cols: &[String] = match constraint {
+                TableConstraint::CompositePrimaryKey(cols, _) | TableConstraint::CompositeUnique(cols, _) => cols,
+                TableConstraint::CompositeForeignKey(fk) => &fk.local_columns,
+            };
+            if let Some(missing) = cols.iter().find(|name| !self.has_column(name)) {
+                return Err(Error::ColumnNotFound(missing.clone()));
             }
+        }
 
-            test_sql_part(&possible)?;
-            test_sql(&mut possible)?; // FUCK
+        if self.without_rowid && !has_pk {
+            return Err(Error::WithoutRowidNoPrimaryKey);
+        }
+
+        if self.without_rowid && self.columns.iter().any(|col| col.pk.as_ref().is_some_and(|pk| pk.autoincrement)) {
+            return Err(Error::AutoincrementOnWithoutRowidTable);
+        }
+
+        // see https://www.sqlite.org/stricttables.html
+        if self.strict {
+            if let Some(col) = self.columns.iter().find(|col| col.typ == SQLiteType::Numeric) {
+                return Err(Error::StrictTableInvalidType { column: col.name.clone(), typ: col.typ });
+            }
         }
         Ok(())
     }
 
-    #[test]
-    fn test_schema() -> Result<()> {
-        {
-            let mut schema: Schema = Schema::new();
-            assert_eq!(schema.len(false, false), Err(Error::SchemaWithoutTables));
+    /// Writes the `CREATE TABLE` Statement, with every [Column] and [TableConstraint] on its own line if `indent` is set.
+    /// The name is qualified by `schema`, see [Schema::set_name].
+    fn write_str(&self, sql: &mut String, indent: Option<usize>, schema: Option<&str>) -> Result<()> {
+        self.check()?;
+
+        // comments only in the pretty format
+        if let (Some(comment), Some(_)) = (self.comment.as_ref(), indent) {
+            push_comment(sql, comment.as_str());
+            sql.push('\n');
         }
-        for num_tbl in 1..3 {
-            let mut schema: Schema = Schema::new();
-            for tbl_idx in 0..num_tbl {
-                let mut tbl = Table::new_default(format!("table{}", tbl_idx));
-                tbl = tbl.add_column(Column::new_default("testcol".to_string()));
-                schema = schema.add_table(tbl);
+        sql.push_str("CREATE ");
+        if self.temp {
+            sql.push_str("TEMPORARY ");
+        }
+        sql.push_str("TABLE ");
+        if self.if_exists {
+            sql.push_str("IF NOT EXISTS ");
+        }
+        push_schema_prefix(sql, schema);
+        self.quote.push_ident(sql, self.name.as_str());
+        sql.push_str(" (");
+
+        let part_num: usize = self.columns.len() + self.constraints.len();
+        for (num, coll) in self.columns.iter().enumerate() {
+            Self::push_indent(sql, indent);
+            coll.part_str(sql)?;
+            if num + 1 < part_num {
+                sql.push(',');
             }
-            test_sql(&mut schema)?;
+            // after the comma, otherwise it would be commented out
+            if let (Some(comment), Some(_)) = (coll.comment.as_ref(), indent) {
+                sql.push(' ');
+                push_comment(sql, comment.as_str());
+            }
+        }
+        for (num, constraint) in self.constraints.iter().enumerate() {
+            Self::push_indent(sql, indent);
+            constraint.write_str(sql, &self.columns)?;
+            if self.columns.len() + num + 1 < part_num {
+                sql.push(',');
+            }
+        }
+        if indent.is_some() {
+            sql.push('\n');
         }
+        sql.push(')');
+
 
+        if self.without_rowid {
+            sql.push_str(" WITHOUT ROWID");
+        }
+        if self.without_rowid && self.strict  {
+            sql.push(',');
+        }
+        if self.strict {
+            sql.push_str(" STRICT");
+        }
         Ok(())
     }
 
-    #[cfg(feature = "xml-config")]
-    mod xml_tests {
-        use super::*;
-
-        #[test]
-        fn test_serialize_deserialize() -> Result<()> {
-            let tbl = Table::new_default("TestName".to_string()).add_column(Column::new_default("TestCol".to_string()));
-            let tbl2  = tbl.clone().set_name("TestName2".to_string());
-            let schema = Schema::new().add_table(tbl).add_table(tbl2);
-            // todo: this is bullshit
-            let serialized: &'static str = Box::leak(quick_xml::se::to_string(&schema)?.into_boxed_str());
-            println!("Serialized XML: \n{}", serialized);
-            let deserialized: Schema = quick_xml::de::from_str(serialized)?;
-            assert_eq!(schema, deserialized);
-            Ok(())
+    fn push_indent(sql: &mut String, indent: Option<usize>) {
+        if let Some(indent) = indent {
+            sql.push('\n');
+            sql.push_str(" ".repeat(indent).as_str());
         }
+    }
 
-        #[test]
-        fn some_test() -> Result<()> {
-            let raw: &str = r#"
-<?xml version="1.0" encoding="UTF-8" standalone="yes" ?>
-<schema xmlns="https://crates.io/crates/sqlayout">
+    pub fn new(name: String, columns: Vec<Column>, without_rowid: bool, strict: bool) -> Self {
+        Self {
+            name,
+            columns,
+            without_rowid,
+            strict,
+            temp: false,
+            quote: Quote::None,
+            comment: None,
+            constraints: Vec::new(),
+            if_exists: false,
+        }
+    }
 
-  <!-- Card data -->
-  <table name="updates" strict="true">
-    <column name="ID" type="integer">
-      <pk/>
-      <not_null/>
-    </column>
-    <column name="timestamp" type="integer">
-      <not_null/>
-    </column>
-    <column name="guid" type="text">
-      <not_null/>
-      <unique/>
-    </column>
-  </table>
+    pub fn new_default(name: String) -> Self {
+        Self {
+            name,
+            columns: Vec::new(),
+            without_rowid: false,
+            strict: false,
+            temp: false,
+            quote: Quote::None,
+            comment: None,
+            constraints: Vec::new(),
+            if_exists: false,
+        }
+    }
 
-  <table name="migrations" strict="true">
-    <column name="ID" type="integer">
-      <pk/>
-      <not_null/>
-    </column>
-    <column name="timestamp" type="integer">
-      <not_null/>
-    </column>
-    <column name="GUID" type="text">
-      <not_null/>
-      <unique/>
-    </column>
-  </table>
+    /// Writes the whole Statement like [SQLStatement::build_into], see [Table::write_str] for `indent` and `schema`.
+    fn write_stmt(&mut self, sql: &mut String, transaction: bool, if_exists: bool, indent: Option<usize>, schema: Option<&str>) -> Result<()> {
+        self.if_exists = if_exists;
+        if transaction {
+            sql.push_str("BEGIN;\n");
+        }
+        self.write_str(sql, indent, schema)?;
+        sql.push(';');
+        if transaction {
+            sql.push_str("\nEND;");
+        }
+        Ok(())
+    }
 
-  <table name="card_data" strict="true">
-    <column name="ID" type="integer">
-      <pk/>
-      <not_null/>
-    </column>
-  </table>
+    /// Whether the DB enforces NOT NULL on `col` without a [NotNull] constraint, which is the case for the Primary Key [Column]s
+    /// of WITHOUT ROWID and STRICT Tables, except for the rowid alias (see [here](https://www.sqlite.org/lang_createtable.html#rowid)).
+    /// `integer` is whether the declared type of `col` is exactly `INTEGER`.
+    #[cfg(feature = "rusqlite")]
+    fn implies_not_null(&self, col: &Column, integer: bool) -> bool {
+        let composite: Option<&Vec<String>> = self.constraints.iter().find_map(|constraint| match constraint {
+            TableConstraint::CompositePrimaryKey(cols, _) => Some(cols),
+            _ => None,
+        });
+        let in_composite: bool = composite.is_some_and(|cols| cols.iter().any(|pk| pk.eq_ignore_ascii_case(col.name.as_str())));
+        if col.pk.is_none() && !in_composite {
+            return false;
+        }
+        if self.without_rowid {
+            return true;
+        }
+        // `INTEGER PRIMARY KEY DESC` is no rowid alias, unlike a single Column `PRIMARY KEY (... DESC)`
+        let rowid_alias: bool = integer
+            && composite.is_none_or(|cols| cols.len() == 1)
+            && col.pk.as_ref().is_none_or(|pk| pk.sort_order != Order::Descending);
+        self.strict && !rowid_alias
+    }
 
-  <!-- Collection Data -->
-  <table name="card_location" strict="true">
-    <column name="ID" type="integer">
-      <pk/>
+    /// Compares the [Column]s of this Table to the ones in the DB, used by [Schema::check_db].
+    /// Deviations are appended to `ret`, prefixed with the Table number `num`.
+    #[cfg(feature = "rusqlite")]
+    fn check_db_columns(&self, num: usize, conn: &Connection, ret: &mut String) -> Result<(), CheckError> {
+        let mut stmt: Statement = conn.prepare(r#"SELECT name, type, "notnull" FROM pragma_table_info(?1) ORDER BY cid;"#)?;
+        let mut rows: Rows = stmt.query([self.name.as_str()])?;
+
+        for (col_num, col) in self.columns.iter().enumerate() {
+            let row: &Row = match rows.next()? {
+                None => {
+                    write!(ret, "Table {} Column {}: expected column '{}', got nothing; ", num, col_num, col.name)?;
+                    break
+                }
+                Some(row) => { row }
+            };
+            let name: String = row.get("name")?;
+            if col.name != name {
+                write!(ret, "Table {} Column {}: expected name '{}', got '{}'; ", num, col_num, col.name, name)?;
+            }
+            // the declared type may be any name with the same affinity, e.g. `INT`
+            let typ: String = row.get("type")?;
+            if !col.typ.compatible_with(SQLiteType::from_affinity_str(typ.as_str())) {
+                write!(ret, "Table {} Column {}: expected type '{}', got '{}'; ", num, col_num, col.typ, typ)?;
+            }
+            let not_null: bool = col.not_null.is_some() || self.implies_not_null(col, col.typ == SQLiteType::Integer);
+            if not_null != row.get::<&str, bool>("notnull")? {
+                write!(ret, "Table {} Column {}: expected not_null {}, got {}; ", num, col_num, not_null, !not_null)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Compares the [ForeignKey]s and [CompositeForeignKey]s of this Table to the ones in the DB, used by [Schema::check_db].
+    /// Foreign Keys are compared per referencing [Column], deviations are appended to `ret`, prefixed with the Table number `num`.
+    #[cfg(feature = "rusqlite")]
+    fn check_db_foreign_keys(&self, num: usize, conn: &Connection, ret: &mut String) -> Result<(), CheckError> {
+        // (from, table, to)
+        let mut expected: Vec<(&str, &str, &str)> = Vec::new();
+        for col in &self.columns {
+            if let Some(fk) = col.fk.as_ref() {
+                expected.push((col.name.as_str(), fk.foreign_table.as_str(), fk.foreign_column.as_str()));
+            }
+        }
+        for constraint in &self.constraints {
+            if let TableConstraint::CompositeForeignKey(fk) = constraint {
+                for (from, to) in fk.local_columns.iter().zip(fk.foreign_columns.iter()) {
+                    expected.push((from.as_str(), fk.foreign_table.as_str(), to.as_str()));
+                }
+            }
+        }
+
+        let mut stmt: Statement = conn.prepare(r#"SELECT "from", "table", "to" FROM pragma_foreign_key_list(?1) ORDER BY id, seq;"#)?;
+        let mut rows: Rows = stmt.query([self.name.as_str()])?;
+        let mut actual: Vec<(String, String, String)> = Vec::new();
+        while let Some(row) = rows.next()? {
+            // "to" is NULL when referencing the Primary Key implicitly
+            actual.push((row.get("from")?, row.get("table")?, row.get::<&str, Option<String>>("to")?.unwrap_or_default()));
+        }
+
+        for (from, table, to) in &expected {
+            if !actual.iter().any(|(act_from, act_table, act_to)| act_from == from && act_table == table && act_to == to) {
+                write!(ret, "Table {}: expected foreign key '{}' -> '{}.{}', got nothing; ", num, from, table, to)?;
+            }
+        }
+        for (from, table, to) in &actual {
+            if !expected.iter().any(|(exp_from, exp_table, exp_to)| exp_from == from && exp_table == table && exp_to == to) {
+                write!(ret, "Table {}: expected nothing, got foreign key '{}' -> '{}.{}'; ", num, from, table, to)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the Table `name` from the DB, see [Schema::from_db].
+    #[cfg(feature = "rusqlite")]
+    fn from_db(name: String, without_rowid: bool, strict: bool, conn: &Connection) -> Result<Table, CheckError> {
+        let mut columns: Vec<Column> = Vec::new();
+        // whether the declared type of each Column is exactly `INTEGER`, see [Table::implies_not_null]
+        let mut integers: Vec<bool> = Vec::new();
+        // (pk position, name)
+        let mut pk_columns: Vec<(usize, String)> = Vec::new();
+        let mut stmt: Statement = conn.prepare(r#"SELECT name, type, "notnull", dflt_value, pk FROM pragma_table_info(?1) ORDER BY cid;"#)?;
+        let mut rows: Rows = stmt.query([name.as_str()])?;
+        while let Some(row) = rows.next()? {
+            let col_name: String = row.get("name")?;
+            let typ: String = row.get("type")?;
+            // `ANY` is only special in STRICT Tables, elsewhere it is just a name with NUMERIC affinity
+            if strict && typ.eq_ignore_ascii_case("ANY") {
+                return Err(Error::StrictTableAnyColumn { table: name, column: col_name }.into());
+            }
+            let pk: usize = row.get("pk")?;
+            if pk > 0 {
+                pk_columns.push((pk, col_name.clone()));
+            }
+            integers.push(typ.eq_ignore_ascii_case("INTEGER"));
+            columns.push(
+                Column::new_typed(SQLiteType::from_affinity_str(typ.as_str()), col_name)
+                    .set_not_null(row.get::<&str, bool>("notnull")?.then(NotNull::default))
+                    .set_default(row.get::<&str, Option<String>>("dflt_value")?.as_deref().map(DefaultValue::from_db))
+            );
+        }
+        pk_columns.sort_unstable();
+        let mut tbl: Table = Table::new(name, columns, without_rowid, strict);
+
+        // the order of a Primary Key is only visible in its automatic Index, which rowid aliases do not have
+        let pk_order: Order = {
+            let mut stmt: Statement = conn.prepare(r#"SELECT x."desc" FROM pragma_index_list(?1) AS l, pragma_index_xinfo(l.name) AS x WHERE l.origin == "pk" AND x.key == 1 LIMIT 1;"#)?;
+            let mut rows: Rows = stmt.query([tbl.name.as_str()])?;
+            match rows.next()? {
+                Some(row) if row.get::<usize, bool>(0)? => Order::Descending,
+                _ => Order::Ascending,
+            }
+        };
+        match pk_columns.as_slice() {
+            [] => {}
+            [(_, col_name)] => {
+                if let Some(col) = tbl.get_column_mut(col_name) {
+                    col.pk = Some(PrimaryKey::default().set_sort_order(pk_order).into());
+                }
+            }
+            _ => tbl.constraints.push(TableConstraint::CompositePrimaryKey(pk_columns.into_iter().map(|(_, col_name)| col_name).collect(), OnConflict::default())),
+        }
+        // NOT NULL implied by the Primary Key is not part of the Table definition
+        let implied: Vec<bool> = tbl.columns.iter().zip(integers).map(|(col, integer)| tbl.implies_not_null(col, integer)).collect();
+        for (col, implied) in tbl.columns.iter_mut().zip(implied) {
+            if implied {
+                col.not_null = None;
+            }
+        }
+
+        let mut stmt: Statement = conn.prepare(r#"SELECT l.name, i.name AS col FROM pragma_index_list(?1) AS l, pragma_index_info(l.name) AS i WHERE l.origin == "u" ORDER BY l.seq DESC, i.seqno;"#)?;
+        let mut rows: Rows = stmt.query([tbl.name.as_str()])?;
+        let mut uniques: Vec<(String, Vec<String>)> = Vec::new();
+        while let Some(row) = rows.next()? {
+            let idx_name: String = row.get("name")?;
+            let col_name: String = row.get("col")?;
+            match uniques.last_mut() {
+                Some((last, cols)) if *last == idx_name => cols.push(col_name),
+                _ => uniques.push((idx_name, vec![col_name])),
+            }
+        }
+        for (_, cols) in uniques {
+            if cols.len() == 1 {
+                if let Some(col) = tbl.get_column_mut(cols[0].as_str()) {
+                    col.unique = Some(Unique::default().into());
+                }
+            } else {
+                tbl.constraints.push(TableConstraint::CompositeUnique(cols, OnConflict::default()));
+            }
+        }
+
+        let mut stmt: Statement = conn.prepare(r#"SELECT id, "table", "from", "to", on_update, on_delete FROM pragma_foreign_key_list(?1) ORDER BY id DESC, seq;"#)?;
+        let mut rows: Rows = stmt.query([tbl.name.as_str()])?;
+        let mut fks: Vec<(i64, CompositeForeignKey)> = Vec::new();
+        while let Some(row) = rows.next()? {
+            let id: i64 = row.get("id")?;
+            let from: String = row.get("from")?;
+            let foreign_table: String = row.get("table")?;
+            // `to` is NULL when referencing the Primary Key of the foreign Table implicitly
+            let to: String = match row.get::<&str, Option<String>>("to")? {
+                Some(to) => to,
+                None => {
+                    let seq: usize = fks.iter().filter(|(other, _)| *other == id).map(|(_, fk)| fk.local_columns.len()).sum();
+                    conn.query_row(r#"SELECT name FROM pragma_table_info(?1) WHERE pk > 0 ORDER BY pk LIMIT 1 OFFSET ?2;"#, (foreign_table.as_str(), seq), |row| row.get(0))?
+                }
+            };
+            let action = |col: &str| -> Result<Option<FKOnAction>, CheckError> {
+                Ok(match FKOnAction::from_str(row.get::<&str, String>(col)?.as_str())? {
+                    FKOnAction::NoAction => None,
+                    action => Some(action),
+                })
+            };
+            match fks.last_mut() {
+                Some((last, fk)) if *last == id => {
+                    fk.local_columns.push(from);
+                    fk.foreign_columns.push(to);
+                }
+                _ => fks.push((id, CompositeForeignKey::new(vec![from], foreign_table, vec![to], action("on_delete")?, action("on_update")?, FKDeferrable::default()))),
+            }
+        }
+        for (_, fk) in fks {
+            if fk.local_columns.len() == 1 {
+                if let Some(col) = tbl.get_column_mut(fk.local_columns[0].as_str()) {
+                    col.fk = Some(ForeignKey::new(fk.foreign_table, fk.foreign_columns[0].clone(), fk.on_delete, fk.on_update, fk.deferrable).into());
+                }
+            } else {
+                tbl.constraints.push(TableConstraint::CompositeForeignKey(fk));
+            }
+        }
+        Ok(tbl)
+    }
+
+    /// Creates the Table if it does not exist yet and returns the plan SQLite chooses for `query` (see [here](https://www.sqlite.org/eqp.html)),
+    /// with one line per step, indented by two spaces per level of nesting.
+    #[cfg(feature = "rusqlite")]
+    pub fn explain_query_plan(&mut self, query: &str, conn: &Connection) -> Result<String, CheckError> {
+        conn.execute_batch(self.build(false, true)?.as_str())?;
+
+        let mut stmt: Statement = conn.prepare(format!("EXPLAIN QUERY PLAN {}", query).as_str())?;
+        let mut rows: Rows = stmt.query(())?;
+        // (id, depth)
+        let mut depths: Vec<(i64, usize)> = Vec::new();
+        let mut ret: String = String::new();
+        while let Some(row) = rows.next()? {
+            let id: i64 = row.get("id")?;
+            let parent: i64 = row.get("parent")?;
+            let detail: String = row.get("detail")?;
+            let depth: usize = depths.iter().find(|(other, _)| *other == parent).map_or(0, |(_, depth)| depth + 1);
+            depths.push((id, depth));
+            if !ret.is_empty() {
+                ret.push('\n');
+            }
+            write!(ret, "{:indent$}{}", "", detail, indent = depth * 2)?;
+        }
+        Ok(ret)
+    }
+
+    /// Creates a Table from any iterator of [Column]s, without `WITHOUT ROWID` or `STRICT`
+    pub fn new_from_columns(name: String, columns: impl IntoIterator<Item = Column>) -> Self {
+        Self::new(name, columns.into_iter().collect(), false, false)
+    }
+
+    fn version_requirements(&self) -> Vec<CompatibilityWarning> {
+        let mut ret: Vec<CompatibilityWarning> = Vec::new();
+        if self.without_rowid {
+            ret.push(CompatibilityWarning::WITHOUT_ROWID);
+        }
+        if self.strict {
+            ret.push(CompatibilityWarning::STRICT);
+        }
+        if self.columns.iter().any(|col| col.fk.is_some()) || self.constraints.iter().any(|cons| matches!(cons, TableConstraint::CompositeForeignKey(_))) {
+            ret.push(CompatibilityWarning::FOREIGN_KEY);
+        }
+        ret
+    }
+
+    /// Reads and deserializes a Table from a XML file. The result is not checked.
+    /// I/O Errors are reported as [DeError::InvalidXml].
+    #[cfg(feature = "xml-config")]
+    pub fn from_xml_file(path: impl AsRef<Path>) -> Result<Table, DeError> {
+        let xml: String = std::fs::read_to_string(path).map_err(quick_xml::Error::from)?;
+        xml::from_str(xml.as_str())
+    }
+
+    /// Deserializes a Table from any buffered reader (e.g. a `BufReader<File>`), see [xml::from_reader]. The result is not checked.
+    #[cfg(feature = "xml-config")]
+    pub fn from_xml_reader<R: std::io::BufRead>(reader: R) -> Result<Table, DeError> {
+        xml::from_reader(reader)
+    }
+
+    /// Serializes the Table and writes it to a XML file, with `table` as the root element.
+    /// I/O Errors are reported as [DeError::InvalidXml] (quick-xml uses [DeError] for serialization as well).
+    #[cfg(feature = "xml-config")]
+    pub fn to_xml_file(&self, path: impl AsRef<Path>) -> Result<(), DeError> {
+        let xml: String = xml::to_string_with_root("table", self)?;
+        std::fs::write(path, xml).map_err(quick_xml::Error::from)?;
+        Ok(())
+    }
+
+    /// Deserializes a Table from JSON. The result is not checked.
+    #[cfg(feature = "json-config")]
+    pub fn from_json(s: &str) -> Result<Table, serde_json::Error> {
+        serde_json::from_str(s)
+    }
+
+    /// Serializes the Table to JSON.
+    #[cfg(feature = "json-config")]
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    /// Deserializes a Table from TOML. The result is not checked.
+    #[cfg(feature = "toml-config")]
+    pub fn from_toml(s: &str) -> Result<Table> {
+        Ok(toml::from_str(s)?)
+    }
+
+    /// Serializes the Table to TOML.
+    #[cfg(feature = "toml-config")]
+    pub fn to_toml(&self) -> Result<String> {
+        Ok(toml::to_string(self)?)
+    }
+
+    pub fn set_name(mut self, name: String) -> Self {
+        self.name = name;
+        self
+    }
+
+    pub fn add_column(mut self, col: Column) -> Self {
+        self.columns.push(col);
+        self
+    }
+
+    /// Inserts `col` at position `index`, shifting all [Column]s after it.
+    /// Panics if `index` is greater than the number of Columns, like [Vec::insert].
+    pub fn insert_column(mut self, index: usize, col: Column) -> Self {
+        self.columns.insert(index, col);
+        self
+    }
+
+    /// Inserts `col` at position `index` like [Table::insert_column], but appends it if `index` is past the end instead of panicking.
+    pub fn add_column_at(mut self, col: Column, index: usize) -> Self {
+        let index: usize = index.min(self.columns.len());
+        self.columns.insert(index, col);
+        self
+    }
+
+    /// Inserts `col` directly after the first [Column] named `after`.
+    /// Fails with [Error::ColumnNotFound] if there is no such Column.
+    pub fn add_column_after(mut self, col: Column, after: &str) -> Result<Self> {
+        let pos: usize = self.columns.iter().position(|other| other.name.eq_ignore_ascii_case(after)).ok_or_else(|| Error::ColumnNotFound(after.to_string()))?;
+        self.columns.insert(pos + 1, col);
+        Ok(self)
+    }
+
+    /// Inserts `col` directly before the first [Column] named `before`.
+    /// Fails with [Error::ColumnNotFound] if there is no such Column.
+    pub fn add_column_before(mut self, col: Column, before: &str) -> Result<Self> {
+        let pos: usize = self.columns.iter().position(|other| other.name.eq_ignore_ascii_case(before)).ok_or_else(|| Error::ColumnNotFound(before.to_string()))?;
+        self.columns.insert(pos, col);
+        Ok(self)
+    }
+
+    /// Replaces the first [Column] with the same name as `col`, keeping its position.
+    /// Fails with [Error::ColumnNotFound] if there is no such Column.
+    pub fn replace_column(mut self, col: Column) -> Result<Self> {
+        match self.get_column_mut(col.name.as_str()) {
+            None => Err(Error::ColumnNotFound(col.name)),
+            Some(old) => {
+                *old = col;
+                Ok(self)
+            }
+        }
+    }
+
+    /// Renames the first [Column] named `old_name` and all references to it in the [TableConstraint]s of this Table,
+    /// like [AlterTableRenameColumn] does in the DB. Fails with [Error::ColumnNotFound] if there is no such Column
+    /// and with [Error::DuplicateColumnName] if another Column is already named `new_name`.
+    ///
+    /// [Check] expressions and [DefaultValue::Expr]s are free-form SQL and are not rewritten, nor are [Index]es or [ForeignKey]s in other Tables,
+    /// so any of them still using `old_name` has to be updated separately (SQLite rewrites them in the DB itself).
+    pub fn rename_column(mut self, old_name: &str, new_name: String) -> Result<Self> {
+        if self.columns.iter().any(|col| !col.name.eq_ignore_ascii_case(old_name) && col.name.eq_ignore_ascii_case(&new_name)) {
+            return Err(Error::DuplicateColumnName(new_name));
+        }
+        let col: &mut Column = self.get_column_mut(old_name).ok_or_else(|| Error::ColumnNotFound(old_name.to_string()))?;
+        col.name = new_name.clone();
+        let self_name: String = self.name.clone();
+        for constraint in &mut self.constraints {
+            let cols: Vec<&mut String> = match constraint {
+                TableConstraint::CompositePrimaryKey(cols, _) | TableConstraint::CompositeUnique(cols, _) => cols.iter_mut().collect(),
+                TableConstraint::CompositeForeignKey(fk) if fk.foreign_table.eq_ignore_ascii_case(&self_name) => fk.local_columns.iter_mut().chain(fk.foreign_columns.iter_mut()).collect(),
+                TableConstraint::CompositeForeignKey(fk) => fk.local_columns.iter_mut().collect(),
+            };
+            for col in cols.into_iter().filter(|col| col.eq_ignore_ascii_case(old_name)) {
+                col.clone_from(&new_name);
+            }
+        }
+        for col in &mut self.columns {
+            if let Some(fk) = col.fk.as_mut() {
+                if fk.foreign_table.eq_ignore_ascii_case(&self_name) && fk.foreign_column.eq_ignore_ascii_case(old_name) {
+                    fk.foreign_column.clone_from(&new_name);
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    /// Moves the [Column] with a [PrimaryKey] to the front, keeping the order of the other Columns.
+    /// Note that this changes the Column order of e.g. `SELECT *` or `INSERT` without Column names.
+    pub fn sort_columns_pk_first(self) -> Self {
+        self.sort_columns_by(|a, b| b.pk.is_some().cmp(&a.pk.is_some()))
+    }
+
+    /// Sorts the [Column]s with the comparator `f`, keeping the order of equal Columns.
+    pub fn sort_columns_by<F: Fn(&Column, &Column) -> std::cmp::Ordering>(mut self, f: F) -> Self {
+        self.columns.sort_by(f);
+        self
+    }
+
+    /// Removes the first [Column] named `name` and returns it, if there is one.
+    pub fn remove_column(&mut self, name: &str) -> Option<Column> {
+        let pos: usize = self.columns.iter().position(|col| col.name.eq_ignore_ascii_case(name))?;
+        Some(self.columns.remove(pos))
+    }
+
+    /// Returns the first [Column] named `name`, if there is one.
+    /// Like in SQLite, Column names are compared case-insensitively (for ASCII), as by every other Column lookup of a Table.
+    pub fn get_column(&self, name: &str) -> Option<&Column> {
+        self.columns.iter().find(|col| col.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Returns the first [Column] named `name` for in-place modification, if there is one.
+    pub fn get_column_mut(&mut self, name: &str) -> Option<&mut Column> {
+        self.columns.iter_mut().find(|col| col.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn column_count(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Returns the [Column] at position `index` (e.g. the `cid` of `PRAGMA table_info`), if there is one.
+    pub fn column_at(&self, index: usize) -> Option<&Column> {
+        self.columns.get(index)
+    }
+
+    /// Returns the [Column] at position `index` for in-place modification, if there is one.
+    pub fn column_at_mut(&mut self, index: usize) -> Option<&mut Column> {
+        self.columns.get_mut(index)
+    }
+
+    pub fn first_column(&self) -> Option<&Column> {
+        self.columns.first()
+    }
+
+    pub fn last_column(&self) -> Option<&Column> {
+        self.columns.last()
+    }
+
+    /// Whether the Table contains a [Column] named `name`
+    pub fn has_column(&self, name: &str) -> bool {
+        self.get_column(name).is_some()
+    }
+
+    /// Whether the Table has a [PrimaryKey] [Column] or a [CompositePrimaryKey](TableConstraint::CompositePrimaryKey)
+    pub fn has_primary_key(&self) -> bool {
+        self.primary_key_column().is_some() || self.constraints.iter().any(|cons| matches!(cons, TableConstraint::CompositePrimaryKey(..)))
+    }
+
+    /// Whether the Table has a [ForeignKey] [Column] or a [CompositeForeignKey]
+    pub fn has_foreign_keys(&self) -> bool {
+        self.foreign_key_columns().next().is_some() || self.constraints.iter().any(|cons| matches!(cons, TableConstraint::CompositeForeignKey(_)))
+    }
+
+    /// Returns the first [Column] with a [PrimaryKey], if there is one.
+    /// A [CompositePrimaryKey](TableConstraint::CompositePrimaryKey) is not considered.
+    pub fn primary_key_column(&self) -> Option<&Column> {
+        self.columns.iter().find(|col| col.pk.is_some())
+    }
+
+    /// Same as [Table::primary_key_column], for in-place modification.
+    pub fn primary_key_column_mut(&mut self) -> Option<&mut Column> {
+        self.columns.iter_mut().find(|col| col.pk.is_some())
+    }
+
+    /// Returns all [Column]s with a [ForeignKey].
+    pub fn foreign_key_columns(&self) -> impl Iterator<Item = &Column> {
+        self.columns.iter().filter(|col| col.fk.is_some())
+    }
+
+    /// Same as [Table::foreign_key_columns], for in-place modification.
+    pub fn foreign_key_columns_mut(&mut self) -> impl Iterator<Item = &mut Column> {
+        self.columns.iter_mut().filter(|col| col.fk.is_some())
+    }
+
+    pub fn add_constraint(mut self, constraint: TableConstraint) -> Self {
+        self.constraints.push(constraint);
+        self
+    }
+
+    pub fn add_unique_constraint(mut self, columns: Vec<String>, on_conflict: OnConflict) -> Self {
+        self.constraints.push(TableConstraint::CompositeUnique(columns, on_conflict));
+        self
+    }
+
+    pub fn add_composite_fk(mut self, fk: CompositeForeignKey) -> Self {
+        self.constraints.push(TableConstraint::CompositeForeignKey(fk));
+        self
+    }
+
+    pub fn set_without_rowid(mut self, without_rowid: bool) -> Self {
+        self.without_rowid = without_rowid;
+        self
+    }
+
+    pub fn set_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    pub fn set_temp(mut self, temp: bool) -> Self {
+        self.temp = temp;
+        self
+    }
+
+    pub fn set_quote(mut self, quote: Quote) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    /// Removes all constraints of the Table and its [Column]s (see [Column::strip_constraints]) and disables `WITHOUT ROWID` and `STRICT`,
+    /// e.g. for inserting minimal test data.
+    pub fn strip_constraints(mut self) -> Self {
+        self.columns = self.columns.into_iter().map(Column::strip_constraints).collect();
+        self.constraints.clear();
+        self.without_rowid = false;
+        self.strict = false;
+        self
+    }
+
+    /// Sets the comment emitted by [SQLStatement::build_pretty], see [Table::comment]
+    pub fn set_comment(mut self, comment: Option<String>) -> Self {
+        self.comment = comment;
+        self
+    }
+
+    /// Description of the Table, only emitted as a SQL comment by [SQLStatement::build_pretty] (above the `CREATE TABLE`)
+    pub fn comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+
+    pub fn quote(&self) -> Quote {
+        self.quote
+    }
+
+    /// The name as emitted in SQL, quoted according to [Table::quote]
+    pub fn quoted_name(&self) -> String {
+        let mut ret: String = String::with_capacity(self.quote.ident_len(self.name.as_str()));
+        self.quote.push_ident(&mut ret, self.name.as_str());
+        ret
+    }
+
+    /// Builds the `CREATE TABLE` Statement, same as [SQLStatement::build]. See [Table::to_drop_stmt] for the counterpart.
+    pub fn to_create_stmt(&mut self, transaction: bool, if_exists: bool) -> Result<String> {
+        self.build(transaction, if_exists)
+    }
+
+    /// Returns the `DROP TABLE [IF EXISTS] name;` Statement for this Table, like [DropTable] with the Table's [Quote] (see [DropTable::set_quote]).
+    /// The name is not checked, see [Table::to_create_stmt].
+    pub fn to_drop_stmt(&self, if_exists: bool) -> String {
+        format!("DROP TABLE {}{};", if if_exists { "IF EXISTS " } else { "" }, self.quoted_name())
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn columns(&self) -> &[Column] {
+        self.columns.as_slice()
+    }
+
+    pub fn columns_iter(&self) -> impl Iterator<Item = &Column> {
+        self.columns.iter()
+    }
+
+    pub fn columns_mut(&mut self) -> impl Iterator<Item = &mut Column> {
+        self.columns.iter_mut()
+    }
+
+    pub fn constraints(&self) -> &[TableConstraint] {
+        self.constraints.as_slice()
+    }
+
+    pub fn is_without_rowid(&self) -> bool {
+        self.without_rowid
+    }
+
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    pub fn is_temp(&self) -> bool {
+        self.temp
+    }
+}
+
+impl SQLPart for Table {
+    fn part_len(&self) -> Result<usize> {
+        self.check()?;
+        let mut cols_len: usize = 0;
+        for col in &self.columns {
+            cols_len += col.part_len()?;
+        }
+        let mut constraints_len: usize = 0;
+        for constraint in &self.constraints {
+            constraints_len += 1 + constraint.write_len(&self.columns)?; // ','
+        }
+        Ok(
+            13  // "CREATE TABLE "
+            + self.temp as usize * 10 // "TEMPORARY "
+            + self.if_exists as usize * 14 // "IF NOT EXISTS "
+            + self.quote.ident_len(self.name.as_str())
+            + 2 // " ("
+            + cols_len
+            + self.columns.len() - 1 // commas for cols, -1 b/c the last doesn't have a comma
+            + constraints_len
+            + 1 // ')'
+            + self.without_rowid as usize * 14 // " WITHOUT ROWID"
+            + (self.without_rowid && self.strict) as usize // ','
+            + self.strict as usize * 7 // " STRICT"
+        )
+    }
+
+    fn part_str(&self, sql: &mut String) -> Result<()> {
+        self.write_str(sql, None, None)
+    }
+
+    #[cfg(test)]
+    fn possibilities(illegal: bool) -> Vec<Box<Self>> {
+        let mut ret: Vec<Box<Self>> = Vec::new();
+        for name in [if illegal { "".to_string() } else { "test".to_string() } , "test".to_string()] {
+            for wo_rowid in [true, false] {
+                for col_num in [if illegal { 0 } else { 3 }, 1, 2] {
+                    let mut cols: Vec<Column> = Vec::new();
+                    for n in 0..col_num {
+                        cols.push(Column::new_default(format!("test{}", n)))
+                        // todo not all column possibilities
+                    }
+                    if !illegal && wo_rowid {
+                        cols[0].pk = Some(Default::default());
+                    }
+
+                    for strict in [true, false] {
+                        ret.push(Box::new(Self::new(name.clone(), cols.clone(), wo_rowid, strict)));
+                    }
+                }
+            }
+        }
+        ret
+    }
+}
+
+impl SQLStatement for Table {
+    fn build_pretty(&mut self, transaction: bool, if_exists: bool, indent: usize) -> Result<String> {
+        let mut ret: String = String::new();
+        self.write_stmt(&mut ret, transaction, if_exists, Some(indent), None)?;
+        Ok(ret)
+    }
+
+    fn check_version_compatibility(&self, version: SQLiteVersion) -> Result<(), Vec<CompatibilityWarning>> {
+        check_requirements(self.version_requirements(), version)
+    }
+}
+
+impl PartialEq<Table> for Table {
+    fn eq(&self, other: &Table) -> bool {
+        if self.name != other.name {
+            return false;
+        }
+        if self.without_rowid != other.without_rowid {
+            return false;
+        }
+        if self.strict != other.strict {
+            return false;
+        }
+        if self.temp != other.temp {
+            return false;
+        }
+        if self.quote != other.quote {
+            return false;
+        }
+        if self.comment != other.comment {
+            return false;
+        }
+        if self.columns.len() != other.columns.len() {
+            return false;
+        }
+        for columns in self.columns.iter().zip(other.columns.iter()) {
+            if columns.0 != columns.1 {
+                return false;
+            }
+        }
+        if self.constraints != other.constraints {
+            return false;
+        }
+        true
+    }
+}
+
+impl Hash for Table {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // without `if_exists`, consistent with PartialEq
+        self.name.hash(state);
+        self.without_rowid.hash(state);
+        self.strict.hash(state);
+        self.temp.hash(state);
+        self.quote.hash(state);
+        self.comment.hash(state);
+        self.columns.hash(state);
+        self.constraints.hash(state);
+    }
+}
+
+// endregion
+
+// region Index
+
+/// A single Column of an [Index], with its sort [Order] and an optional [Collation].
+/// It is a Error for the `name` to be empty ([Error::EmptyColumnName]).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IndexColumn {
+    name: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    quote: Quote,
+    #[cfg_attr(feature = "serde", serde(default))]
+    order: Order,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    collation: Option<Collation>,
+}
+
+impl IndexColumn {
+    fn check(&self) -> Result<()> {
+        if self.name.is_empty() {
+            return Err(Error::EmptyColumnName);
+        }
+        Ok(())
+    }
+
+    pub fn new(name: String, order: Order) -> Self {
+        Self {
+            name,
+            quote: Quote::None,
+            order,
+            collation: None,
+        }
+    }
+
+    pub fn new_collated(name: String, order: Order, collation: Collation) -> Self {
+        Self {
+            name,
+            quote: Quote::None,
+            order,
+            collation: Some(collation),
+        }
+    }
+
+    /// Sets how the name is quoted, should be the same as the [Quote] of the indexed [Column]
+    pub fn set_quote(mut self, quote: Quote) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn quote(&self) -> Quote {
+        self.quote
+    }
+
+    pub fn order(&self) -> Order {
+        self.order
+    }
+
+    pub fn collation(&self) -> Option<&Collation> {
+        self.collation.as_ref()
+    }
+}
+
+impl SQLPart for IndexColumn {
+    fn part_len(&self) -> Result<usize> {
+        self.check()?;
+        let collation_len: usize = if let Some(collation) = self.collation.as_ref() {
+            collation.part_len()? + 1
+        } else {
+            0
+        };
+        Ok(self.quote.ident_len(self.name.as_str()) + collation_len + 1 + self.order.part_len()?)
+    }
+
+    fn part_str(&self, sql: &mut String) -> Result<()> {
+        self.check()?;
+        self.quote.push_ident(sql, self.name.as_str());
+        if let Some(collation) = self.collation.as_ref() {
+            sql.push(' ');
+            collation.part_str(sql)?;
+        }
+        sql.push(' ');
+        self.order.part_str(sql)
+    }
+
+    #[cfg(test)]
+    fn possibilities(illegal: bool) -> Vec<Box<Self>> {
+        let mut ret: Vec<Box<Self>> = Vec::new();
+        for name in [if illegal { "".to_string() } else { "test".to_string() }, "test".to_string()] {
+            for order in Order::possibilities(false) {
+                ret.push(Box::new(Self::new(name.clone(), *order)));
+                ret.push(Box::new(Self::new(name.clone(), *order).set_quote(Quote::DoubleQuote)));
+                for collation in Collation::possibilities(false) {
+                    ret.push(Box::new(Self::new_collated(name.clone(), *order, *collation.clone())));
+                }
+            }
+        }
+        ret
+    }
+}
+
+/// Represents an Index on one or more Columns of a [Table], see [here](https://www.sqlite.org/lang_createindex.html).
+/// Can be converted into an SQL Statement via the [SQLStatement] Methods.
+/// It is a Error for the `name` ([Error::EmptyIndexName]) or `table` ([Error::EmptyIndexTable]) to be empty or the Index to have no Columns ([Error::EmptyIndexColumns]).
+/// The `quote` is used for both the `name` and the `table`, the Columns have their own (see [IndexColumn::set_quote]).
+#[derive(Debug, Clone, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Index {
+    name: String,
+    table: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    quote: Quote,
+    #[cfg_attr(feature = "serde", serde(rename = "column"))]
+    columns: Vec<IndexColumn>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    unique: bool,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    #[cfg_attr(feature = "serde", serde(rename = "where"))]
+    where_expr: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) if_exists: bool,
+}
+
+impl Index {
+    fn check(&self) -> Result<()> {
+        if self.name.is_empty() {
+            return Err(Error::EmptyIndexName);
+        }
+
+        if self.table.is_empty() {
+            return Err(Error::EmptyIndexTable);
+        }
+
+        if self.columns.is_empty() {
+            return Err(Error::EmptyIndexColumns);
+        }
+
+        if self.where_expr.as_ref().is_some_and(|where_expr| where_expr.is_empty()) {
+            return Err(Error::EmptyIndexWhereClause);
+        }
+        Ok(())
+    }
+
+    pub fn new(name: String, table: String, columns: Vec<IndexColumn>, unique: bool, where_expr: Option<String>) -> Self {
+        Self {
+            name,
+            table,
+            quote: Quote::None,
+            columns,
+            unique,
+            where_expr,
+            if_exists: false,
+        }
+    }
+
+    pub fn new_default(name: String, table: String) -> Self {
+        Self {
+            name,
+            table,
+            quote: Quote::None,
+            columns: Vec::new(),
+            unique: false,
+            where_expr: None,
+            if_exists: false,
+        }
+    }
+
+    pub fn set_name(mut self, name: String) -> Self {
+        self.name = name;
+        self
+    }
+
+    pub fn set_table(mut self, table: String) -> Self {
+        self.table = table;
+        self
+    }
+
+    /// Sets how the `name` and `table` are quoted, see [Schema::auto_quote]
+    pub fn set_quote(mut self, quote: Quote) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    pub fn add_column(mut self, name: String, order: Order) -> Self {
+        self.columns.push(IndexColumn::new(name, order));
+        self
+    }
+
+    pub fn add_collated_column(mut self, name: String, order: Order, collation: Collation) -> Self {
+        self.columns.push(IndexColumn::new_collated(name, order, collation));
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn table(&self) -> &str {
+        self.table.as_str()
+    }
+
+    pub fn quote(&self) -> Quote {
+        self.quote
+    }
+
+    pub fn columns(&self) -> &[IndexColumn] {
+        self.columns.as_slice()
+    }
+
+    pub fn is_unique(&self) -> bool {
+        self.unique
+    }
+
+    pub fn where_expr(&self) -> Option<&str> {
+        self.where_expr.as_deref()
+    }
+
+    pub fn set_unique(mut self, unique: bool) -> Self {
+        self.unique = unique;
+        self
+    }
+
+    pub fn set_where_expr(mut self, where_expr: Option<String>) -> Self {
+        self.where_expr = where_expr;
+        self
+    }
+
+    /// Makes this a partial Index on the rows matching `expr`, same as `set_where_expr(Some(expr))`
+    pub fn set_where(self, expr: String) -> Self {
+        self.set_where_expr(Some(expr))
+    }
+
+    /// Writes the `CREATE INDEX` Statement, the name is qualified by `schema` (see [Schema::set_name]).
+    fn write_str(&self, sql: &mut String, schema: Option<&str>) -> Result<()> {
+        self.check()?;
+
+        sql.push_str("CREATE ");
+        if self.unique {
+            sql.push_str("UNIQUE ");
+        }
+        sql.push_str("INDEX ");
+        if self.if_exists {
+            sql.push_str("IF NOT EXISTS ");
+        }
+        // the schema qualifies the Index, its Table is always in the same schema
+        push_schema_prefix(sql, schema);
+        self.quote.push_ident(sql, self.name.as_str());
+        sql.push_str(" ON ");
+        self.quote.push_ident(sql, self.table.as_str());
+        sql.push_str(" (");
+
+        let mut needs_comma = false;
+        for col in &self.columns {
+            if needs_comma {
+                sql.push_str(", ");
+            }
+            col.part_str(sql)?;
+            needs_comma = true;
+        }
+        sql.push(')');
+
+        if let Some(where_expr) = self.where_expr.as_ref() {
+            sql.push_str(" WHERE ");
+            sql.push_str(where_expr.as_str());
+        }
+        Ok(())
+    }
+
+    /// Writes the whole Statement like [SQLStatement::build_into], see [Index::write_str] for `schema`.
+    fn write_stmt(&mut self, sql: &mut String, transaction: bool, if_exists: bool, schema: Option<&str>) -> Result<()> {
+        self.if_exists = if_exists;
+        if transaction {
+            sql.push_str("BEGIN;\n");
+        }
+        self.write_str(sql, schema)?;
+        sql.push(';');
+        if transaction {
+            sql.push_str("\nEND;");
+        }
+        Ok(())
+    }
+
+    fn version_requirements(&self) -> Vec<CompatibilityWarning> {
+        if self.where_expr.is_some() {
+            vec![CompatibilityWarning::PARTIAL_INDEX]
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Compares the [IndexColumn]s (names and [Order]s) of this Index to the ones in the DB, used by [Schema::check_db].
+    /// Deviations are appended to `ret`, prefixed with the Index number `num`.
+    #[cfg(feature = "rusqlite")]
+    fn check_db_columns(&self, num: usize, conn: &Connection, ret: &mut String) -> Result<(), CheckError> {
+        let mut stmt: Statement = conn.prepare(r#"SELECT name, "desc" FROM pragma_index_xinfo(?1) WHERE key == 1 ORDER BY seqno;"#)?;
+        let mut rows: Rows = stmt.query([self.name.as_str()])?;
+
+        for (col_num, col) in self.columns.iter().enumerate() {
+            let row: &Row = match rows.next()? {
+                None => {
+                    write!(ret, "Index {} Column {}: expected column '{}', got nothing; ", num, col_num, col.name)?;
+                    break
+                }
+                Some(row) => { row }
+            };
+            // expressions have no name
+            let name: String = row.get::<&str, Option<String>>("name")?.unwrap_or_default();
+            if col.name != name {
+                write!(ret, "Index {} Column {}: expected name '{}', got '{}'; ", num, col_num, col.name, name)?;
+            }
+            let order: Order = if row.get::<&str, bool>("desc")? { Order::Descending } else { Order::Ascending };
+            if col.order != order {
+                write!(ret, "Index {} Column {}: expected order {}, got {}; ", num, col_num, col.order, order)?;
+            }
+        }
+
+        let mut col_num: usize = self.columns.len();
+        while let Some(row) = rows.next()? {
+            write!(ret, "Index {} Column {}: expected nothing, got column '{}'; ", num, col_num, row.get::<&str, Option<String>>("name")?.unwrap_or_default())?;
+            col_num += 1;
+        }
+        Ok(())
+    }
+}
+
+impl SQLPart for Index {
+    fn part_len(&self) -> Result<usize> {
+        self.check()?;
+        let mut cols_len: usize = 0;
+        for col in &self.columns {
+            cols_len += col.part_len()?;
+        }
+
+        let where_len: usize = if let Some(where_expr) = self.where_expr.as_ref() {
+            7 + where_expr.len() // " WHERE "
+        } else {
+            0
+        };
+
+        Ok(
+            7 // "CREATE "
+            + self.unique as usize * 7 // "UNIQUE "
+            + 6 // "INDEX "
+            + self.if_exists as usize * 14 // "IF NOT EXISTS "
+            + self.quote.ident_len(self.name.as_str())
+            + 4 // " ON "
+            + self.quote.ident_len(self.table.as_str())
+            + 2 // " ("
+            + cols_len
+            + (self.columns.len() - 1) * 2 // ", " for cols, -1 b/c the last doesn't have a comma
+            + 1 // ')'
+            + where_len
+        )
+    }
+
+    fn part_str(&self, sql: &mut String) -> Result<()> {
+        self.write_str(sql, None)
+    }
+
+    #[cfg(test)]
+    fn possibilities(illegal: bool) -> Vec<Box<Self>> {
+        let mut ret: Vec<Box<Self>> = Vec::new();
+        for name in [if illegal { "".to_string() } else { "test_idx".to_string() } , "test_idx".to_string()] {
+            for table in [if illegal { "".to_string() } else { "test".to_string() } , "test".to_string()] {
+                for col_num in [if illegal { 0 } else { 3 }, 1, 2] {
+                    for unique in [true, false] {
+                        for where_expr in [None, Some("test0 IS NOT NULL".to_string())] {
+                            let mut idx = Self::new(name.clone(), table.clone(), Vec::new(), unique, where_expr);
+                            for n in 0..col_num {
+                                idx = idx.add_column(format!("test{}", n), if n % 2 == 0 { Order::Ascending } else { Order::Descending });
+                            }
+                            ret.push(Box::new(idx));
+                        }
+                    }
+                }
+            }
+        }
+        ret.push(Box::new(Self::new_default("order idx".to_string(), "test".to_string()).set_quote(Quote::DoubleQuote)
+            .add_column("test0".to_string(), Order::Ascending)));
+        ret
+    }
+}
+
+impl SQLStatement for Index {
+    fn check_version_compatibility(&self, version: SQLiteVersion) -> Result<(), Vec<CompatibilityWarning>> {
+        check_requirements(self.version_requirements(), version)
+    }
+}
+
+impl PartialEq<Index> for Index {
+    fn eq(&self, other: &Index) -> bool {
+        self.name == other.name
+            && self.table == other.table
+            && self.quote == other.quote
+            && self.columns == other.columns
+            && self.unique == other.unique
+            && self.where_expr == other.where_expr
+    }
+}
+
+impl Hash for Index {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // without `if_exists`, consistent with PartialEq
+        self.name.hash(state);
+        self.table.hash(state);
+        self.quote.hash(state);
+        self.columns.hash(state);
+        self.unique.hash(state);
+        self.where_expr.hash(state);
+    }
+}
+
+// endregion
+
+// region Drop
+
+/// Represents a `DROP TABLE ...` Statement, see [here](https://www.sqlite.org/lang_droptable.html).
+/// Can be converted into an SQL Statement via the [SQLStatement] Methods, where `if_exists` adds a `...IF EXISTS...` guard.
+/// The name is quoted by the [Quote] of the Table and optionally qualified by a `schema` (see [Schema::set_name]).
+/// It is a Error for the `name` to be empty ([Error::EmptyTableName]).
+#[derive(Debug, Clone, Default, Eq)]
+pub struct DropTable {
+    name: String,
+    quote: Quote,
+    schema: Option<String>,
+    pub(crate) if_exists: bool,
+}
+
+impl DropTable {
+    fn check(&self) -> Result<()> {
+        if self.name.is_empty() {
+            return Err(Error::EmptyTableName);
+        }
+        Ok(())
+    }
+
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            quote: Quote::None,
+            schema: None,
+            if_exists: false,
+        }
+    }
+
+    pub fn set_name(mut self, name: String) -> Self {
+        self.name = name;
+        self
+    }
+
+    /// Sets how the name is quoted, should be the same as the [Quote] of the dropped [Table]
+    pub fn set_quote(mut self, quote: Quote) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    /// Sets the name of the database the Table lives in, see [Schema::set_name]
+    pub fn set_schema(mut self, schema: Option<String>) -> Self {
+        self.schema = schema;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn quote(&self) -> Quote {
+        self.quote
+    }
+
+    pub fn schema(&self) -> Option<&str> {
+        self.schema.as_deref()
+    }
+}
+
+impl SQLPart for DropTable {
+    fn part_len(&self) -> Result<usize> {
+        self.check()?;
+        Ok(
+            11 // "DROP TABLE "
+            + self.if_exists as usize * 10 // "IF EXISTS "
+            + schema_prefix_len(self.schema.as_deref())
+            + self.quote.ident_len(self.name.as_str())
+        )
+    }
+
+    fn part_str(&self, sql: &mut String) -> Result<()> {
+        self.check()?;
+        sql.push_str("DROP TABLE ");
+        if self.if_exists {
+            sql.push_str("IF EXISTS ");
+        }
+        push_schema_prefix(sql, self.schema.as_deref());
+        self.quote.push_ident(sql, self.name.as_str());
+        Ok(())
+    }
+
+    #[cfg(test)]
+    fn possibilities(illegal: bool) -> Vec<Box<Self>> {
+        let mut ret: Vec<Box<Self>> = vec![Box::new(Self::new("test".to_string()))];
+        if illegal {
+            ret.push(Box::new(Self::new("".to_string())));
+        }
+        ret
+    }
+}
+
+impl SQLStatement for DropTable {}
+
+impl PartialEq<DropTable> for DropTable {
+    fn eq(&self, other: &DropTable) -> bool {
+        self.name == other.name && self.quote == other.quote && self.schema == other.schema
+    }
+}
+
+impl Hash for DropTable {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // without `if_exists`, consistent with PartialEq
+        self.name.hash(state);
+        self.quote.hash(state);
+        self.schema.hash(state);
+    }
+}
+
+/// Represents a `DROP INDEX ...` Statement, see [here](https://www.sqlite.org/lang_dropindex.html).
+/// Can be converted into an SQL Statement via the [SQLStatement] Methods, where `if_exists` adds a `...IF EXISTS...` guard.
+/// The name is quoted by the [Quote] of the Index and optionally qualified by a `schema` (see [Schema::set_name]).
+/// It is a Error for the `name` to be empty ([Error::EmptyIndexName]).
+#[derive(Debug, Clone, Default, Eq)]
+pub struct DropIndex {
+    name: String,
+    quote: Quote,
+    schema: Option<String>,
+    pub(crate) if_exists: bool,
+}
+
+impl DropIndex {
+    fn check(&self) -> Result<()> {
+        if self.name.is_empty() {
+            return Err(Error::EmptyIndexName);
+        }
+        Ok(())
+    }
+
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            quote: Quote::None,
+            schema: None,
+            if_exists: false,
+        }
+    }
+
+    pub fn set_name(mut self, name: String) -> Self {
+        self.name = name;
+        self
+    }
+
+    /// Sets how the name is quoted, should be the same as the [Quote] of the dropped [Index]
+    pub fn set_quote(mut self, quote: Quote) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    /// Sets the name of the database the Index lives in, see [Schema::set_name]
+    pub fn set_schema(mut self, schema: Option<String>) -> Self {
+        self.schema = schema;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn quote(&self) -> Quote {
+        self.quote
+    }
+
+    pub fn schema(&self) -> Option<&str> {
+        self.schema.as_deref()
+    }
+}
+
+impl SQLPart for DropIndex {
+    fn part_len(&self) -> Result<usize> {
+        self.check()?;
+        Ok(
+            11 // "DROP INDEX "
+            + self.if_exists as usize * 10 // "IF EXISTS "
+            + schema_prefix_len(self.schema.as_deref())
+            + self.quote.ident_len(self.name.as_str())
+        )
+    }
+
+    fn part_str(&self, sql: &mut String) -> Result<()> {
+        self.check()?;
+        sql.push_str("DROP INDEX ");
+        if self.if_exists {
+            sql.push_str("IF EXISTS ");
+        }
+        push_schema_prefix(sql, self.schema.as_deref());
+        self.quote.push_ident(sql, self.name.as_str());
+        Ok(())
+    }
+
+    #[cfg(test)]
+    fn possibilities(illegal: bool) -> Vec<Box<Self>> {
+        let mut ret: Vec<Box<Self>> = vec![Box::new(Self::new("test".to_string()))];
+        if illegal {
+            ret.push(Box::new(Self::new("".to_string())));
+        }
+        ret
+    }
+}
+
+impl SQLStatement for DropIndex {}
+
+impl PartialEq<DropIndex> for DropIndex {
+    fn eq(&self, other: &DropIndex) -> bool {
+        self.name == other.name && self.quote == other.quote && self.schema == other.schema
+    }
+}
+
+impl Hash for DropIndex {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // without `if_exists`, consistent with PartialEq
+        self.name.hash(state);
+        self.quote.hash(state);
+        self.schema.hash(state);
+    }
+}
+
+// endregion
+
+// region AlterTableRename
+
+/// Represents a `ALTER TABLE ... RENAME TO ...` Statement, see [here](https://www.sqlite.org/lang_altertable.html#alter_table_rename).
+/// Can be converted into an SQL Statement via the [SQLStatement] Methods.
+/// Both names are quoted by the same [Quote], usually the one of the renamed [Table].
+/// It is a Error for the `old_name` or `new_name` to be empty ([Error::EmptyTableName]).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct AlterTableRename {
+    old_name: String,
+    new_name: String,
+    quote: Quote,
+}
+
+impl AlterTableRename {
+    fn check(&self) -> Result<()> {
+        if self.old_name.is_empty() || self.new_name.is_empty() {
+            return Err(Error::EmptyTableName);
+        }
+        Ok(())
+    }
+
+    pub fn new(old_name: String, new_name: String) -> Self {
+        Self {
+            old_name,
+            new_name,
+            quote: Quote::None,
+        }
+    }
+
+    /// Sets how both names are quoted
+    pub fn set_quote(mut self, quote: Quote) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    pub fn old_name(&self) -> &str {
+        self.old_name.as_str()
+    }
+
+    pub fn new_name(&self) -> &str {
+        self.new_name.as_str()
+    }
+
+    pub fn quote(&self) -> Quote {
+        self.quote
+    }
+}
+
+impl SQLPart for AlterTableRename {
+    fn part_len(&self) -> Result<usize> {
+        self.check()?;
+        Ok(
+            12 // "ALTER TABLE "
+            + self.quote.ident_len(self.old_name.as_str())
+            + 11 // " RENAME TO "
+            + self.quote.ident_len(self.new_name.as_str())
+        )
+    }
+
+    fn part_str(&self, sql: &mut String) -> Result<()> {
+        self.check()?;
+        sql.push_str("ALTER TABLE ");
+        self.quote.push_ident(sql, self.old_name.as_str());
+        sql.push_str(" RENAME TO ");
+        self.quote.push_ident(sql, self.new_name.as_str());
+        Ok(())
+    }
+
+    #[cfg(test)]
+    fn possibilities(illegal: bool) -> Vec<Box<Self>> {
+        let mut ret: Vec<Box<Self>> = vec![
+            Box::new(Self::new("test".to_string(), "other".to_string())),
+            Box::new(Self::new("order".to_string(), "my table".to_string()).set_quote(Quote::DoubleQuote)),
+        ];
+        if illegal {
+            ret.push(Box::new(Self::new("".to_string(), "other".to_string())));
+            ret.push(Box::new(Self::new("test".to_string(), "".to_string())));
+        }
+        ret
+    }
+}
+
+impl SQLStatement for AlterTableRename {
+    fn check_version_compatibility(&self, version: SQLiteVersion) -> Result<(), Vec<CompatibilityWarning>> {
+        check_requirements(vec![CompatibilityWarning::RENAME_TABLE], version)
+    }
+}
+
+// endregion
+
+// region AlterTableRenameColumn
+
+/// Represents a `ALTER TABLE ... RENAME COLUMN ... TO ...` Statement, see [here](https://www.sqlite.org/lang_altertable.html#alter_table_rename_column).
+/// Can be converted into an SQL Statement via the [SQLStatement] Methods, requires SQLite 3.25.0.
+/// It is a Error for the `table` ([Error::EmptyTableName]), `old_column` or `new_column` ([Error::EmptyColumnName]) to be empty.
+/// The `table` is quoted by `quote`, both Columns by `column_quote`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct AlterTableRenameColumn {
+    table: String,
+    quote: Quote,
+    old_column: String,
+    new_column: String,
+    column_quote: Quote,
+}
+
+impl AlterTableRenameColumn {
+    fn check(&self) -> Result<()> {
+        if self.table.is_empty() {
+            return Err(Error::EmptyTableName);
+        }
+        if self.old_column.is_empty() || self.new_column.is_empty() {
+            return Err(Error::EmptyColumnName);
+        }
+        Ok(())
+    }
+
+    pub fn new(table: String, old_column: String, new_column: String) -> Self {
+        Self {
+            table,
+            quote: Quote::None,
+            old_column,
+            new_column,
+            column_quote: Quote::None,
+        }
+    }
+
+    /// Sets how the `table` Name is quoted, should be the same as the [Quote] of the [Table]
+    pub fn set_quote(mut self, quote: Quote) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    /// Sets how the `old_column` and `new_column` Names are quoted
+    pub fn set_column_quote(mut self, quote: Quote) -> Self {
+        self.column_quote = quote;
+        self
+    }
+
+    pub fn table(&self) -> &str {
+        self.table.as_str()
+    }
+
+    pub fn quote(&self) -> Quote {
+        self.quote
+    }
+
+    pub fn column_quote(&self) -> Quote {
+        self.column_quote
+    }
+
+    pub fn old_column(&self) -> &str {
+        self.old_column.as_str()
+    }
+
+    pub fn new_column(&self) -> &str {
+        self.new_column.as_str()
+    }
+}
+
+impl SQLPart for AlterTableRenameColumn {
+    fn part_len(&self) -> Result<usize> {
+        self.check()?;
+        Ok(
+            12 // "ALTER TABLE "
+            + self.quote.ident_len(self.table.as_str())
+            + 15 // " RENAME COLUMN "
+            + self.column_quote.ident_len(self.old_column.as_str())
+            + 4 // " TO "
+            + self.column_quote.ident_len(self.new_column.as_str())
+        )
+    }
+
+    fn part_str(&self, sql: &mut String) -> Result<()> {
+        self.check()?;
+        sql.push_str("ALTER TABLE ");
+        self.quote.push_ident(sql, self.table.as_str());
+        sql.push_str(" RENAME COLUMN ");
+        self.column_quote.push_ident(sql, self.old_column.as_str());
+        sql.push_str(" TO ");
+        self.column_quote.push_ident(sql, self.new_column.as_str());
+        Ok(())
+    }
+
+    #[cfg(test)]
+    fn possibilities(illegal: bool) -> Vec<Box<Self>> {
+        let mut ret: Vec<Box<Self>> = vec![
+            Box::new(Self::new("test".to_string(), "old".to_string(), "new".to_string())),
+            Box::new(Self::new("order".to_string(), "old col".to_string(), "new col".to_string()).set_quote(Quote::DoubleQuote).set_column_quote(Quote::DoubleQuote)),
+        ];
+        if illegal {
+            ret.push(Box::new(Self::new("".to_string(), "old".to_string(), "new".to_string())));
+            ret.push(Box::new(Self::new("test".to_string(), "".to_string(), "new".to_string())));
+            ret.push(Box::new(Self::new("test".to_string(), "old".to_string(), "".to_string())));
+        }
+        ret
+    }
+}
+
+impl SQLStatement for AlterTableRenameColumn {
+    fn check_version_compatibility(&self, version: SQLiteVersion) -> Result<(), Vec<CompatibilityWarning>> {
+        check_requirements(vec![CompatibilityWarning::RENAME_COLUMN], version)
+    }
+}
+
+// endregion
+
+// region AlterTableAddColumn
+
+/// Represents a `ALTER TABLE ... ADD COLUMN ...` Statement, see [here](https://www.sqlite.org/lang_altertable.html#alter_table_add_column).
+/// Can be converted into an SQL Statement via the [SQLStatement] Methods.
+/// The `table` Name is quoted by the given [Quote] (usually the one of the [Table]), the [Column] by its own.
+/// It is a Error for the `table` Name to be empty ([Error::EmptyTableName]) or the [Column] to have a [PrimaryKey] ([Error::AddColumnPrimaryKey]).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlterTableAddColumn {
+    table: String,
+    quote: Quote,
+    schema: Option<String>,
+    column: Column,
+}
+
+impl AlterTableAddColumn {
+    fn check(&self) -> Result<()> {
+        if self.table.is_empty() {
+            return Err(Error::EmptyTableName);
+        }
+        if self.column.pk.is_some() {
+            return Err(Error::AddColumnPrimaryKey);
+        }
+        Ok(())
+    }
+
+    pub fn new(table: String, column: Column) -> Self {
+        Self {
+            table,
+            quote: Quote::None,
+            schema: None,
+            column,
+        }
+    }
+
+    /// Sets how the `table` Name is quoted, should be the same as the [Quote] of the [Table]
+    pub fn set_quote(mut self, quote: Quote) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    /// Sets the name of the database the Table lives in, see [Schema::set_name]
+    pub fn set_schema(mut self, schema: Option<String>) -> Self {
+        self.schema = schema;
+        self
+    }
+
+    pub fn table(&self) -> &str {
+        self.table.as_str()
+    }
+
+    pub fn quote(&self) -> Quote {
+        self.quote
+    }
+
+    pub fn schema(&self) -> Option<&str> {
+        self.schema.as_deref()
+    }
+
+    pub fn column(&self) -> &Column {
+        &self.column
+    }
+}
+
+impl SQLPart for AlterTableAddColumn {
+    fn part_len(&self) -> Result<usize> {
+        self.check()?;
+        Ok(
+            12 // "ALTER TABLE "
+            + schema_prefix_len(self.schema.as_deref())
+            + self.quote.ident_len(self.table.as_str())
+            + 12 // " ADD COLUMN "
+            + self.column.part_len()?
+        )
+    }
+
+    fn part_str(&self, sql: &mut String) -> Result<()> {
+        self.check()?;
+        sql.push_str("ALTER TABLE ");
+        push_schema_prefix(sql, self.schema.as_deref());
+        self.quote.push_ident(sql, self.table.as_str());
+        sql.push_str(" ADD COLUMN ");
+        self.column.part_str(sql)
+    }
+
+    #[cfg(test)]
+    fn possibilities(illegal: bool) -> Vec<Box<Self>> {
+        // not all Column possibilities, those are tested on their own
+        let mut ret: Vec<Box<Self>> = vec![
+            Box::new(Self::new("test".to_string(), Column::new_default("test".to_string()))),
+            Box::new(Self::new("test".to_string(), Column::new_typed(SQLiteType::Text, "test".to_string()).set_unique(Some(Unique::default())))),
+            Box::new(Self::new("order".to_string(), Column::new_typed(SQLiteType::Text, "my col".to_string()).set_quote(Quote::DoubleQuote)).set_quote(Quote::DoubleQuote)),
+            Box::new(Self::new("test".to_string(), Column::new_default("test".to_string())).set_schema(Some("main".to_string()))),
+        ];
+        if illegal {
+            ret.push(Box::new(Self::new("".to_string(), Column::new_default("test".to_string()))));
+            ret.push(Box::new(Self::new("test".to_string(), Column::new_default("".to_string()))));
+            ret.push(Box::new(Self::new("test".to_string(), Column::new_typed(SQLiteType::Integer, "test".to_string()).set_pk(Some(PrimaryKey::default())))));
+        }
+        ret
+    }
+}
+
+impl SQLStatement for AlterTableAddColumn {
+    fn check_version_compatibility(&self, version: SQLiteVersion) -> Result<(), Vec<CompatibilityWarning>> {
+        let mut requirements: Vec<CompatibilityWarning> = vec![CompatibilityWarning::ADD_COLUMN];
+        if self.column.fk.is_some() {
+            requirements.push(CompatibilityWarning::FOREIGN_KEY);
+        }
+        check_requirements(requirements, version)
+    }
+}
+
+// endregion
+
+// region Trigger
+
+/// The Statement that fires a [Trigger]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
+#[allow(missing_docs)]
+pub enum TriggerEvent {
+    Insert,
+    Update,
+    Delete,
+}
+
+impl Default for TriggerEvent {
+    fn default() -> Self {
+        Self::Insert
+    }
+}
+
+impl SQLPart for TriggerEvent {
+    fn part_len(&self) -> Result<usize> {
+        Ok(6) // all three are six characters long
+    }
+
+    fn part_str(&self, sql: &mut String) -> Result<()> {
+        match self {
+            TriggerEvent::Insert => { sql.push_str("INSERT") }
+            TriggerEvent::Update => { sql.push_str("UPDATE") }
+            TriggerEvent::Delete => { sql.push_str("DELETE") }
+        }
+        Ok(())
+    }
+
+    #[cfg(test)]
+    fn possibilities(_: bool) -> Vec<Box<Self>> {
+        vec![Box::new(Self::Insert), Box::new(Self::Update), Box::new(Self::Delete)]
+    }
+}
+
+impl fmt::Display for TriggerEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_part(self, f)
+    }
+}
+
+impl FromStr for TriggerEvent {
+    type Err = Error;
+
+    /// Case-insensitive, surrounding whitespace is ignored
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match normalize_keyword(s).as_str() {
+            "insert" => { Self::Insert }
+            "update" => { Self::Update }
+            "delete" => { Self::Delete }
+            _ => { return Err(Error::UnknownKeyword(s.to_string())) }
+        })
+    }
+}
+
+/// Represents a `CREATE TRIGGER ... INSTEAD OF ...` Statement, see [here](https://www.sqlite.org/lang_createtrigger.html#instead_of_triggers),
+/// which makes a View writable by running `body` instead of the `event`.
+/// The `body` is one or more Statements, without the `;` after the last one.
+/// Can be converted into an SQL Statement via the [SQLStatement] Methods, where `if_exists` adds a `...IF NOT EXISTS...` guard.
+/// The `name` and `table_name` are quoted by the same [Quote], the `body` is emitted as is.
+/// It is a Error for the `name` ([Error::EmptyTriggerName]), `table_name` ([Error::EmptyTableName]) or `body` ([Error::EmptyTriggerBody]) to be empty.
+#[derive(Debug, Clone, Default, Eq)]
+pub struct Trigger {
+    name: String,
+    event: TriggerEvent,
+    table_name: String,
+    quote: Quote,
+    body: String,
+    pub(crate) if_exists: bool,
+}
+
+impl Trigger {
+    fn check(&self) -> Result<()> {
+        if self.name.is_empty() {
+            return Err(Error::EmptyTriggerName);
+        }
+        if self.table_name.is_empty() {
+            return Err(Error::EmptyTableName);
+        }
+        if self.body.is_empty() {
+            return Err(Error::EmptyTriggerBody);
+        }
+        Ok(())
+    }
+
+    pub fn new(name: String, event: TriggerEvent, table_name: String, body: String) -> Self {
+        Self {
+            name,
+            event,
+            table_name,
+            quote: Quote::None,
+            body,
+            if_exists: false,
+        }
+    }
+
+    /// Sets how the `name` and `table_name` are quoted
+    pub fn set_quote(mut self, quote: Quote) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    pub fn quote(&self) -> Quote {
+        self.quote
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn event(&self) -> TriggerEvent {
+        self.event
+    }
+
+    pub fn table_name(&self) -> &str {
+        self.table_name.as_str()
+    }
+
+    pub fn body(&self) -> &str {
+        self.body.as_str()
+    }
+}
+
+impl SQLPart for Trigger {
+    fn part_len(&self) -> Result<usize> {
+        self.check()?;
+        Ok(
+            15 // "CREATE TRIGGER "
+            + self.if_exists as usize * 14 // "IF NOT EXISTS "
+            + self.quote.ident_len(self.name.as_str())
+            + 12 // " INSTEAD OF "
+            + self.event.part_len()?
+            + 4 // " ON "
+            + self.quote.ident_len(self.table_name.as_str())
+            + 7 // " BEGIN "
+            + self.body.len()
+            + 5 // "; END"
+        )
+    }
+
+    fn part_str(&self, sql: &mut String) -> Result<()> {
+        self.check()?;
+        sql.push_str("CREATE TRIGGER ");
+        if self.if_exists {
+            sql.push_str("IF NOT EXISTS ");
+        }
+        self.quote.push_ident(sql, self.name.as_str());
+        sql.push_str(" INSTEAD OF ");
+        self.event.part_str(sql)?;
+        sql.push_str(" ON ");
+        self.quote.push_ident(sql, self.table_name.as_str());
+        sql.push_str(" BEGIN ");
+        sql.push_str(self.body.as_str());
+        sql.push_str("; END");
+        Ok(())
+    }
+
+    #[cfg(test)]
+    fn possibilities(illegal: bool) -> Vec<Box<Self>> {
+        let mut ret: Vec<Box<Self>> = Vec::new();
+        for event in TriggerEvent::possibilities(false) {
+            ret.push(Box::new(Self::new("test_trigger".to_string(), *event, "test".to_string(), "SELECT 1".to_string())));
+        }
+        ret.push(Box::new(Self::new("my trigger".to_string(), TriggerEvent::Insert, "order".to_string(), "SELECT 1".to_string()).set_quote(Quote::DoubleQuote)));
+        if illegal {
+            ret.push(Box::new(Self::new("".to_string(), TriggerEvent::Insert, "test".to_string(), "SELECT 1".to_string())));
+            ret.push(Box::new(Self::new("test_trigger".to_string(), TriggerEvent::Insert, "".to_string(), "SELECT 1".to_string())));
+            ret.push(Box::new(Self::new("test_trigger".to_string(), TriggerEvent::Insert, "test".to_string(), "".to_string())));
+        }
+        ret
+    }
+}
+
+impl SQLStatement for Trigger {}
+
+impl PartialEq<Trigger> for Trigger {
+    fn eq(&self, other: &Trigger) -> bool {
+        self.name == other.name
+            && self.event == other.event
+            && self.table_name == other.table_name
+            && self.quote == other.quote
+            && self.body == other.body
+    }
+}
+
+impl Hash for Trigger {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // without `if_exists`, consistent with PartialEq
+        self.name.hash(state);
+        self.event.hash(state);
+        self.table_name.hash(state);
+        self.quote.hash(state);
+        self.body.hash(state);
+    }
+}
+
+// endregion
+
+// region Pragma
+
+/// The value of a [Pragma]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
+pub enum PragmaValue {
+    /// `ON`
+    On,
+    /// `OFF`
+    Off,
+    /// A integer literal
+    Integer(i64),
+    /// A string literal, single quotes are escaped
+    Text(String),
+}
+
+impl SQLPart for PragmaValue {
+    fn part_len(&self) -> Result<usize> {
+        Ok(match self {
+            PragmaValue::On => { 2 }
+            PragmaValue::Off => { 3 }
+            PragmaValue::Integer(int) => { int.to_string().len() }
+            PragmaValue::Text(text) => { 1 + text.len() + text.matches('\'').count() + 1 }
+        })
+    }
+
+    fn part_str(&self, sql: &mut String) -> Result<()> {
+        match self {
+            PragmaValue::On => { sql.push_str("ON") }
+            PragmaValue::Off => { sql.push_str("OFF") }
+            PragmaValue::Integer(int) => { sql.push_str(int.to_string().as_str()) }
+            PragmaValue::Text(text) => {
+                sql.push('\'');
+                sql.push_str(text.replace('\'', "''").as_str());
+                sql.push('\'');
+            }
+        };
+        Ok(())
+    }
+
+    #[cfg(test)]
+    fn possibilities(_: bool) -> Vec<Box<Self>> {
+        vec![
+            Box::new(Self::On),
+            Box::new(Self::Off),
+            Box::new(Self::Integer(-2000)),
+            Box::new(Self::Text("WAL".to_string())),
+            Box::new(Self::Text("it's".to_string())),
+        ]
+    }
+}
+
+/// Represents a `PRAGMA name = value` Statement, see [here](https://www.sqlite.org/pragma.html).
+/// Can be converted into an SQL Statement via the [SQLStatement] Methods.
+/// Pragmas added to a [Schema] are emitted before all other Statements, outside of the Transaction
+/// (some Pragmas, e.g. `foreign_keys`, have no effect inside a Transaction).
+/// It is a Error for the `name` to be empty ([Error::EmptyPragmaName]).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Pragma {
+    name: String,
+    // the value is a element, e.g. `<pragma name="cache_size"><integer>-2000</integer></pragma>`
+    value: PragmaValue,
+}
+
+impl Pragma {
+    fn check(&self) -> Result<()> {
+        if self.name.is_empty() {
+            return Err(Error::EmptyPragmaName);
+        }
+        Ok(())
+    }
+
+    pub fn new(name: String, value: PragmaValue) -> Self {
+        Self {
+            name,
+            value,
+        }
+    }
+
+    /// `PRAGMA foreign_keys = ON`, enables the enforcement of [ForeignKey]s
+    pub fn foreign_keys_on() -> Self {
+        Self::new("foreign_keys".to_string(), PragmaValue::On)
+    }
+
+    /// `PRAGMA foreign_keys = OFF`, disables the enforcement of [ForeignKey]s (the SQLite default)
+    pub fn foreign_keys_off() -> Self {
+        Self::new("foreign_keys".to_string(), PragmaValue::Off)
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn value(&self) -> &PragmaValue {
+        &self.value
+    }
+}
+
+impl SQLPart for Pragma {
+    fn part_len(&self) -> Result<usize> {
+        self.check()?;
+        Ok(
+            7 // "PRAGMA "
+            + self.name.len()
+            + 3 // " = "
+            + self.value.part_len()?
+        )
+    }
+
+    fn part_str(&self, sql: &mut String) -> Result<()> {
+        self.check()?;
+        sql.push_str("PRAGMA ");
+        sql.push_str(self.name.as_str());
+        sql.push_str(" = ");
+        self.value.part_str(sql)
+    }
+
+    #[cfg(test)]
+    fn possibilities(illegal: bool) -> Vec<Box<Self>> {
+        let mut ret: Vec<Box<Self>> = Vec::new();
+        for value in PragmaValue::possibilities(illegal) {
+            ret.push(Box::new(Self::new("test".to_string(), *value.clone())));
+            if illegal {
+                ret.push(Box::new(Self::new("".to_string(), *value)));
+            }
+        }
+        ret
+    }
+}
+
+impl SQLStatement for Pragma {}
+
+// endregion
+
+// region Schema
+
+/// A Schema (or Layout, hence the crate name) encompasses one or more [Table]s and any number of [Index]es on them.
+/// Can be converted into an SQL Statement via the [SQLStatement] Methods.
+/// It is a Error for the Schema to be empty ([Error::SchemaWithoutTables]).
+#[derive(Debug, Clone, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename = "schema"))]
+pub struct Schema {
+    #[cfg_attr(feature = "serde", serde(rename = "table"))]
+    tables: Vec<Table>,
+    #[cfg_attr(feature = "serde", serde(rename = "index", default, skip_serializing_if = "Vec::is_empty"))]
+    indexes: Vec<Index>,
+    #[cfg_attr(feature = "serde", serde(rename = "pragma", default, skip_serializing_if = "Vec::is_empty"))]
+    pragmas: Vec<Pragma>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    comment: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    name: Option<String>,
+}
+
+/// Top-level TOML document, so that a [Schema] is written as `[[schema.table]]`
+#[cfg(feature = "toml-config")]
+#[derive(Serialize, Deserialize)]
+struct TomlSchema<S> {
+    schema: S,
+}
+
+impl Schema {
+    fn check(&self) -> Result<()> {
+        if self.tables.is_empty() {
+            return Err(Error::SchemaWithoutTables);
+        }
+
+        for (num, tbl) in self.tables.iter().enumerate() {
+            // SQLite identifiers are case-insensitive (for ASCII), so `test` and `TEST` clash
+            if self.tables[..num].iter().any(|other| other.name.eq_ignore_ascii_case(&tbl.name)) {
+                return Err(Error::DuplicateTableName(tbl.name.clone()));
+            }
+        }
+
+        if self.name.as_deref() == Some("") {
+            return Err(Error::EmptySchemaName);
+        }
+        Ok(())
+    }
+
+    pub fn new() -> Self {
+        Self {
+            tables: Vec::new(),
+            indexes: Vec::new(),
+            pragmas: Vec::new(),
+            comment: None,
+            name: None,
+        }
+    }
+
+    /// Creates an empty Schema described by `name`, which is stored as its comment (see [Schema::comment]).
+    pub fn named(name: String) -> Self {
+        Self::new().set_comment(Some(name))
+    }
+
+    /// Creates a Schema from any iterator of [Table]s, like the `From<Vec<Table>>` impl
+    pub fn from_tables(tables: impl IntoIterator<Item = Table>) -> Self {
+        Self::from(tables.into_iter().collect::<Vec<Table>>())
+    }
+
+    /// Reads and deserializes a Schema from a XML file. The result is not checked, see [Schema::validate].
+    /// I/O Errors are reported as [DeError::InvalidXml].
+    #[cfg(feature = "xml-config")]
+    pub fn from_xml_file(path: impl AsRef<Path>) -> Result<Schema, DeError> {
+        let xml: String = std::fs::read_to_string(path).map_err(quick_xml::Error::from)?;
+        xml::from_str(xml.as_str())
+    }
+
+    /// Deserializes a Schema from any buffered reader (e.g. a `BufReader<File>`), see [xml::from_reader]. The result is not checked.
+    #[cfg(feature = "xml-config")]
+    pub fn from_xml_reader<R: std::io::BufRead>(reader: R) -> Result<Schema, DeError> {
+        xml::from_reader(reader)
+    }
+
+    /// Serializes the Schema and writes it to a XML file.
+    /// I/O Errors are reported as [DeError::InvalidXml] (quick-xml uses [DeError] for serialization as well).
+    #[cfg(feature = "xml-config")]
+    pub fn to_xml_file(&self, path: impl AsRef<Path>) -> Result<(), DeError> {
+        let xml: String = xml::to_string(self)?;
+        std::fs::write(path, xml).map_err(quick_xml::Error::from)?;
+        Ok(())
+    }
+
+    /// Deserializes a Schema from JSON. The result is not checked, see [Schema::validate].
+    #[cfg(feature = "json-config")]
+    pub fn from_json(s: &str) -> Result<Schema, serde_json::Error> {
+        serde_json::from_str(s)
+    }
+
+    /// Serializes the Schema to JSON.
+    #[cfg(feature = "json-config")]
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    /// Deserializes a Schema from TOML, the [Table]s are expected as `[[schema.table]]`. The result is not checked, see [Schema::validate].
+    #[cfg(feature = "toml-config")]
+    pub fn from_toml(s: &str) -> Result<Schema> {
+        Ok(toml::from_str::<TomlSchema<Schema>>(s)?.schema)
+    }
+
+    /// Serializes the Schema to TOML, the [Table]s are written as `[[schema.table]]`.
+    #[cfg(feature = "toml-config")]
+    pub fn to_toml(&self) -> Result<String> {
+        Ok(toml::to_string(&TomlSchema { schema: self })?)
+    }
+
+    pub fn add_table(mut self, new_table: Table) -> Self {
+        self.tables.push(new_table);
+        self
+    }
+
+    pub fn add_index(mut self, new_index: Index) -> Self {
+        self.indexes.push(new_index);
+        self
+    }
+
+    /// Adds a [Pragma], which is emitted before all other Statements.
+    pub fn add_pragma(mut self, new_pragma: Pragma) -> Self {
+        self.pragmas.push(new_pragma);
+        self
+    }
+
+    /// Replaces all [Pragma]s, see [Schema::add_pragma].
+    pub fn with_pragmas(mut self, pragmas: Vec<Pragma>) -> Self {
+        self.pragmas = pragmas;
+        self
+    }
+
+    fn version_requirements(&self) -> Vec<CompatibilityWarning> {
+        let mut ret: Vec<CompatibilityWarning> = Vec::new();
+        let reqs = self.tables.iter().flat_map(Table::version_requirements).chain(self.indexes.iter().flat_map(Index::version_requirements));
+        for req in reqs {
+            if !ret.contains(&req) {
+                ret.push(req);
+            }
+        }
+        ret
+    }
+
+    /// Returns the minimum [SQLiteVersion] supporting all features used by the Schema (at least `3.0.0`).
+    pub fn min_required_version(&self) -> SQLiteVersion {
+        self.version_requirements().iter()
+            .map(CompatibilityWarning::required)
+            .fold(SQLiteVersion::new(3, 0, 0), Ord::max)
+    }
+
+    /// Sets [Quote::DoubleQuote] wherever a name has to be quoted (see [needs_quoting]): on [Table]s, [Column]s and [Index]es,
+    /// and on the references to them in [ForeignKey]s, [CompositeForeignKey]s and [IndexColumn]s.
+    /// The Columns of [TableConstraint]s are quoted like the Columns of the Table. Quoting other than [Quote::None] is left as-is.
+    pub fn auto_quote(mut self) -> Self {
+        fn quote_if_needed(quote: &mut Quote, names: &[&str]) {
+            if *quote == Quote::None && names.iter().any(|name| needs_quoting(name)) {
+                *quote = Quote::DoubleQuote;
+            }
+        }
+
+        fn quote_name_if_needed<C>(named: Option<&mut Named<C>>) {
+            if let Some(named) = named {
+                if let Some(name) = named.name.as_deref() {
+                    quote_if_needed(&mut named.name_quote, &[name]);
+                }
+            }
+        }
+
+        for tbl in &mut self.tables {
+            quote_if_needed(&mut tbl.quote, &[tbl.name.as_str()]);
+            for col in &mut tbl.columns {
+                quote_if_needed(&mut col.quote, &[col.name.as_str()]);
+                if let Some(fk) = col.fk.as_deref_mut() {
+                    quote_if_needed(&mut fk.quote, &[fk.foreign_table.as_str(), fk.foreign_column.as_str()]);
+                }
+                quote_name_if_needed(col.pk.as_mut());
+                quote_name_if_needed(col.unique.as_mut());
+                quote_name_if_needed(col.fk.as_mut());
+                quote_name_if_needed(col.not_null.as_mut());
+                quote_name_if_needed(col.check.as_mut());
+            }
+            for constraint in &mut tbl.constraints {
+                if let TableConstraint::CompositeForeignKey(fk) = constraint {
+                    let mut names: Vec<&str> = vec![fk.foreign_table.as_str()];
+                    names.extend(fk.foreign_columns.iter().map(String::as_str));
+                    quote_if_needed(&mut fk.quote, &names);
+                }
+            }
+        }
+        for idx in &mut self.indexes {
+            quote_if_needed(&mut idx.quote, &[idx.name.as_str(), idx.table.as_str()]);
+            for col in &mut idx.columns {
+                quote_if_needed(&mut col.quote, &[col.name.as_str()]);
+            }
+        }
+        self
+    }
+
+    /// Sets `PRAGMA foreign_keys` (see [Pragma::foreign_keys_on]), replacing any previously added `foreign_keys` [Pragma].
+    /// SQLite does not enforce [ForeignKey]s unless enabled.
+    pub fn with_foreign_keys_enabled(mut self, enabled: bool) -> Self {
+        self.pragmas.retain(|pragma| pragma.name != "foreign_keys");
+        self.add_pragma(if enabled { Pragma::foreign_keys_on() } else { Pragma::foreign_keys_off() })
+    }
+
+    pub fn pragmas(&self) -> &[Pragma] {
+        self.pragmas.as_slice()
+    }
+
+    /// Combines two Schemas by appending the [Table]s, [Index]es and [Pragma]s of `other`.
+    /// The merged Schema is checked, so overlapping [Table] names result in a Error ([Error::DuplicateTableName]).
+    pub fn merge(mut self, other: Schema) -> Result<Schema> {
+        self.tables.extend(other.tables);
+        self.indexes.extend(other.indexes);
+        self.pragmas.extend(other.pragmas);
+        self.check()?;
+        Ok(self)
+    }
+
+    /// Appends clones of the [Table]s, [Index]es and [Pragma]s of `other`, without checking the result.
+    pub fn extend(&mut self, other: &Schema) {
+        self.tables.extend(other.tables.iter().cloned());
+        self.indexes.extend(other.indexes.iter().cloned());
+        self.pragmas.extend(other.pragmas.iter().cloned());
+    }
+
+    /// Removes the first [Table] named `name` and returns it, if there is one.
+    pub fn remove_table(&mut self, name: &str) -> Option<Table> {
+        let pos: usize = self.tables.iter().position(|tbl| tbl.name.eq_ignore_ascii_case(name))?;
+        Some(self.tables.remove(pos))
+    }
+
+    /// Returns the first [Table] named `name`, if there is one.
+    /// Like in SQLite, Table names are compared case-insensitively (for ASCII), as by every other Table lookup of a Schema.
+    pub fn get_table(&self, name: &str) -> Option<&Table> {
+        self.tables.iter().find(|tbl| tbl.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Checks the entire Schema, including all [Table]s and [Index]es, and additionally verifies that
+    /// every [ForeignKey] references a [Column] of a [Table] within this Schema ([Error::UnresolvedForeignKey]).
+    /// Collects all violations instead of stopping at the first one.
+    pub fn validate(&self) -> Result<(), Vec<Error>> {
+        let mut errors: Vec<Error> = Vec::new();
+
+        if let Err(err) = self.check() {
+            errors.push(err);
+        }
+
+        for tbl in &self.tables {
+            if let Err(err) = tbl.check() {
+                errors.push(err);
+            }
+
+            for col in &tbl.columns {
+                if let Some(fk) = col.fk.as_ref() {
+                    let resolved: bool = self.tables.iter()
+                        .filter(|foreign_tbl| foreign_tbl.name.eq_ignore_ascii_case(&fk.foreign_table))
+                        .any(|foreign_tbl| foreign_tbl.has_column(fk.foreign_column.as_str()));
+                    if !resolved {
+                        errors.push(Error::UnresolvedForeignKey {
+                            from_table: tbl.name.clone(),
+                            from_column: col.name.clone(),
+                            to_table: fk.foreign_table.clone(),
+                            to_column: fk.foreign_column.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        for idx in &self.indexes {
+            if let Err(err) = idx.check() {
+                errors.push(err);
+            }
+
+            match self.get_table(idx.table.as_str()) {
+                None => errors.push(Error::TableNotFound(idx.table.clone())),
+                Some(tbl) => {
+                    if let Some(missing) = idx.columns.iter().find(|col| !tbl.has_column(col.name.as_str())) {
+                        errors.push(Error::ColumnNotFound(missing.name.clone()));
+                    }
+                }
+            }
+        }
+
+        for pragma in &self.pragmas {
+            if let Err(err) = pragma.check() {
+                errors.push(err);
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    pub fn tables(&self) -> &[Table] {
+        self.tables.as_slice()
+    }
+
+    pub fn tables_iter(&self) -> impl Iterator<Item = &Table> {
+        self.tables.iter()
+    }
+
+    pub fn tables_mut(&mut self) -> impl Iterator<Item = &mut Table> {
+        self.tables.iter_mut()
+    }
+
+    pub fn table_count(&self) -> usize {
+        self.tables.len()
+    }
+
+    pub fn indexes(&self) -> &[Index] {
+        self.indexes.as_slice()
+    }
+
+    pub fn indexes_iter(&self) -> impl Iterator<Item = &Index> {
+        self.indexes.iter()
+    }
+
+    /// Removes the first [Index] named `name` and returns it, if there is one.
+    pub fn remove_index(&mut self, name: &str) -> Option<Index> {
+        let pos: usize = self.indexes.iter().position(|idx| idx.name == name)?;
+        Some(self.indexes.remove(pos))
+    }
+
+    /// Sets the comment emitted by [SQLStatement::build_pretty], see [Schema::comment]
+    pub fn set_comment(mut self, comment: Option<String>) -> Self {
+        self.comment = comment;
+        self
+    }
+
+    /// Description of the Schema, only emitted as a SQL comment at the start of [SQLStatement::build_pretty]
+    pub fn comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+
+    /// Sets the name of the database the Schema lives in, e.g. `temp` or one attached via [Schema::to_attach_stmt].
+    /// When set, all [Table]s and [Index]es are built qualified, like `name.table`, the name is quoted if needed (see [needs_quoting]).
+    /// [Pragma]s are not qualified. Unlike [Schema::named], this changes the generated SQL.
+    /// It is a Error for the name to be empty ([Error::EmptySchemaName]).
+    pub fn set_name(mut self, name: String) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// Name of the database the Schema lives in, see [Schema::set_name]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the `ATTACH DATABASE 'path' AS name;` Statement for this Schema, fails with [Error::SchemaWithoutName] if it has no name
+    /// and with [Error::EmptySchemaName] if it is empty.
+    pub fn to_attach_stmt(&self, path: &str) -> Result<String> {
+        let name: &str = self.name.as_deref().ok_or(Error::SchemaWithoutName)?;
+        if name.is_empty() {
+            return Err(Error::EmptySchemaName);
+        }
+        let mut ret: String = format!("ATTACH DATABASE '{}' AS ", path.replace('\'', "''"));
+        Quote::for_name(name).push_ident(&mut ret, name);
+        ret.push(';');
+        Ok(ret)
+    }
+
+    /// Wraps the Schema for a readable tree view in logs, see [SchemaDebugDisplay]
+    pub fn display(&self) -> SchemaDebugDisplay<'_> {
+        SchemaDebugDisplay(self)
+    }
+
+    /// Builds every Statement of this Schema on its own, without a Transaction.
+    /// Every [String] is a complete Statement including the trailing `;`, in the order [Pragma]s, [Table]s, [Index]es.
+    pub fn build_separate(&mut self, if_exists: bool) -> Result<Vec<String>> {
+        self.check()?;
+        let mut ret: Vec<String> = Vec::with_capacity(self.pragmas.len() + self.tables.len() + self.indexes.len());
+        for pragma in &mut self.pragmas {
+            ret.push(pragma.build(false, if_exists)?);
+        }
+        for tbl in &mut self.tables {
+            let mut sql: String = String::new();
+            tbl.write_stmt(&mut sql, false, if_exists, None, self.name.as_deref())?;
+            ret.push(sql);
+        }
+        for idx in &mut self.indexes {
+            let mut sql: String = String::new();
+            idx.write_stmt(&mut sql, false, if_exists, self.name.as_deref())?;
+            ret.push(sql);
+        }
+        Ok(ret)
+    }
+
+    /// Compares the [Table]s of this Schema (the old version) to the ones of `other` (the new version), matched by name.
+    /// See [SchemaDiff].
+    pub fn diff(&self, other: &Schema) -> SchemaDiff {
+        let mut ret: SchemaDiff = SchemaDiff {
+            schema: other.name.clone(),
+            old_indexes: self.indexes.clone(),
+            ..SchemaDiff::default()
+        };
+        for old in &self.tables {
+            for col in &old.columns {
+                if let Some(fk) = &col.fk {
+                    ret.old_fk_targets.push((fk.foreign_table.clone(), fk.foreign_column.clone()));
+                }
+            }
+            for constraint in &old.constraints {
+                if let TableConstraint::CompositeForeignKey(fk) = constraint {
+                    ret.old_fk_targets.extend(fk.foreign_columns.iter().map(|col| (fk.foreign_table.clone(), col.clone())));
+                }
+            }
+            match other.get_table(old.name.as_str()) {
+                None => ret.removed_tables.push(old.clone()),
+                // a Table name differing only in case is the same Table to SQLite
+                Some(new) if new.clone().set_name(old.name.clone()) != *old => ret.modified_tables.push((old.clone(), new.clone())),
+                Some(_) => {}
+            }
+        }
+        for new in &other.tables {
+            if self.get_table(new.name.as_str()).is_none() {
+                ret.added_tables.push(new.clone());
+            }
+        }
+        ret
+    }
+
+    /// Executes the given `pragmas` one by one and then the Schema itself (including its own [Pragma]s) on the given [Connection].
+    /// `transaction` and `if_exists` are the same as in [SQLStatement::build].
+    #[cfg(feature = "rusqlite")]
+    pub fn execute_with_pragmas(&mut self, pragmas: &[Pragma], transaction: bool, if_exists: bool, conn: &Connection) -> Result<(), ExecError> {
+        self.check()?;
+        for pragma in pragmas {
+            conn.execute_batch(pragma.clone().build(false, false)?.as_str())?;
+        }
+        self.execute(transaction, if_exists, conn)
+    }
+
+    /// Executes the Schema in a Transaction with `PRAGMA foreign_keys = ON`, see [Schema::execute_with_pragmas].
+    #[cfg(feature = "rusqlite")]
+    pub fn execute_default(&mut self, conn: &Connection) -> Result<(), ExecError> {
+        self.execute_with_pragmas(&[Pragma::foreign_keys_on()], true, false, conn)
+    }
+
+    /// Runs [Table::explain_query_plan] for every `(table_name, query)` pair and returns the `(table_name, plan)` pairs.
+    /// Fails with [Error::TableNotFound] if the Schema has no Table named `table_name`.
+    #[cfg(feature = "rusqlite")]
+    pub fn explain_all_query_plans(&mut self, queries: &[(&str, &str)], conn: &Connection) -> Result<Vec<(String, String)>, CheckError> {
+        let mut ret: Vec<(String, String)> = Vec::with_capacity(queries.len());
+        for (table_name, query) in queries {
+            let tbl: &mut Table = self.tables.iter_mut()
+                .find(|tbl| tbl.name.eq_ignore_ascii_case(table_name))
+                .ok_or_else(|| Error::TableNotFound(table_name.to_string()))?;
+            ret.push((table_name.to_string(), tbl.explain_query_plan(query, conn)?));
+        }
+        Ok(ret)
+    }
+
+    /// Builds the Statements dropping everything in the Schema, in reverse order of creation:
+    /// first all [Index]es, then all [Table]s (later Tables first, as they may reference earlier ones).
+    /// `if_exists` adds `IF EXISTS` guards, see [DropTable] and [DropIndex].
+    pub fn to_drop_stmt(&self, if_exists: bool) -> Result<String> {
+        self.check()?;
+        let mut ret: String = String::new();
+        for idx in self.indexes.iter().rev() {
+            DropIndex::new(idx.name.clone())
+                .set_quote(idx.quote)
+                .set_schema(self.name.clone())
+                .build_into(&mut ret, false, if_exists)?;
+        }
+        for tbl in self.tables.iter().rev() {
+            DropTable::new(tbl.name.clone())
+                .set_quote(tbl.quote)
+                .set_schema(self.name.clone())
+                .build_into(&mut ret, false, if_exists)?;
+        }
+        Ok(ret)
+    }
+
+    /// Executes [Schema::to_drop_stmt] in a Transaction, with [ForeignKey]s only checked at its end (`PRAGMA defer_foreign_keys`).
+    /// If a Statement fails, the Transaction is rolled back.
+    #[cfg(feature = "rusqlite")]
+    pub fn execute_drop(&mut self, if_exists: bool, conn: &Connection) -> Result<(), ExecError> {
+        let mut sql: String = String::from("BEGIN;\n");
+        Pragma::new("defer_foreign_keys".to_string(), PragmaValue::On).build_into(&mut sql, false, false)?;
+        sql.push_str(self.to_drop_stmt(if_exists)?.as_str());
+        sql.push_str("\nEND;");
+        if let Err(err) = conn.execute_batch(sql.as_str()) {
+            // a failing Statement leaves the Transaction open
+            if !conn.is_autocommit() {
+                conn.execute_batch("ROLLBACK;")?;
+            }
+            return Err(err.into());
+        }
+        Ok(())
+    }
+
+    /// Validates the Schema (see [Schema::validate]) and executes it in a Transaction with `IF NOT EXISTS` guards.
+    /// If validation fails, the first violation is returned as [ExecError::Error].
+    #[cfg(feature = "rusqlite")]
+    pub fn apply_to(&mut self, conn: &Connection) -> Result<(), ExecError> {
+        if let Err(mut errors) = self.validate() {
+            return Err(ExecError::Error(errors.swap_remove(0)));
+        }
+        self.execute(true, true, conn)
+    }
+
+    /// Drops all existing Tables in the DB (together with their Indexes) and then applies the Schema, see [Schema::apply_to].
+    /// Nothing is dropped if validation fails, and if dropping fails, its Transaction is rolled back.
+    #[cfg(feature = "rusqlite")]
+    pub fn apply_fresh(&mut self, conn: &Connection) -> Result<(), ExecError> {
+        if let Err(mut errors) = self.validate() {
+            return Err(ExecError::Error(errors.swap_remove(0)));
+        }
+
+        let mut stmt: Statement = conn.prepare("SELECT name FROM sqlite_master WHERE type == 'table' AND name NOT LIKE 'sqlite_%';")?;
+        let mut rows: Rows = stmt.query(())?;
+        // Foreign Keys between the dropped Tables are only checked at the end of the Transaction
+        let mut sql: String = String::from("BEGIN;\n");
+        Pragma::new("defer_foreign_keys".to_string(), PragmaValue::On).build_into(&mut sql, false, false)?;
+        while let Some(row) = rows.next()? {
+            // the names in the DB may be anything, e.g. keywords or with spaces
+            DropTable::new(row.get("name")?).set_quote(Quote::DoubleQuote).build_into(&mut sql, false, true)?;
+        }
+        sql.push_str("\nEND;");
+        if let Err(err) = conn.execute_batch(sql.as_str()) {
+            // a failing Statement leaves the Transaction open
+            if !conn.is_autocommit() {
+                conn.execute_batch("ROLLBACK;")?;
+            }
+            return Err(err.into());
+        }
+
+        self.apply_to(conn)
+    }
+
+    /// Reads the Schema of the `main` DB as far as SQLite reports it via `PRAGMA`s: [Table]s (`WITHOUT ROWID`, `STRICT`),
+    /// [Column]s (type affinity, [PrimaryKey], [Unique], [ForeignKey], [NotNull], [DefaultValue]) and explicitly created [Index]es (without `WHERE` clause).
+    /// Not read are e.g. `CHECK` and `ON CONFLICT` clauses, Column [Collation]s, `AUTOINCREMENT`, `DEFERRABLE`, constraint names and comments.
+    /// Names are quoted where needed, see [Schema::auto_quote]. `STRICT` Tables with `ANY` Columns cannot be read ([Error::StrictTableAnyColumn]).
+    #[cfg(feature = "rusqlite")]
+    pub fn from_db(conn: &Connection) -> Result<Schema, CheckError> {
+        let mut ret: Schema = Schema::new();
+
+        let mut stmt: Statement = conn.prepare(r#"SELECT m.name, l.wr, l.strict FROM sqlite_master AS m, pragma_table_list(m.name) AS l WHERE m.type == "table" AND m.name NOT LIKE "sqlite_%" AND l.schema == "main" ORDER BY m.rowid;"#)?;
+        let mut rows: Rows = stmt.query(())?;
+        while let Some(row) = rows.next()? {
+            ret.tables.push(Table::from_db(row.get("name")?, row.get("wr")?, row.get("strict")?, conn)?);
+        }
+
+        let mut stmt: Statement = conn.prepare(r#"SELECT m.name, m.tbl_name, l."unique" FROM sqlite_master AS m, pragma_index_list(m.tbl_name) AS l WHERE m.type == "index" AND l.name == m.name AND l.origin == "c" ORDER BY m.rowid;"#)?;
+        let mut rows: Rows = stmt.query(())?;
+        let mut col_stmt: Statement = conn.prepare(r#"SELECT name, "desc", coll FROM pragma_index_xinfo(?1) WHERE key == 1 ORDER BY seqno;"#)?;
+        while let Some(row) = rows.next()? {
+            let name: String = row.get("name")?;
+            let mut columns: Vec<IndexColumn> = Vec::new();
+            let mut col_rows: Rows = col_stmt.query([name.as_str()])?;
+            while let Some(col_row) = col_rows.next()? {
+                columns.push(IndexColumn {
+                    name: col_row.get("name")?,
+                    quote: Quote::None,
+                    order: if col_row.get("desc")? { Order::Descending } else { Order::Ascending },
+                    collation: Collation::from_db(col_row.get::<&str, String>("coll")?.as_str()),
+                });
+            }
+            ret.indexes.push(Index::new(name, row.get("tbl_name")?, columns, row.get("unique")?, None));
+        }
+        // names from the DB may be keywords or contain spaces
+        Ok(ret.auto_quote())
+    }
+
+    /// Compares this Schema (as the old version) to the DB (as the new version), see [Schema::from_db] and [Schema::diff].
+    #[cfg(feature = "rusqlite")]
+    pub fn diff_from_db(&self, conn: &Connection) -> Result<SchemaDiff, CheckError> {
+        Ok(self.diff(&Schema::from_db(conn)?))
+    }
+
+    /// Checks the given DB for deviations from the given Schema, including its [Index]es (see [Schema::check_db_with]).
+    /// todo: document return
+    #[cfg(feature = "rusqlite")]
+    pub fn check_db(&mut self, conn: &Connection) -> Result<Option<String>, CheckError> {
+        self.check_db_with(conn, true)
+    }
+
+    /// Same as [Schema::check_db], the [Index]es (uniqueness, partiality and [IndexColumn]s) are only compared if `check_indexes` is set.
+    #[cfg(feature = "rusqlite")]
+    pub fn check_db_with(&mut self, conn: &Connection, check_indexes: bool) -> Result<Option<String>, CheckError> {
+        self.tables.sort_unstable_by_key(| table: &Table | table.name.clone()); // todo ugly :(
+
+        let mut ret: String = String::new();
+
+        let mut stmt: Statement = conn.prepare(r#"SELECT name, ncol, wr, strict FROM pragma_table_list() WHERE (schema == "main") AND (type == "table") AND name NOT LIKE "%schema" ORDER BY name;"#)?;
+        let mut rows: Rows = stmt.query(())?;
+
+
+        for( num, table) in self.tables.iter().enumerate() {
+            let row: &Row = {
+                let raw_row = rows.next()?;
+                match raw_row {
+                    None => {
+                        write!(ret, "Table {}: expected table '{}', got nothing; ", num, table.name)?;
+                        break
+                    }
+                    Some(row) => { row }
+                }
+            };
+            if table.name != row.get::<&str, String>("name")? {
+                write!(ret, "Table {}: expected name '{}', got '{}'; ", num, table.name, row.get::<&str, String>("name")?)?;
+            }
+            if table.without_rowid != row.get::<&str, bool>("wr")? {
+                write!(ret, "Table {}: expected without_rowid {}, got {}; ", num, table.without_rowid, row.get::<&str, bool>("wr")?)?;
+            }
+            if table.strict != row.get::<&str, bool>("strict")? {
+                write!(ret, "Table {}: expected strict {}, got {}; ", num, table.strict, row.get::<&str, bool>("strict")?)?;
+            }
+            if table.columns.len() != row.get::<&str, usize>("ncol")? {
+                write!(ret, "Table {}: expected number of columns {}, got {}; ", num, table.columns.len(), row.get::<&str, usize>("ncol")?)?;
+            }
+            if table.name == row.get::<&str, String>("name")? {
+                table.check_db_columns(num, conn, &mut ret)?;
+                table.check_db_foreign_keys(num, conn, &mut ret)?;
+            }
+        }
+
+        let mut i: usize = self.tables.len();
+        while let Some(row) = rows.next()? {
+            write!(ret, "Table {}: expected nothing, got table '{}'; ", i, row.get::<&str, String>("name")?)?;
+            i += 1;
+        }
+
+        if check_indexes {
+            self.check_db_indexes(conn, &mut ret)?;
+        }
+
+        if ret.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(ret))
+        }
+    }
+
+    /// Compares the [Index]es of this Schema to the ones in the DB, used by [Schema::check_db_with].
+    #[cfg(feature = "rusqlite")]
+    fn check_db_indexes(&self, conn: &Connection, ret: &mut String) -> Result<(), CheckError> {
+        let mut stmt: Statement = conn.prepare(r#"SELECT name, "unique", partial FROM pragma_index_list(?1) WHERE name == ?2;"#)?;
+        for (num, idx) in self.indexes.iter().enumerate() {
+            let mut rows: Rows = stmt.query([idx.table.as_str(), idx.name.as_str()])?;
+            let Some(row) = rows.next()? else {
+                write!(ret, "Index {}: expected index '{}' on '{}', got nothing; ", num, idx.name, idx.table)?;
+                continue
+            };
+            if idx.unique != row.get::<&str, bool>("unique")? {
+                write!(ret, "Index {}: expected unique {}, got {}; ", num, idx.unique, !idx.unique)?;
+            }
+            if idx.where_expr.is_some() != row.get::<&str, bool>("partial")? {
+                write!(ret, "Index {}: expected partial {}, got {}; ", num, idx.where_expr.is_some(), idx.where_expr.is_none())?;
+            }
+            idx.check_db_columns(num, conn, ret)?;
+        }
+
+        // automatic Indexes (e.g. for UNIQUE Constraints) have no SQL
+        let mut stmt: Statement = conn.prepare(r#"SELECT name FROM sqlite_master WHERE type == "index" AND sql IS NOT NULL ORDER BY name;"#)?;
+        let mut rows: Rows = stmt.query(())?;
+        while let Some(row) = rows.next()? {
+            let name: String = row.get("name")?;
+            if !self.indexes.iter().any(|idx| idx.name == name) {
+                write!(ret, "Index: expected nothing, got index '{}'; ", name)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The part of a Schema consists of the Statements of its [Table]s and [Index]es, each with its trailing `;`.
+/// The [Pragma]s are not included, as they are placed outside of the Transaction by the [SQLStatement] Methods.
+impl SQLPart for Schema {
+    fn part_len(&self) -> Result<usize> {
+        self.check()?;
+        let prefix_len: usize = schema_prefix_len(self.name.as_deref());
+        let mut tbls_len: usize = 0;
+        for tbl in &self.tables {
+            tbls_len += prefix_len + tbl.part_len()? + 1; // ';'
+        }
+        let mut idxs_len: usize = 0;
+        for idx in &self.indexes {
+            idxs_len += prefix_len + idx.part_len()? + 1; // ';'
+        }
+        Ok(tbls_len + idxs_len)
+    }
+
+    fn part_str(&self, sql: &mut String) -> Result<()> {
+        self.check()?;
+        for tbl in &self.tables {
+            tbl.write_str(sql, None, self.name.as_deref())?;
+            sql.push(';');
+        }
+        for idx in &self.indexes {
+            idx.write_str(sql, self.name.as_deref())?;
+            sql.push(';');
+        }
+        Ok(())
+    }
+
+    #[cfg(test)]
+    fn possibilities(illegal: bool) -> Vec<Box<Self>> {
+        // not all Table and Index possibilities, those are tested on their own
+        let tbl: Table = Table::new_default("test".to_string()).add_column(Column::new_typed(SQLiteType::Integer, "test0".to_string()));
+        let mut ret: Vec<Box<Self>> = vec![
+            Box::new(Self::new().add_table(tbl.clone())),
+            Box::new(Self::new().add_table(tbl.clone()).add_index(Index::new_default("test_idx".to_string(), "test".to_string()).add_column("test0".to_string(), Order::Ascending))),
+            Box::new(Self::new().add_table(tbl.clone()).set_name("temp".to_string())),
+        ];
+        if illegal {
+            ret.push(Box::new(Self::new()));
+            ret.push(Box::new(Self::new().add_table(tbl.clone()).add_table(tbl)));
+        }
+        ret
+    }
+}
+
+impl SQLStatement for Schema {
+    fn len(&mut self, transaction: bool, if_exists: bool) -> Result<usize> {
+        self.set_if_exists(if_exists);
+        let mut pragmas_len: usize = 0;
+        for pragma in &self.pragmas {
+            pragmas_len += pragma.part_len()? + 1; // ';'
+        }
+        Ok(pragmas_len + transaction as usize * 7 + self.part_len()? + transaction as usize * 5)
+    }
+
+    fn build_into(&mut self, buf: &mut String, transaction: bool, if_exists: bool) -> Result<()> {
+        buf.reserve(self.len(transaction, if_exists)?);
+        // outside the Transaction, see Pragma
+        for pragma in &self.pragmas {
+            pragma.part_str(buf)?;
+            buf.push(';');
+        }
+        if transaction {
+            buf.push_str("BEGIN;\n");
+        }
+        self.part_str(buf)?;
+        if transaction {
+            buf.push_str("\nEND;")
+        }
+        Ok(())
+    }
+
+    fn len_with_mode(&mut self, mode: BuildMode, if_exists: bool) -> Result<usize> {
+        match mode {
+            BuildMode::Single => { self.len(true, if_exists) }
+            BuildMode::PerStatement => {
+                let stmt_num: usize = self.tables.len() + self.indexes.len();
+                // every Statement except Pragmas is wrapped by itself, plus newlines in between
+                Ok(self.len(false, if_exists)? + stmt_num * (7 + 5) + self.pragmas.len() + stmt_num - 1)
+            }
+        }
+    }
+
+    fn build_with_mode(&mut self, mode: BuildMode, if_exists: bool) -> Result<String> {
+        match mode {
+            BuildMode::Single => { self.build(true, if_exists) }
+            BuildMode::PerStatement => {
+                let mut ret: String = String::with_capacity(self.len_with_mode(mode, if_exists)?);
+                let mut needs_newline = false;
+                for pragma in &mut self.pragmas {
+                    if needs_newline {
+                        ret.push('\n');
+                    }
+                    pragma.build_into(&mut ret, false, if_exists)?;
+                    needs_newline = true;
+                }
+                for tbl in &mut self.tables {
+                    if needs_newline {
+                        ret.push('\n');
+                    }
+                    tbl.write_stmt(&mut ret, true, if_exists, None, self.name.as_deref())?;
+                    needs_newline = true;
+                }
+                for idx in &mut self.indexes {
+                    if needs_newline {
+                        ret.push('\n');
+                    }
+                    idx.write_stmt(&mut ret, true, if_exists, self.name.as_deref())?;
+                    needs_newline = true;
+                }
+                Ok(ret)
+            }
+        }
+    }
+
+    fn build_pretty(&mut self, transaction: bool, if_exists: bool, indent: usize) -> Result<String> {
+        self.check()?;
+        let mut ret: String = String::new();
+        if let Some(comment) = self.comment.as_ref() {
+            push_comment(&mut ret, comment.as_str());
+            ret.push('\n');
+        }
+        for pragma in &mut self.pragmas {
+            pragma.build_into(&mut ret, false, if_exists)?;
+            ret.push('\n');
+        }
+        if transaction {
+            ret.push_str("BEGIN;\n");
+        }
+
+        // one Statement per line
+        let mut needs_newline = false;
+        for tbl in &mut self.tables {
+            if needs_newline {
+                ret.push('\n');
+            }
+            tbl.write_stmt(&mut ret, false, if_exists, Some(indent), self.name.as_deref())?;
+            needs_newline = true;
+        }
+        for idx in &mut self.indexes {
+            if needs_newline {
+                ret.push('\n');
+            }
+            idx.write_stmt(&mut ret, false, if_exists, self.name.as_deref())?;
+            needs_newline = true;
+        }
+
+        if transaction {
+            ret.push_str("\nEND;");
+        }
+        Ok(ret)
+    }
+
+    fn check_version_compatibility(&self, version: SQLiteVersion) -> Result<(), Vec<CompatibilityWarning>> {
+        check_requirements(self.version_requirements(), version)
+    }
+}
+
+impl PartialEq<Schema> for Schema {
+    fn eq(&self, other: &Schema) -> bool {
+        if self.tables.len() != other.tables.len() {
+            return false;
+        }
+        for tables in self.tables.iter().zip(other.tables.iter()) {
+            if tables.0 != tables.1 {
+                return false;
+            }
+        }
+        if self.indexes != other.indexes {
+            return false;
+        }
+        if self.pragmas != other.pragmas {
+            return false;
+        }
+        if self.comment != other.comment {
+            return false;
+        }
+        if self.name != other.name {
+            return false;
+        }
+        true
+    }
+}
+
+impl Hash for Schema {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.tables.hash(state);
+        self.indexes.hash(state);
+        self.pragmas.hash(state);
+        self.comment.hash(state);
+        self.name.hash(state);
+    }
+}
+
+impl Default for Schema {
+    /// Same as [Schema::new]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Table> for Schema {
+    /// Creates a Schema containing only `table`
+    fn from(table: Table) -> Self {
+        Self::new().add_table(table)
+    }
+}
+
+impl From<Vec<Table>> for Schema {
+    /// Creates a Schema containing `tables`
+    fn from(tables: Vec<Table>) -> Self {
+        let mut ret = Self::new();
+        ret.tables = tables;
+        ret
+    }
+}
+
+/// Any single Statement a [Schema] consists of, see the [IntoIterator] and [FromIterator] impls of [Schema].
+/// Can be converted into an SQL Statement via the [SQLStatement] Methods, which are forwarded to the contained Statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AnySqlStatement {
+    Pragma(Pragma),
+    Table(Table),
+    Index(Index),
+}
+
+impl AnySqlStatement {
+    fn as_statement(&self) -> &dyn SQLStatement {
+        match self {
+            AnySqlStatement::Pragma(pragma) => pragma,
+            AnySqlStatement::Table(tbl) => tbl,
+            AnySqlStatement::Index(idx) => idx,
+        }
+    }
+
+    fn as_statement_mut(&mut self) -> &mut dyn SQLStatement {
+        match self {
+            AnySqlStatement::Pragma(pragma) => pragma,
+            AnySqlStatement::Table(tbl) => tbl,
+            AnySqlStatement::Index(idx) => idx,
+        }
+    }
+}
+
+impl SQLPart for AnySqlStatement {
+    fn part_len(&self) -> Result<usize> {
+        self.as_statement().part_len()
+    }
+
+    fn part_str(&self, sql: &mut String) -> Result<()> {
+        self.as_statement().part_str(sql)
+    }
+
+    #[cfg(test)]
+    fn possibilities(illegal: bool) -> Vec<Box<Self>> {
+        let mut ret: Vec<Box<Self>> = Vec::new();
+        ret.extend(Pragma::possibilities(illegal).into_iter().map(|pragma| Box::new(AnySqlStatement::Pragma(*pragma))));
+        ret.extend(Table::possibilities(illegal).into_iter().map(|tbl| Box::new(AnySqlStatement::Table(*tbl))));
+        ret.extend(Index::possibilities(illegal).into_iter().map(|idx| Box::new(AnySqlStatement::Index(*idx))));
+        ret
+    }
+}
+
+impl SQLStatement for AnySqlStatement {
+    fn len(&mut self, transaction: bool, if_exists: bool) -> Result<usize> {
+        self.as_statement_mut().len(transaction, if_exists)
+    }
+
+    fn build(&mut self, transaction: bool, if_exists: bool) -> Result<String> {
+        self.as_statement_mut().build(transaction, if_exists)
+    }
+
+    fn build_pretty(&mut self, transaction: bool, if_exists: bool, indent: usize) -> Result<String> {
+        self.as_statement_mut().build_pretty(transaction, if_exists, indent)
+    }
+
+    fn check_version_compatibility(&self, version: SQLiteVersion) -> Result<(), Vec<CompatibilityWarning>> {
+        self.as_statement().check_version_compatibility(version)
+    }
+
+    fn build_into(&mut self, buf: &mut String, transaction: bool, if_exists: bool) -> Result<()> {
+        self.as_statement_mut().build_into(buf, transaction, if_exists)
+    }
+
+    fn build_arr(&mut self, buf: &mut [u8], transaction: bool, if_exists: bool) -> Result<usize> {
+        self.as_statement_mut().build_arr(buf, transaction, if_exists)
+    }
+}
+
+impl From<Pragma> for AnySqlStatement {
+    fn from(pragma: Pragma) -> Self {
+        AnySqlStatement::Pragma(pragma)
+    }
+}
+
+impl From<Table> for AnySqlStatement {
+    fn from(table: Table) -> Self {
+        AnySqlStatement::Table(table)
+    }
+}
+
+impl From<Index> for AnySqlStatement {
+    fn from(index: Index) -> Self {
+        AnySqlStatement::Index(index)
+    }
+}
+
+impl IntoIterator for Schema {
+    type Item = AnySqlStatement;
+    type IntoIter = std::vec::IntoIter<AnySqlStatement>;
+
+    /// Yields all Statements in the order they are built in: [Pragma]s first, then [Table]s, then [Index]es.
+    /// The `comment` and `name` of the Schema are dropped, so the yielded [Table]s and [Index]es are not qualified.
+    fn into_iter(self) -> Self::IntoIter {
+        let mut ret: Vec<AnySqlStatement> = Vec::with_capacity(self.pragmas.len() + self.tables.len() + self.indexes.len());
+        ret.extend(self.pragmas.into_iter().map(AnySqlStatement::Pragma));
+        ret.extend(self.tables.into_iter().map(AnySqlStatement::Table));
+        ret.extend(self.indexes.into_iter().map(AnySqlStatement::Index));
+        ret.into_iter()
+    }
+}
+
+impl FromIterator<AnySqlStatement> for Schema {
+    /// Collects Statements into a Schema, keeping the relative order of each kind. The result is not checked.
+    fn from_iter<T: IntoIterator<Item = AnySqlStatement>>(iter: T) -> Self {
+        let mut ret = Self::new();
+        for stmt in iter {
+            match stmt {
+                AnySqlStatement::Pragma(pragma) => ret.pragmas.push(pragma),
+                AnySqlStatement::Table(tbl) => ret.tables.push(tbl),
+                AnySqlStatement::Index(idx) => ret.indexes.push(idx),
+            }
+        }
+        ret
+    }
+}
+
+/// Builder for a [Schema] that is validated on [SchemaBuilder::build], so the resulting [Schema] is always valid.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaBuilder {
+    schema: Schema,
+}
+
+impl SchemaBuilder {
+    pub fn new() -> Self {
+        Self { schema: Schema::new() }
+    }
+
+    pub fn add_table(mut self, new_table: Table) -> Self {
+        self.schema = self.schema.add_table(new_table);
+        self
+    }
+
+    pub fn add_index(mut self, new_index: Index) -> Self {
+        self.schema = self.schema.add_index(new_index);
+        self
+    }
+
+    /// See [Schema::add_pragma]
+    pub fn add_pragma(mut self, new_pragma: Pragma) -> Self {
+        self.schema = self.schema.add_pragma(new_pragma);
+        self
+    }
+
+    /// See [Schema::set_comment]
+    pub fn set_comment(mut self, comment: Option<String>) -> Self {
+        self.schema = self.schema.set_comment(comment);
+        self
+    }
+
+    /// Validates the Schema with [Schema::validate] and returns it, or the first violation.
+    pub fn build(self) -> Result<Schema> {
+        if let Err(mut errors) = self.schema.validate() {
+            return Err(errors.swap_remove(0));
+        }
+        Ok(self.schema)
+    }
+}
+
+/// Displays a [Schema] as a tree, created by [Schema::display]: the Schema (and its `name`) at the root,
+/// [Table]s indented by two spaces and their [Column]s by four, followed by the [TableConstraint]s and [Index]es.
+/// Every Column lists its name, type and constraints as SQL, e.g. `id INTEGER: PRIMARY KEY ASC ON CONFLICT ABORT, NOT NULL ON CONFLICT ABORT`.
+/// [Debug](fmt::Debug) is the same as [Display](fmt::Display).
+#[derive(Clone, Copy)]
+pub struct SchemaDebugDisplay<'a>(pub &'a Schema);
+
+impl SchemaDebugDisplay<'_> {
+    /// Renders a part as SQL, a broken part is shown as its [Error] instead of failing the whole output
+    fn part<P: SQLPart>(part: &P) -> String {
+        render_part(part).unwrap_or_else(|err| format!("<{}>", err))
+    }
+}
+
+impl fmt::Display for SchemaDebugDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("schema")?;
+        if let Some(name) = self.0.name.as_ref() {
+            write!(f, " {}", name)?;
+        }
+        for tbl in &self.0.tables {
+            write!(f, "\n  table {}", tbl.name)?;
+            for col in &tbl.columns {
+                write!(f, "\n    {} {}", col.name, col.typ)?;
+                let mut constraints: Vec<String> = Vec::new();
+                if let Some(pk) = col.pk.as_ref() {
+                    constraints.push(Self::part(pk));
+                }
+                if let Some(unique) = col.unique.as_ref() {
+                    constraints.push(Self::part(unique));
+                }
+                if let Some(not_null) = col.not_null.as_ref() {
+                    constraints.push(Self::part(not_null));
+                }
+                if let Some(fk) = col.fk.as_ref() {
+                    constraints.push(Self::part(fk));
+                }
+                if let Some(check) = col.check.as_ref() {
+                    constraints.push(Self::part(check));
+                }
+                if let Some(default) = col.default.as_ref() {
+                    constraints.push(Self::part(default));
+                }
+                if let Some(collation) = col.collation.as_ref() {
+                    constraints.push(Self::part(collation));
+                }
+                if !constraints.is_empty() {
+                    write!(f, ": {}", constraints.join(", "))?;
+                }
+            }
+            for constraint in &tbl.constraints {
+                write!(f, "\n    {}", Self::part(constraint))?;
+            }
+        }
+        for idx in &self.0.indexes {
+            write!(f, "\n  {}", Self::part(idx))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for SchemaDebugDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+// endregion Schema
+
+// region SchemaDiff
+
+/// The differences between two versions of a [Schema], see [Schema::diff].
+/// [Table]s are matched by name, [Index]es are not compared.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaDiff {
+    added_tables: Vec<Table>,
+    removed_tables: Vec<Table>,
+    modified_tables: Vec<(Table, Table)>,
+    // the name of the new Schema, qualifying every Table in the Statements
+    schema: Option<String>,
+    // the Indexes and ForeignKey targets (as `(table, column)`) of the old Schema, which prevent dropping a Column
+    old_indexes: Vec<Index>,
+    old_fk_targets: Vec<(String, String)>,
+}
+
+impl SchemaDiff {
+    /// [Table]s only in the new [Schema]
+    pub fn added_tables(&self) -> &[Table] {
+        self.added_tables.as_slice()
+    }
+
+    /// [Table]s only in the old [Schema]
+    pub fn removed_tables(&self) -> &[Table] {
+        self.removed_tables.as_slice()
+    }
+
+    /// [Table]s in both [Schema]s, but with differences, as `(old, new)`
+    pub fn modified_tables(&self) -> &[(Table, Table)] {
+        self.modified_tables.as_slice()
+    }
+
+    /// `true` if the [Table]s of both [Schema]s are the same
+    pub fn is_empty(&self) -> bool {
+        self.added_tables.is_empty() && self.removed_tables.is_empty() && self.modified_tables.is_empty()
+    }
+
+    // ref. https://www.sqlite.org/lang_altertable.html#alter_table_add_column
+    fn can_add_column(col: &Column) -> bool {
+        let non_null_default: bool = !matches!(col.default, None | Some(DefaultValue::Null));
+        col.pk.is_none()
+            && col.unique.is_none()
+            && (col.not_null.is_none() || non_null_default)
+            && !matches!(col.default, Some(DefaultValue::CurrentTime | DefaultValue::CurrentDate | DefaultValue::CurrentTimestamp | DefaultValue::Expr(_)))
+            && (col.fk.is_none() || !non_null_default)
+    }
+
+    // ref. https://www.sqlite.org/lang_altertable.html#alter_table_drop_column
+    fn can_drop_column(&self, tbl: &Table, col: &Column) -> bool {
+        let in_constraint: bool = tbl.constraints.iter().any(|constraint| match constraint {
+            TableConstraint::CompositePrimaryKey(cols, _) | TableConstraint::CompositeUnique(cols, _) => cols.iter().any(|name| name.eq_ignore_ascii_case(&col.name)),
+            TableConstraint::CompositeForeignKey(fk) => fk.local_columns.iter().any(|name| name.eq_ignore_ascii_case(&col.name)),
+        });
+        col.pk.is_none()
+            && col.unique.is_none()
+            && col.fk.is_none()
+            && !in_constraint
+            && !self.old_indexes.iter().any(|idx| idx.table.eq_ignore_ascii_case(&tbl.name) && idx.columns.iter().any(|idx_col| idx_col.name.eq_ignore_ascii_case(&col.name)))
+            && !self.old_fk_targets.iter().any(|(table, column)| table.eq_ignore_ascii_case(&tbl.name) && column.eq_ignore_ascii_case(&col.name))
+    }
+
+    /// Converts the diff into SQL Statements migrating the old [Schema] to the new one, each independently executable.
+    /// Added [Table]s are created first, then modified [Table]s are altered (`ADD COLUMN` and `DROP COLUMN`, see [SchemaDiff::check_version_compatibility]),
+    /// then removed [Table]s are dropped. `if_exists` adds `IF NOT EXISTS`/`IF EXISTS` guards to the `CREATE TABLE`/`DROP TABLE` Statements.
+    ///
+    /// Modifications other than added or removed [Column]s (e.g. a changed [SQLiteType]) cannot be expressed by SQLite's `ALTER TABLE`
+    /// and result in a Error ([Error::UnsupportedAlteration]), as do Columns SQLite cannot add or drop:
+    ///
+    /// * added Columns with a [PrimaryKey] or [Unique] constraint, with [NotNull] but without a non-NULL [DefaultValue],
+    ///   with a `CURRENT_*` or [DefaultValue::Expr] default or with a [ForeignKey] and a non-NULL default
+    /// * removed Columns that are part of a (composite) [PrimaryKey], [Unique] constraint or [ForeignKey], that are indexed by an [Index]
+    ///   or that are referenced by a ForeignKey of the old [Schema]
+    pub fn to_alter_statements(&self, if_exists: bool) -> Result<Vec<String>> {
+        let mut ret: Vec<String> = Vec::new();
+
+        for tbl in &self.added_tables {
+            let mut sql: String = String::new();
+            tbl.clone().write_stmt(&mut sql, false, if_exists, None, self.schema.as_deref())?;
+            ret.push(sql);
+        }
+
+        for (old, new) in &self.modified_tables {
+            if old.without_rowid != new.without_rowid || old.strict != new.strict || old.temp != new.temp || old.constraints != new.constraints {
+                return Err(Error::UnsupportedAlteration(new.name.clone()));
+            }
+            for col in &new.columns {
+                match old.get_column(col.name.as_str()) {
+                    // see https://www.sqlite.org/lang_altertable.html#alter_table_add_column
+                    None if !Self::can_add_column(col) => return Err(Error::UnsupportedAlteration(new.name.clone())),
+                    None => ret.push(
+                        AlterTableAddColumn::new(new.name.clone(), col.clone())
+                            .set_quote(new.quote)
+                            .set_schema(self.schema.clone())
+                            .build(false, false)?
+                    ),
+                    // comments are not part of the DB
+                    Some(old_col) if old_col.clone().set_comment(None) != col.clone().set_comment(None) => return Err(Error::UnsupportedAlteration(new.name.clone())),
+                    Some(_) => {}
+                }
+            }
+            for col in &old.columns {
+                if new.get_column(col.name.as_str()).is_none() {
+                    if !self.can_drop_column(old, col) {
+                        return Err(Error::UnsupportedAlteration(new.name.clone()));
+                    }
+                    let mut sql: String = String::from("ALTER TABLE ");
+                    push_schema_prefix(&mut sql, self.schema.as_deref());
+                    new.quote.push_ident(&mut sql, new.name.as_str());
+                    sql.push_str(" DROP COLUMN ");
+                    col.quote.push_ident(&mut sql, col.name.as_str());
+                    sql.push(';');
+                    ret.push(sql);
+                }
+            }
+        }
+
+        for tbl in &self.removed_tables {
+            ret.push(DropTable::new(tbl.name.clone()).set_quote(tbl.quote).set_schema(self.schema.clone()).build(false, if_exists)?);
+        }
+        Ok(ret)
+    }
+
+    /// Checks whether the Statements of [SchemaDiff::to_alter_statements] are supported by the given SQLite `version`,
+    /// like [SQLStatement::check_version_compatibility] does for a single Statement (e.g. `DROP COLUMN` requires SQLite 3.35.0).
+    pub fn check_version_compatibility(&self, version: SQLiteVersion) -> Result<(), Vec<CompatibilityWarning>> {
+        let mut requirements: Vec<CompatibilityWarning> = Vec::new();
+        for (old, new) in &self.modified_tables {
+            for col in new.columns.iter().filter(|col| !old.has_column(col.name.as_str())) {
+                requirements.push(CompatibilityWarning::ADD_COLUMN);
+                if col.fk.is_some() {
+                    requirements.push(CompatibilityWarning::FOREIGN_KEY);
+                }
+            }
+            if old.columns.iter().any(|col| !new.has_column(col.name.as_str())) {
+                requirements.push(CompatibilityWarning::DROP_COLUMN);
+            }
+        }
+        let mut unique: Vec<CompatibilityWarning> = Vec::new();
+        for req in self.added_tables.iter().flat_map(Table::version_requirements).chain(requirements) {
+            if !unique.contains(&req) {
+                unique.push(req);
+            }
+        }
+        check_requirements(unique, version)
+    }
+}
+
+impl PartialEq<SchemaDiff> for SchemaDiff {
+    fn eq(&self, other: &SchemaDiff) -> bool {
+        // the context of the old Schema is not part of the differences
+        self.added_tables == other.added_tables
+            && self.removed_tables == other.removed_tables
+            && self.modified_tables == other.modified_tables
+    }
+}
+
+impl Eq for SchemaDiff {}
+
+// endregion SchemaDiff
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    #[cfg(feature = "rusqlite")]
+    fn test_sql<S: SQLStatement>(stmt: &mut S) -> Result<()> {
+        for if_exists in [true, false] {
+            for transaction in [true, false] {
+                let sql: String = stmt.build(transaction, if_exists)?;
+
+                assert_eq!(sql.len(), stmt.len(transaction, if_exists)?);
+
+                let conn: Connection = Connection::open_in_memory()?;
+                let ret = conn.execute_batch(&sql);
+                if ret.is_err() {
+                    println!("Error SQL: '{}'", sql)
+                }
+                ret?
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "rusqlite"))]
+    fn test_sql<S: SQLStatement>(_stmt: &mut S) -> Result<()> {
+        // todo
+        Ok(())
+    }
+
+    /// A Schema using [TableConstraint]s and [Pragma]s, for the serde tests
+    #[cfg(any(feature = "xml-config", feature = "json-config", feature = "toml-config"))]
+    fn constrained_schema() -> Schema {
+        Schema::new()
+            .add_table(Table::new_default("parent".to_string())
+                .add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()))
+                .add_column(Column::new_typed(SQLiteType::Integer, "b".to_string()))
+                .add_constraint(TableConstraint::CompositePrimaryKey(vec!["a".to_string(), "b".to_string()], OnConflict::Abort)))
+            .add_table(Table::new_default("child".to_string())
+                .add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()))
+                .add_column(Column::new_typed(SQLiteType::Integer, "b".to_string()))
+                .add_unique_constraint(vec!["a".to_string(), "b".to_string()], OnConflict::Replace)
+                .add_composite_fk(CompositeForeignKey::new(vec!["a".to_string(), "b".to_string()], "parent".to_string(), vec!["a".to_string(), "b".to_string()],
+                    Some(FKOnAction::Cascade), None, FKDeferrable::InitiallyDeferred).set_quote(Quote::DoubleQuote)))
+            .add_pragma(Pragma::foreign_keys_on())
+            .add_pragma(Pragma::new("cache_size".to_string(), PragmaValue::Integer(-2000)))
+            .add_pragma(Pragma::new("journal_mode".to_string(), PragmaValue::Text("WAL".to_string())))
+    }
+
+    fn test_sql_part<P: SQLPart>(part: &P) -> Result<()> {
+        let str: String = part.to_sql_part()?;
+        assert_eq!(str.len(), part.part_len()?);
+
+        let mut arr: Vec<u8> = vec![0; str.len()];
+        assert_eq!(part.part_arr(arr.as_mut_slice())?, str.len());
+        assert_eq!(arr, str.as_bytes());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_arr() -> Result<()> {
+        let mut buf: [u8; 32] = [0; 32];
+        assert_eq!(SQLiteType::Integer.part_arr(&mut buf)?, 7);
+        assert_eq!(&buf[..7], b"INTEGER");
+        assert_eq!(SQLiteType::Integer.part_arr(&mut buf[..3]), Err(Error::BufferTooSmall { needed: 7, available: 3 }));
+        assert_eq!(Column::new_typed(SQLiteType::Text, "".to_string()).part_arr(&mut buf), Err(Error::EmptyColumnName));
+
+        let mut tbl = Table::new_default("test".to_string()).add_column(Column::new_typed(SQLiteType::Text, "a".to_string()));
+        let sql: String = tbl.build(true, true)?;
+        let mut buf: Vec<u8> = vec![0; sql.len() + 4];
+        assert_eq!(tbl.build_arr(buf.as_mut_slice(), true, true)?, sql.len());
+        assert_eq!(&buf[..sql.len()], sql.as_bytes());
+        assert_eq!(&buf[sql.len()..], &[0; 4]);
+        assert_eq!(tbl.build_arr(&mut buf[..10], true, true), Err(Error::BufferTooSmall { needed: sql.len(), available: 10 }));
+        assert_eq!(&buf[..sql.len()], sql.as_bytes());
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_part() -> Result<()> {
+        assert_eq!(render_part(&SQLiteType::Integer)?, "INTEGER");
+        assert_eq!(render_part(&Column::new_typed(SQLiteType::Text, "test".to_string()))?, "test TEXT");
+        assert_eq!(render_part(&Column::new_typed(SQLiteType::Text, "".to_string())), Err(Error::EmptyColumnName));
+        assert_eq!(PrimaryKey::default().to_sql_part()?, render_part(&PrimaryKey::default())?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_sqlite_type() -> Result<()> {
+        let mut str: String;
+
+        str = String::new();
+        SQLiteType::Blob.part_str(&mut str)?;
+        assert_eq!(str, "BLOB");
+        assert_eq!(str.len(), SQLiteType::Blob.part_len()?);
+
+        str = String::new();
+        SQLiteType::Numeric.part_str(&mut str)?;
+        assert_eq!(str, "NUMERIC");
+        assert_eq!(str.len(), SQLiteType::Numeric.part_len()?);
+
+        str = String::new();
+        SQLiteType::Integer.part_str(&mut str)?;
+        assert_eq!(str, "INTEGER");
+        assert_eq!(str.len(), SQLiteType::Integer.part_len()?);
+
+        str = String::new();
+        SQLiteType::Real.part_str(&mut str)?;
+        assert_eq!(str, "REAL");
+        assert_eq!(str.len(), SQLiteType::Real.part_len()?);
+
+        str = String::new();
+        SQLiteType::Text.part_str(&mut str)?;
+        assert_eq!(str, "TEXT");
+        assert_eq!(str.len(), SQLiteType::Text.part_len()?);
+
+        for typ in SQLiteType::possibilities(false) {
+            assert_eq!(typ.as_str(), render_part(&*typ)?);
+            assert_eq!(typ.affinity(), *typ);
+            assert_eq!(SQLiteType::from_affinity_str(typ.as_str()), *typ);
+        }
+
+        // examples from https://www.sqlite.org/datatype3.html#affinity_name_examples
+        for (name, typ) in [
+            ("INT", SQLiteType::Integer), ("TINYINT", SQLiteType::Integer), ("UNSIGNED BIG INT", SQLiteType::Integer), ("int8", SQLiteType::Integer),
+            ("CHARACTER(20)", SQLiteType::Text), ("VARCHAR(255)", SQLiteType::Text), ("NATIVE CHARACTER(70)", SQLiteType::Text), ("CLOB", SQLiteType::Text),
+            ("", SQLiteType::Blob),
+            ("DOUBLE", SQLiteType::Real), ("DOUBLE PRECISION", SQLiteType::Real), ("FLOAT", SQLiteType::Real),
+            ("DECIMAL(10,5)", SQLiteType::Numeric), ("BOOLEAN", SQLiteType::Numeric), ("DATETIME", SQLiteType::Numeric),
+            // "INT" takes precedence, see rule 1
+            ("FLOATING POINT", SQLiteType::Integer),
+        ] {
+            assert_eq!(SQLiteType::from_affinity_str(name), typ, "{}", name);
+            assert!(SQLiteType::from_affinity_str(name).compatible_with(typ), "{}", name);
+            assert_eq!(SQLiteType::from_affinity_str(name).affinity_name(), typ.as_str());
+        }
+        assert_eq!(SQLiteType::Integer.affinity_name(), "INTEGER");
+        assert!(!SQLiteType::Integer.compatible_with(SQLiteType::Numeric));
+        assert!(!SQLiteType::Text.compatible_with(SQLiteType::Blob));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_display() -> Result<()> {
+        for typ in SQLiteType::possibilities(false) {
+            let mut str: String = String::new();
+            typ.part_str(&mut str)?;
+            assert_eq!(format!("{}", typ), str);
+        }
+        for ord in Order::possibilities(false) {
+            let mut str: String = String::new();
+            ord.part_str(&mut str)?;
+            assert_eq!(format!("{}", ord), str);
+        }
+        for conf in OnConflict::possibilities(false) {
+            let mut str: String = String::new();
+            conf.part_str(&mut str)?;
+            assert_eq!(format!("{}", conf), str);
+        }
+        for action in FKOnAction::possibilities(false) {
+            let mut str: String = String::new();
+            action.part_str(&mut str)?;
+            assert_eq!(format!("{}", action), str);
+        }
+        for defer in FKDeferrable::possibilities(false) {
+            let mut str: String = String::new();
+            defer.part_str(&mut str)?;
+            assert_eq!(format!("{}", defer), str);
+        }
+        for collation in Collation::possibilities(false) {
+            let mut str: String = String::new();
+            collation.part_str(&mut str)?;
+            assert_eq!(format!("{}", collation), str);
+        }
+
+        assert_eq!(format!("{}", SQLiteType::Integer), "INTEGER");
+        assert_eq!(format!("{}", Order::Descending), "DESC");
+        assert_eq!(format!("{}", OnConflict::Replace), "ON CONFLICT REPLACE");
+        assert_eq!(OnConflict::Replace.keyword(), "REPLACE");
+        for conf in OnConflict::possibilities(false) {
+            assert_eq!(conf.keyword().parse::<OnConflict>()?, *conf);
+        }
+        assert_eq!(format!("{}", FKOnAction::SetNull), "SET NULL");
+        assert_eq!(format!("{}", FKDeferrable::InitiallyDeferred), "DEFERRABLE INITIALLY DEFERRED");
+        assert_eq!(format!("{}", Collation::NoCase), "COLLATE NOCASE");
+        assert_eq!(format!("{}", Collation::Custom("".to_string())), "COLLATE ");
+        assert_eq!(format!("{:>5}", Order::Ascending), "  ASC");
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_str() -> Result<()> {
+        for typ in SQLiteType::possibilities(false) {
+            assert_eq!(typ.to_string().parse::<SQLiteType>()?, *typ);
+            assert_eq!(typ.to_string().to_lowercase().parse::<SQLiteType>()?, *typ);
+            assert_eq!(format!("{:?}", typ).parse::<SQLiteType>()?, *typ);
+        }
+        for ord in Order::possibilities(false) {
+            assert_eq!(ord.to_string().parse::<Order>()?, *ord);
+            assert_eq!(format!("{:?}", ord).parse::<Order>()?, *ord);
+        }
+        for conf in OnConflict::possibilities(false) {
+            assert_eq!(conf.to_string().parse::<OnConflict>()?, *conf);
+            assert_eq!(format!("{:?}", conf).parse::<OnConflict>()?, *conf);
+        }
+        for action in FKOnAction::possibilities(false) {
+            assert_eq!(action.to_string().parse::<FKOnAction>()?, *action);
+            assert_eq!(format!("{:?}", action).parse::<FKOnAction>()?, *action);
+        }
+
+        assert_eq!(" Integer\t".parse::<SQLiteType>()?, SQLiteType::Integer);
+        assert_eq!("  desc ".parse::<Order>()?, Order::Descending);
+        assert_eq!("on conflict ignore".parse::<OnConflict>()?, OnConflict::Ignore);
+        assert_eq!("set_default".parse::<FKOnAction>()?, FKOnAction::SetDefault);
+
+        assert_eq!("int".parse::<SQLiteType>(), Err(Error::UnknownKeyword("int".to_string())));
+        assert_eq!("".parse::<Order>(), Err(Error::UnknownKeyword("".to_string())));
+        assert_eq!("ON CONFLICT".parse::<OnConflict>(), Err(Error::UnknownKeyword("ON CONFLICT".to_string())));
+        assert_eq!("set".parse::<FKOnAction>(), Err(Error::UnknownKeyword("set".to_string())));
+        Ok(())
+    }
+
+    #[test]
+    fn test_order() -> Result<()> {
+        let mut str: String;
+
+        str = String::new();
+        Order::Ascending.part_str(&mut str)?;
+        assert_eq!(str, "ASC");
+        assert_eq!(str.len(), Order::Ascending.part_len()?);
+
+        str = String::new();
+        Order::Descending.part_str(&mut str)?;
+        assert_eq!(str, "DESC");
+        assert_eq!(str.len(), Order::Descending.part_len()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_on_conflict() -> Result<()> {
+        let mut str: String;
+
+        str = String::new();
+        OnConflict::Rollback.part_str(&mut str)?;
+        assert_eq!(str, "ON CONFLICT ROLLBACK");
+        assert_eq!(str.len(), OnConflict::Rollback.part_len()?);
+
+        str = String::new();
+        OnConflict::Abort.part_str(&mut str)?;
+        assert_eq!(str, "ON CONFLICT ABORT");
+        assert_eq!(str.len(), OnConflict::Abort.part_len()?);
+
+        str = String::new();
+        OnConflict::Fail.part_str(&mut str)?;
+        assert_eq!(str, "ON CONFLICT FAIL");
+        assert_eq!(str.len(), OnConflict::Fail.part_len()?);
+
+        str = String::new();
+        OnConflict::Ignore.part_str(&mut str)?;
+        assert_eq!(str, "ON CONFLICT IGNORE");
+        assert_eq!(str.len(), OnConflict::Ignore.part_len()?);
+
+        str = String::new();
+        OnConflict::Replace.part_str(&mut str)?;
+        assert_eq!(str, "ON CONFLICT REPLACE");
+        assert_eq!(str.len(), OnConflict::Replace.part_len()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fk_on_action() -> Result<()> {
+        let mut str: String;
+
+        str = String::new();
+        FKOnAction::SetNull.part_str(&mut str)?;
+        assert_eq!(str, "SET NULL");
+        assert_eq!(str.len(), FKOnAction::SetNull.part_len()?);
+
+        str = String::new();
+        FKOnAction::SetDefault.part_str(&mut str)?;
+        assert_eq!(str, "SET DEFAULT");
+        assert_eq!(str.len(), FKOnAction::SetDefault.part_len()?);
+
+        str = String::new();
+        FKOnAction::Cascade.part_str(&mut str)?;
+        assert_eq!(str, "CASCADE");
+        assert_eq!(str.len(), FKOnAction::Cascade.part_len()?);
+
+        str = String::new();
+        FKOnAction::Restrict.part_str(&mut str)?;
+        assert_eq!(str, "RESTRICT");
+        assert_eq!(str.len(), FKOnAction::Restrict.part_len()?);
+
+        str = String::new();
+        FKOnAction::NoAction.part_str(&mut str)?;
+        assert_eq!(str, "NO ACTION");
+        assert_eq!(str.len(), FKOnAction::NoAction.part_len()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fk_deferrable() -> Result<()> {
+        let mut str: String;
+
+        str = String::new();
+        FKDeferrable::NotDeferrable.part_str(&mut str)?;
+        assert_eq!(str, "");
+        assert_eq!(str.len(), FKDeferrable::NotDeferrable.part_len()?);
+
+        str = String::new();
+        FKDeferrable::InitiallyImmediate.part_str(&mut str)?;
+        assert_eq!(str, "DEFERRABLE INITIALLY IMMEDIATE");
+        assert_eq!(str.len(), FKDeferrable::InitiallyImmediate.part_len()?);
+
+        str = String::new();
+        FKDeferrable::InitiallyDeferred.part_str(&mut str)?;
+        assert_eq!(str, "DEFERRABLE INITIALLY DEFERRED");
+        assert_eq!(str.len(), FKDeferrable::InitiallyDeferred.part_len()?);
+
+        assert_eq!(FKDeferrable::from(true), FKDeferrable::InitiallyDeferred);
+        assert_eq!(FKDeferrable::from(false), FKDeferrable::NotDeferrable);
+
+        str = String::new();
+        ForeignKey::new_default("test".to_string(), "test".to_string()).set_deferrable(FKDeferrable::InitiallyImmediate).part_str(&mut str)?;
+        assert_eq!(str, "REFERENCES test (test) DEFERRABLE INITIALLY IMMEDIATE");
+
+        str = String::new();
+        ForeignKey::new_default("test".to_string(), "test".to_string()).set_deferrable(false.into()).part_str(&mut str)?;
+        assert_eq!(str, "REFERENCES test (test)");
+
+        str = String::new();
+        let fk = ForeignKey::new_default("test".to_string(), "test".to_string()).set_on_delete(Some(FKOnAction::SetNull)).set_on_update(Some(FKOnAction::Cascade));
+        fk.part_str(&mut str)?;
+        assert_eq!(str, "REFERENCES test (test) ON DELETE SET NULL ON UPDATE CASCADE");
+        assert_eq!(str.len(), fk.part_len()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_not_null() -> Result<()> {
+        let mut str: String;
+
+        str = String::new();
+        NotNull::new(OnConflict::Rollback).part_str(&mut str)?;
+        assert_eq!(str, "NOT NULL ON CONFLICT ROLLBACK");
+        assert_eq!(str.len(), NotNull::new(OnConflict::Rollback).part_len()?);
+        assert_eq!(NotNull::new(OnConflict::Rollback).on_conflict(), OnConflict::Rollback);
+
+        str = String::new();
+        NotNull::new(OnConflict::Abort).part_str(&mut str)?;
+        assert_eq!(str, "NOT NULL ON CONFLICT ABORT");
+        assert_eq!(str.len(), NotNull::new(OnConflict::Abort).part_len()?);
+
+        str = String::new();
+        NotNull::new(OnConflict::Fail).part_str(&mut str)?;
+        assert_eq!(str, "NOT NULL ON CONFLICT FAIL");
+        assert_eq!(str.len(), NotNull::new(OnConflict::Fail).part_len()?);
+
+        str = String::new();
+        NotNull::new(OnConflict::Ignore).part_str(&mut str)?;
+        assert_eq!(str, "NOT NULL ON CONFLICT IGNORE");
+        assert_eq!(str.len(), NotNull::new(OnConflict::Ignore).part_len()?);
+
+        str = String::new();
+        NotNull::new(OnConflict::Replace).part_str(&mut str)?;
+        assert_eq!(str, "NOT NULL ON CONFLICT REPLACE");
+        assert_eq!(str.len(), NotNull::new(OnConflict::Replace).part_len()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unique() -> Result<()> {
+        let mut str: String;
+
+        str = String::new();
+        Unique::new(OnConflict::Rollback).part_str(&mut str)?;
+        assert_eq!(str, "UNIQUE ON CONFLICT ROLLBACK");
+        assert_eq!(str.len(), Unique::new(OnConflict::Rollback).part_len()?);
+        assert_eq!(Unique::new(OnConflict::Rollback).on_conflict(), OnConflict::Rollback);
+
+        str = String::new();
+        Unique::new(OnConflict::Abort).part_str(&mut str)?;
+        assert_eq!(str, "UNIQUE ON CONFLICT ABORT");
+        assert_eq!(str.len(), Unique::new(OnConflict::Abort).part_len()?);
+
+        str = String::new();
+        Unique::new(OnConflict::Fail).part_str(&mut str)?;
+        assert_eq!(str, "UNIQUE ON CONFLICT FAIL");
+        assert_eq!(str.len(), Unique::new(OnConflict::Fail).part_len()?);
+
+        str = String::new();
+        Unique::new(OnConflict::Ignore).part_str(&mut str)?;
+        assert_eq!(str, "UNIQUE ON CONFLICT IGNORE");
+        assert_eq!(str.len(), Unique::new(OnConflict::Ignore).part_len()?);
+
+        str = String::new();
+        Unique::new(OnConflict::Replace).part_str(&mut str)?;
+        assert_eq!(str, "UNIQUE ON CONFLICT REPLACE");
+        assert_eq!(str.len(), Unique::new(OnConflict::Replace).part_len()?);
+
+        Ok(())
+
+    }
+
+    #[test]
+    fn test_fk_references() -> Result<()> {
+        assert_eq!(ForeignKey::references("users", "id"), ForeignKey::new_default("users".to_string(), "id".to_string()));
+        assert_eq!(ForeignKey::references(String::from("users"), "id".to_string()).foreign_table(), "users");
+
+        let fk = ForeignKey::references("users", "id").with_on_delete(FKOnAction::Cascade).with_on_update(FKOnAction::SetNull);
+        assert_eq!(fk, ForeignKey::new_default("users".to_string(), "id".to_string()).set_on_delete(Some(FKOnAction::Cascade)).set_on_update(Some(FKOnAction::SetNull)));
+        assert_eq!(fk.on_delete(), Some(FKOnAction::Cascade));
+        assert_eq!(fk.on_update(), Some(FKOnAction::SetNull));
+        assert_eq!(fk.to_sql_part()?, "REFERENCES users (id) ON DELETE CASCADE ON UPDATE SET NULL");
+        Ok(())
+    }
+
+    #[test]
+    fn test_constraint_name() -> Result<()> {
+        assert_eq!(PrimaryKey::default().with_name("pk_id".to_string()).to_sql_part()?, "CONSTRAINT pk_id PRIMARY KEY ASC ON CONFLICT ABORT");
+        assert_eq!(NotNull::default().with_name("nn".to_string()).to_sql_part()?, "CONSTRAINT nn NOT NULL ON CONFLICT ABORT");
+        assert_eq!(Unique::default().with_name("uq".to_string()).to_sql_part()?, "CONSTRAINT uq UNIQUE ON CONFLICT ABORT");
+        assert_eq!(ForeignKey::new_default("a".to_string(), "b".to_string()).with_name("fk".to_string()).to_sql_part()?, "CONSTRAINT fk REFERENCES a (b)");
+        assert_eq!(Check::new("1".to_string()).with_name("chk".to_string()).to_sql_part()?, "CONSTRAINT chk CHECK(1)");
+        assert_eq!(Check::new("1".to_string()).with_name("chk".to_string()).name(), Some("chk"));
+        assert_eq!(Check::new("1".to_string()).with_name("chk".to_string()).set_name(None), Named::from(Check::new("1".to_string())));
+        assert_eq!(NotNull::default().with_name("my nn".to_string()).set_name_quote(Quote::DoubleQuote).to_sql_part()?, r#"CONSTRAINT "my nn" NOT NULL ON CONFLICT ABORT"#);
+
+        for pk in Named::<PrimaryKey>::possibilities(true) {
+            if pk.name() == Some("") {
+                assert_eq!(pk.part_len(), Err(Error::EmptyConstraintName));
+            } else {
+                test_sql_part(&*pk)?;
+            }
+        }
+        for nn in Named::<NotNull>::possibilities(true) {
+            if nn.name() == Some("") {
+                assert_eq!(nn.part_len(), Err(Error::EmptyConstraintName));
+            } else {
+                test_sql_part(&*nn)?;
+            }
+        }
+        for unique in Named::<Unique>::possibilities(true) {
+            if unique.name() == Some("") {
+                assert_eq!(unique.part_len(), Err(Error::EmptyConstraintName));
+            } else {
+                test_sql_part(&*unique)?;
+            }
+        }
+        assert_eq!(ForeignKey::new_default("a".to_string(), "b".to_string()).with_name("".to_string()).part_len(), Err(Error::EmptyConstraintName));
+        assert_eq!(Check::new("1".to_string()).with_name("".to_string()).part_len(), Err(Error::EmptyConstraintName));
+
+        let mut tbl = Table::new_default("test".to_string())
+            .add_column(Column::new_typed(SQLiteType::Integer, "id".to_string()).set_pk_named(Some(PrimaryKey::default().with_name("pk_id".to_string()))))
+            .add_column(Column::new_typed(SQLiteType::Integer, "a".to_string())
+                .set_unique_named(Some(Unique::default().with_name("uq_a".to_string())))
+                .set_not_null_named(Some(NotNull::default().with_name("nn_a".to_string())))
+                .set_check_named(Some(Check::new("a > 0".to_string()).with_name("chk_a".to_string())))
+                .set_fk_named(Some(ForeignKey::new_default("test".to_string(), "id".to_string()).with_name("fk_a".to_string()))));
+        test_sql(&mut tbl)?;
+
+        let schema = Schema::new().add_table(Table::new_default("test".to_string())
+            .add_column(Column::new_typed(SQLiteType::Integer, "id".to_string()).set_pk_named(Some(PrimaryKey::default().with_name("pk id".to_string()))))
+            .add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()).set_unique_named(Some(Unique::default().with_name("uq_a".to_string())))))
+            .auto_quote();
+        assert_eq!(schema.tables()[0].columns()[0].primary_key_named().map(Named::name_quote), Some(Quote::DoubleQuote));
+        assert_eq!(schema.tables()[0].columns()[1].unique_named().map(Named::name_quote), Some(Quote::None));
+        Ok(())
+    }
+
+    #[test]
+    fn test_column_as_primary_key() -> Result<()> {
+        let col = Column::new_default("id".to_string());
+        assert_eq!(
+            col.clone().as_primary_key(Order::Descending, OnConflict::Replace, false),
+            col.clone().set_pk(Some(PrimaryKey::new(Order::Descending, OnConflict::Replace, false)))
+        );
+        assert_eq!(col.clone().as_integer_pk().to_sql_part()?, "id INTEGER PRIMARY KEY ASC ON CONFLICT ABORT");
+        assert_eq!(col.clone().as_autoincrement().to_sql_part()?, "id INTEGER PRIMARY KEY ASC ON CONFLICT ABORT AUTOINCREMENT");
+        assert_eq!(col.as_integer_pk().set_not_null(Some(NotNull::default())).to_sql_part()?, "id INTEGER PRIMARY KEY ASC ON CONFLICT ABORT NOT NULL ON CONFLICT ABORT");
+
+        let mut tbl = Table::new_default("test".to_string()).add_column(Column::new_default("id".to_string()).as_autoincrement());
+        test_sql(&mut tbl)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_column_nullable_indexed() -> Result<()> {
+        let col = Column::new_typed(SQLiteType::Integer, "test".to_string());
+        assert!(col.is_nullable());
+        assert!(!col.is_required());
+        assert!(!col.is_indexed());
+
+        let not_null = col.clone().set_not_null(Some(NotNull::default()));
+        assert!(!not_null.is_nullable());
+        assert!(not_null.is_required());
+        assert!(!not_null.is_indexed());
+
+        let pk = col.clone().set_pk(Some(PrimaryKey::default()));
+        assert!(!pk.is_nullable());
+        assert!(pk.is_required());
+        assert!(pk.is_indexed());
+
+        assert!(col.clone().set_unique(Some(Unique::default())).is_indexed());
+        assert!(col.clone().set_fk(Some(ForeignKey::new_default("other".to_string(), "id".to_string()))).is_indexed());
+        assert!(col.set_fk(Some(ForeignKey::new_default("other".to_string(), "id".to_string()))).is_nullable());
+        Ok(())
+    }
+
+    #[test]
+    fn test_check() -> Result<()> {
+        let mut str: String = String::new();
+        Check::new("a > 0".to_string()).part_str(&mut str)?;
+        assert_eq!(str, "CHECK(a > 0)");
+        assert_eq!(str.len(), Check::new("a > 0".to_string()).part_len()?);
+
+        assert_eq!(Check::new("".to_string()).part_len(), Err(Error::EmptyCheckExpr));
+        assert_eq!(Column::new_default("test".to_string()).set_check(Some(Check::new("".to_string()))).part_len(), Err(Error::EmptyCheckExpr));
+
+        for check in Check::possibilities(false) {
+            let mut tbl = Table::new_default("test".to_string())
+                .add_column(Column::new_typed(SQLiteType::Integer, "testcol".to_string()).set_check(Some(*check)));
+            test_sql_part(&tbl)?;
+            test_sql(&mut tbl)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_default_value() -> Result<()> {
+        for possible in DefaultValue::possibilities(false) {
+            test_sql_part(&*possible)?;
+        }
+
+        let mut str: String;
+
+        str = String::new();
+        DefaultValue::Null.part_str(&mut str)?;
+        assert_eq!(str, "DEFAULT NULL");
+
+        str = String::new();
+        DefaultValue::Integer(-42).part_str(&mut str)?;
+        assert_eq!(str, "DEFAULT -42");
+
+        str = String::new();
+        DefaultValue::Real(1.0).part_str(&mut str)?;
+        assert_eq!(str, "DEFAULT 1.0");
+
+        str = String::new();
+        DefaultValue::Text("it's".to_string()).part_str(&mut str)?;
+        assert_eq!(str, "DEFAULT 'it''s'");
+
+        str = String::new();
+        DefaultValue::CurrentTimestamp.part_str(&mut str)?;
+        assert_eq!(str, "DEFAULT CURRENT_TIMESTAMP");
+
+        str = String::new();
+        DefaultValue::Expr("1 + 2".to_string()).part_str(&mut str)?;
+        assert_eq!(str, "DEFAULT (1 + 2)");
+
+        for default in DefaultValue::possibilities(true) {
+            if matches!(*default, DefaultValue::Real(real) if !real.is_finite()) {
+                assert_eq!(default.part_len(), Err(Error::NonFiniteDefault));
+                assert_eq!(default.to_sql_part(), Err(Error::NonFiniteDefault));
+            } else {
+                test_sql_part(&*default)?;
+            }
+        }
+        assert_eq!(Column::new_default("test".to_string()).set_default(Some(DefaultValue::Real(f64::INFINITY))).part_len(), Err(Error::NonFiniteDefault));
+
+        assert_eq!(DefaultValue::Real(f64::NAN), DefaultValue::Real(f64::NAN));
+        assert_ne!(DefaultValue::Real(0.0), DefaultValue::Integer(0));
+        assert_ne!(DefaultValue::CurrentDate, DefaultValue::CurrentTime);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_collation() -> Result<()> {
+        let mut str: String;
+
+        str = String::new();
+        Collation::Binary.part_str(&mut str)?;
+        assert_eq!(str, "COLLATE BINARY");
+        assert_eq!(str.len(), Collation::Binary.part_len()?);
+
+        str = String::new();
+        Collation::NoCase.part_str(&mut str)?;
+        assert_eq!(str, "COLLATE NOCASE");
+        assert_eq!(str.len(), Collation::NoCase.part_len()?);
+
+        str = String::new();
+        Collation::RTrim.part_str(&mut str)?;
+        assert_eq!(str, "COLLATE RTRIM");
+        assert_eq!(str.len(), Collation::RTrim.part_len()?);
+
+        str = String::new();
+        Collation::Custom("test".to_string()).part_str(&mut str)?;
+        assert_eq!(str, "COLLATE test");
+        assert_eq!(str.len(), Collation::Custom("test".to_string()).part_len()?);
+
+        assert_eq!(Collation::Custom("".to_string()).part_len(), Err(Error::EmptyCollationName));
+        assert_eq!(Column::new_default("test".to_string()).set_collation(Some(Collation::Custom("".to_string()))).part_len(), Err(Error::EmptyCollationName));
+
+        str = String::new();
+        Column::new_typed(SQLiteType::Text, "test".to_string()).set_collation(Some(Collation::NoCase)).part_str(&mut str)?;
+        assert!(str.ends_with(" COLLATE NOCASE"));
+
+        for collation in Collation::possibilities(false) {
+            let mut tbl = Table::new_default("test".to_string())
+                .add_column(Column::new_typed(SQLiteType::Text, "testcol".to_string()).set_default(Some(DefaultValue::Text("test".to_string()))).set_collation(Some(*collation)));
+            test_sql_part(&tbl)?;
+            test_sql(&mut tbl)?;
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_primary_key() -> Result<()> {
+        for so in [Order::Ascending, Order::Descending] {
+            for conf in [OnConflict::Rollback, OnConflict::Abort, OnConflict::Fail, OnConflict::Ignore, OnConflict::Replace] {
+                for autoinc in [true, false] {
+                    let pk = PrimaryKey::new(so, conf, autoinc);
+                    assert_eq!((pk.sort_order(), pk.on_conflict(), pk.is_autoincrement()), (so, conf, autoinc));
+                    test_sql_part(&pk)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_foreign_key() -> Result<()> {
+        for defer in [FKDeferrable::NotDeferrable, FKDeferrable::InitiallyImmediate, FKDeferrable::InitiallyDeferred] {
+            for on_del in [None, Some(FKOnAction::SetNull), Some(FKOnAction::SetDefault), Some(FKOnAction::Cascade), Some(FKOnAction::Restrict), Some(FKOnAction::NoAction)] {
+                for on_upd in [None, Some(FKOnAction::SetNull), Some(FKOnAction::SetDefault), Some(FKOnAction::Cascade), Some(FKOnAction::Restrict), Some(FKOnAction::NoAction)] {
+                    // todo: test string params
+                    assert_eq!(ForeignKey::new("".to_string(), "test".to_string(), on_del, on_upd, defer).part_len(), Err(Error::EmptyForeignTableName));
+                    assert_eq!(ForeignKey::new("test".to_string(), "".to_string(), on_del, on_upd, defer).part_len(), Err(Error::EmptyForeignColumnName));
+
+                    let fk = ForeignKey::new("parent".to_string(), "id".to_string(), on_del, on_upd, defer);
+                    assert_eq!((fk.foreign_table(), fk.foreign_column()), ("parent", "id"));
+                    assert_eq!((fk.on_delete(), fk.on_update(), fk.deferrable()), (on_del, on_upd, defer));
+                    assert_eq!(fk.is_deferrable(), defer != FKDeferrable::NotDeferrable);
+                    test_sql_part(&fk)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_column_autoincrement() -> Result<()> {
+        assert_eq!(Column::new(SQLiteType::Text, "id".to_string(), Some(PrimaryKey::new(Order::Ascending, OnConflict::Abort, true)), None, None, None).part_len(), Err(Error::AutoincrementRequiresInteger));
+        assert_eq!(Column::new(SQLiteType::Integer, "id".to_string(), Some(PrimaryKey::new(Order::Descending, OnConflict::Abort, true)), None, None, None).part_len(), Err(Error::AutoincrementRequiresInteger));
+        // checked before the Primary Key / Foreign Key exclusion
+        assert_eq!(Column::new(SQLiteType::Blob, "id".to_string(), Some(PrimaryKey::new(Order::Ascending, OnConflict::Abort, true)), None, Some(ForeignKey::new_default("test".to_string(), "test".to_string())), None).part_len(), Err(Error::AutoincrementRequiresInteger));
+
+        let mut tbl = Table::new_default("test".to_string())
+            .add_column(Column::new(SQLiteType::Integer, "id".to_string(), Some(PrimaryKey::new(Order::Ascending, OnConflict::Abort, true)), None, None, None));
+        test_sql_part(&tbl)?;
+        test_sql(&mut tbl)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_table_autoincrement_without_rowid() -> Result<()> {
+        let mut tbl = Table::new_default("test".to_string())
+            .add_column(Column::new(SQLiteType::Integer, "id".to_string(), Some(PrimaryKey::new(Order::Ascending, OnConflict::Abort, true)), None, None, None))
+            .set_without_rowid(true);
+        assert_eq!(tbl.part_len(), Err(Error::AutoincrementOnWithoutRowidTable));
+        assert_eq!(tbl.build(false, false), Err(Error::AutoincrementOnWithoutRowidTable));
+
+        tbl = tbl.set_without_rowid(false);
+        test_sql(&mut tbl)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_column() -> Result<()> {
+        for typ in [SQLiteType::Blob, SQLiteType::Numeric, SQLiteType::Integer, SQLiteType::Real, SQLiteType::Text] {
+            for pk in [None, Some(PrimaryKey::default())] {
+                for uniq in [None, Some(Unique::default())] {
+                    for fk in [None, Some(ForeignKey::new_default("test".to_string(), "test".to_string()))] {
+                        for nn in [None, Some(NotNull::default())] {
+                            assert_eq!(Column::new(typ, "".to_string(),Clone::clone(&pk), Clone::clone(&uniq), Clone::clone(&fk), Clone::clone(&nn)).part_len(), Err(Error::EmptyColumnName));
+
+                            let col: Column = Column::new(typ, "test".to_string(), Clone::clone(&pk), Clone::clone(&uniq), Clone::clone(&fk), Clone::clone(&nn));
+
+                            if col.pk.is_some() && col.fk.is_some() {
+                                assert_eq!(col.part_len(), Err(Error::PrimaryKeyAndForeignKey));
+                            } else if col.pk.is_some() && col.unique.is_some() {
+                                assert_eq!(col.part_len(), Err(Error::PrimaryKeyAndUnique));
+                            } else {
+                                test_sql_part(&col)?;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let col: Column = Column::new_typed(SQLiteType::Integer, "x".to_string()).set_not_null(Some(NotNull::default()));
+        assert_eq!(col, Column::new(SQLiteType::Integer, "x".to_string(), None, None, None, Some(NotNull::default())));
+        let mut str: String = String::new();
+        col.part_str(&mut str)?;
+        assert_eq!(str, "x INTEGER NOT NULL ON CONFLICT ABORT");
+        assert_eq!(str.len(), col.part_len()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_table() -> Result<()> {
+        'poss: for mut possible in Table::possibilities(false).into_iter().map(|boxed| *boxed) {
+            let mut has_pk: bool = false;
+
+            for col in &possible.columns {
+                if col.pk.is_some() && col.unique.is_some() {
+                    assert_eq!(col.part_len(), Err(Error::PrimaryKeyAndUnique));
+                    continue 'poss;
+                }
+                if col.pk.is_some() && col.fk.is_some() {
+                    assert_eq!(col.part_len(), Err(Error::PrimaryKeyAndForeignKey));
+                    continue 'poss;
+                }
+                if col.pk.is_some() {
+                    has_pk = true;
+                }
+            }
+            if !possible.without_rowid && has_pk {
+                assert_eq!(possible.part_len(), Err(Error::WithoutRowidNoPrimaryKey));
+                continue;
+            }
+
+            if possible.name.is_empty() {
+                assert_eq!(possible.part_len(), Err(Error::EmptyTableName));
+                continue;
+            }
+
+            if possible.columns.is_empty() {
+                assert_eq!(possible.part_len(), Err(Error::NoColumns));
+                continue;
+            }
+
+            test_sql_part(&possible)?;
+            test_sql(&mut possible)?; // FUCK
+        }
+        Ok(())
+    }
+
+    fn hash_of<H: Hash>(value: &H) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn test_hash() -> Result<()> {
+        for default in DefaultValue::possibilities(false) {
+            assert_eq!(hash_of(&*default), hash_of(&default.clone()));
+        }
+        assert_eq!(hash_of(&DefaultValue::Real(f64::NAN)), hash_of(&DefaultValue::Real(f64::NAN)));
+        assert_ne!(hash_of(&DefaultValue::Text("a".to_string())), hash_of(&DefaultValue::Expr("a".to_string())));
+
+        let mut tbl = Table::new_default("test".to_string())
+            .add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()).set_default(Some(DefaultValue::Real(1.5))))
+            .add_unique_constraint(vec!["a".to_string()], OnConflict::Abort);
+        let mut other = tbl.clone();
+        // `if_exists` is not part of equality, so it must not be part of the hash either
+        tbl.len(false, true)?;
+        other.len(false, false)?;
+        assert_eq!(tbl, other);
+        assert_eq!(hash_of(&tbl), hash_of(&other));
+        assert_ne!(hash_of(&tbl), hash_of(&other.clone().set_strict(true)));
+
+        let mut idx = Index::new_default("idx".to_string(), "test".to_string()).add_column("a".to_string(), Order::Ascending);
+        let mut other_idx = idx.clone();
+        idx.len(false, true)?;
+        other_idx.len(false, false)?;
+        assert_eq!(hash_of(&idx), hash_of(&other_idx));
+
+        let schema = Schema::new().add_table(tbl).add_index(idx);
+        assert_eq!(hash_of(&schema), hash_of(&schema.clone()));
+
+        let set: std::collections::HashSet<Schema> = [schema.clone(), schema.clone(), Schema::new()].into_iter().collect();
+        assert_eq!(set.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_getters() -> Result<()> {
+        let col = Column::new(SQLiteType::Integer, "test".to_string(), Some(PrimaryKey::default()), None, None, Some(NotNull::default()))
+            .set_check(Some(Check::new("test > 0".to_string())))
+            .set_default(Some(DefaultValue::Integer(1)))
+            .set_collation(Some(Collation::Binary));
+        assert_eq!(col.name(), "test");
+        assert_eq!(col.col_type(), SQLiteType::Integer);
+        assert_eq!(col.primary_key(), Some(&PrimaryKey::default()));
+        assert_eq!(col.unique(), None);
+        assert_eq!(col.foreign_key(), None);
+        assert_eq!(col.not_null(), Some(&NotNull::default()));
+        assert_eq!(col.check_constraint(), Some(&Check::new("test > 0".to_string())));
+        assert_eq!(col.default_value(), Some(&DefaultValue::Integer(1)));
+        assert_eq!(col.collation(), Some(&Collation::Binary));
+
+        let tbl = Table::new("tbl".to_string(), vec![col.clone()], true, false)
+            .add_unique_constraint(vec!["test".to_string()], OnConflict::Abort);
+        assert_eq!(tbl.name(), "tbl");
+        assert_eq!(tbl.columns(), &[col]);
+        assert_eq!(tbl.constraints(), &[TableConstraint::CompositeUnique(vec!["test".to_string()], OnConflict::Abort)]);
+        assert!(tbl.is_without_rowid());
+        assert!(!tbl.is_strict());
+
+        let idx = Index::new_default("idx".to_string(), "tbl".to_string()).add_column("test".to_string(), Order::Ascending);
+        let schema = Schema::new().add_table(tbl.clone()).add_index(idx.clone());
+        assert_eq!(schema.tables(), &[tbl]);
+        assert_eq!(schema.indexes(), std::slice::from_ref(&idx));
+        assert_eq!(schema.indexes_iter().map(Index::name).collect::<Vec<&str>>(), vec!["idx"]);
+        assert_eq!((idx.name(), idx.table(), idx.is_unique(), idx.where_expr()), ("idx", "tbl", false, None));
+
+        let mut schema = schema;
+        assert_eq!(schema.remove_index("missing"), None);
+        assert_eq!(schema.remove_index("idx"), Some(idx));
+        assert!(schema.indexes().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_table_constraint() -> Result<()> {
+        for possible in TableConstraint::possibilities(false) {
+            test_sql_part(&*possible)?;
+        }
+
+        for possible in TableConstraint::possibilities(true) {
+            match &*possible {
+                TableConstraint::CompositePrimaryKey(cols, _) | TableConstraint::CompositeUnique(cols, _) if cols.is_empty() => {
+                    assert_eq!(possible.part_len(), Err(Error::EmptyConstraintColumns));
+                }
+                TableConstraint::CompositeForeignKey(fk) if fk.local_columns().is_empty() => {
+                    assert_eq!(possible.part_len(), Err(Error::EmptyConstraintColumns));
+                }
+                TableConstraint::CompositeForeignKey(fk) if fk.foreign_table().is_empty() => {
+                    assert_eq!(possible.part_len(), Err(Error::EmptyForeignTableName));
+                }
+                TableConstraint::CompositeForeignKey(fk) if fk.local_columns().len() != fk.foreign_columns().len() => {
+                    assert_eq!(possible.part_len(), Err(Error::ForeignKeyColumnCountMismatch));
+                }
+                _ => test_sql_part(&*possible)?,
+            }
+        }
+
+        let mut str: String = String::new();
+        TableConstraint::CompositePrimaryKey(vec!["a".to_string(), "b".to_string()], OnConflict::Abort).part_str(&mut str)?;
+        assert_eq!(str, "PRIMARY KEY (a, b) ON CONFLICT ABORT");
+
+        str = String::new();
+        TableConstraint::CompositeUnique(vec!["a".to_string(), "b".to_string()], OnConflict::Rollback).part_str(&mut str)?;
+        assert_eq!(str, "UNIQUE (a, b) ON CONFLICT ROLLBACK");
+
+        str = String::new();
+        TableConstraint::CompositeForeignKey(
+            CompositeForeignKey::new_default(vec!["a".to_string(), "b".to_string()], "other".to_string(), vec!["x".to_string(), "y".to_string()])
+                .set_on_delete(Some(FKOnAction::Cascade))
+                .set_deferrable(FKDeferrable::InitiallyDeferred)
+        ).part_str(&mut str)?;
+        assert_eq!(str, "FOREIGN KEY (a, b) REFERENCES other (x, y) ON DELETE CASCADE DEFERRABLE INITIALLY DEFERRED");
+
+        let fk = CompositeForeignKey::new_default(vec!["a".to_string()], "other".to_string(), Vec::new());
+        assert_eq!(fk.part_len(), Err(Error::EmptyForeignColumnName));
+
+        for wo_rowid in [true, false] {
+            for strict in [true, false] {
+                let mut tbl = Table::new_default("test".to_string())
+                    .add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()))
+                    .add_column(Column::new_typed(SQLiteType::Text, "b".to_string()))
+                    .add_constraint(TableConstraint::CompositePrimaryKey(vec!["a".to_string(), "b".to_string()], OnConflict::Abort))
+                    .add_unique_constraint(vec!["b".to_string(), "a".to_string()], OnConflict::Rollback)
+                    .set_without_rowid(wo_rowid)
+                    .set_strict(strict);
+                test_sql_part(&tbl)?;
+                test_sql(&mut tbl)?;
+            }
+        }
+
+        let tbl = Table::new_default("test".to_string())
+            .add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()).set_pk(Some(PrimaryKey::default())))
+            .add_column(Column::new_typed(SQLiteType::Text, "b".to_string()))
+            .add_constraint(TableConstraint::CompositePrimaryKey(vec!["a".to_string(), "b".to_string()], OnConflict::Abort));
+        assert_eq!(tbl.part_len(), Err(Error::MultiplePrimaryKeys));
+
+        let tbl = Table::new_default("test".to_string())
+            .add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()))
+            .add_unique_constraint(Vec::new(), OnConflict::Abort);
+        assert_eq!(tbl.part_len(), Err(Error::EmptyConstraintColumns));
+        Ok(())
+    }
+
+    #[test]
+    fn test_index() -> Result<()> {
+        for possible in Index::possibilities(true) {
+            if possible.name.is_empty() {
+                assert_eq!(possible.part_len(), Err(Error::EmptyIndexName));
+            } else if possible.table.is_empty() {
+                assert_eq!(possible.part_len(), Err(Error::EmptyIndexTable));
+            } else if possible.columns.is_empty() {
+                assert_eq!(possible.part_len(), Err(Error::EmptyIndexColumns));
+            } else {
+                test_sql_part(&*possible)?;
+            }
+        }
+
+        let mut str: String = String::new();
+        Index::new_default("idx".to_string(), "tbl".to_string())
+            .add_column("a".to_string(), Order::Ascending)
+            .add_column("b".to_string(), Order::Descending)
+            .set_unique(true)
+            .set_where_expr(Some("a > 0".to_string()))
+            .part_str(&mut str)?;
+        assert_eq!(str, "CREATE UNIQUE INDEX idx ON tbl (a ASC, b DESC) WHERE a > 0");
+
+        let idx = Index::new_default("idx".to_string(), "tbl".to_string())
+            .add_collated_column("a".to_string(), Order::Ascending, Collation::NoCase)
+            .add_column("b".to_string(), Order::Descending);
+        assert_eq!(render_part(&idx)?, "CREATE INDEX idx ON tbl (a COLLATE NOCASE ASC, b DESC)");
+        assert_eq!(idx.columns()[0], IndexColumn::new_collated("a".to_string(), Order::Ascending, Collation::NoCase));
+        assert_eq!((idx.columns()[1].name(), idx.columns()[1].order(), idx.columns()[1].collation()), ("b", Order::Descending, None));
+        assert_eq!(Index::new_default("idx".to_string(), "tbl".to_string()).add_column("".to_string(), Order::Ascending).part_len(), Err(Error::EmptyColumnName));
+        assert_eq!(Index::new_default("idx".to_string(), "tbl".to_string()).add_column("a".to_string(), Order::Ascending).set_where("".to_string()).part_len(), Err(Error::EmptyIndexWhereClause));
+
+        for possible in IndexColumn::possibilities(true) {
+            if possible.name.is_empty() {
+                assert_eq!(possible.part_len(), Err(Error::EmptyColumnName));
+            } else {
+                test_sql_part(&*possible)?;
+            }
+        }
+
+        for possible in Index::possibilities(false) {
+            let mut tbl = Table::new_default("test".to_string());
+            for n in 0..3 {
+                tbl = tbl.add_column(Column::new_default(format!("test{}", n)));
+            }
+            let mut schema = Schema::new().add_table(tbl).add_index(*possible);
+            test_sql(&mut schema)?;
+        }
+
+        let mut schema = Schema::new()
+            .add_table(Table::new_default("tbl".to_string()).add_column(Column::new_typed(SQLiteType::Text, "a".to_string())))
+            .add_index(Index::new_default("idx".to_string(), "tbl".to_string()).add_collated_column("a".to_string(), Order::Descending, Collation::RTrim));
+        test_sql(&mut schema)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_schema() -> Result<()> {
+        {
+            let mut schema: Schema = Schema::new();
+            assert_eq!(schema.len(false, false), Err(Error::SchemaWithoutTables));
+        }
+        for num_tbl in 1..3 {
+            let mut schema: Schema = Schema::new();
+            for tbl_idx in 0..num_tbl {
+                let mut tbl = Table::new_default(format!("table{}", tbl_idx));
+                tbl = tbl.add_column(Column::new_default("testcol".to_string()));
+                schema = schema.add_table(tbl);
+            }
+            test_sql(&mut schema)?;
+        }
+
+        for default in DefaultValue::possibilities(false) {
+            let mut tbl = Table::new_default("test".to_string())
+                .add_column(Column::new_default("testcol".to_string()).set_default(Some(*default)));
+            test_sql(&mut tbl)?;
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_table_temp() -> Result<()> {
+        let mut tbl = Table::new_default("test".to_string()).add_column(Column::new_default("testcol".to_string())).set_temp(true);
+        assert!(tbl.is_temp());
+        assert_eq!(tbl.build(false, false)?, "CREATE TEMPORARY TABLE test (testcol BLOB);");
+        assert_eq!(tbl.build(false, true)?, "CREATE TEMPORARY TABLE IF NOT EXISTS test (testcol BLOB);");
+        test_sql_part(&tbl)?;
+        test_sql(&mut tbl)?;
+        assert_ne!(tbl, tbl.clone().set_temp(false));
+        Ok(())
+    }
+
+    #[test]
+    fn test_table_get_remove_column() -> Result<()> {
+        let col_a = Column::new_typed(SQLiteType::Integer, "a".to_string());
+        let col_b = Column::new_typed(SQLiteType::Text, "b".to_string());
+        let mut tbl = Table::new_default("test".to_string()).add_column(col_a.clone()).add_column(col_b.clone());
+        assert_eq!(tbl.column_count(), 2);
+
+        assert_eq!(tbl.get_column("a"), Some(&col_a));
+        assert_eq!(tbl.get_column("missing"), None);
+        assert!(tbl.get_column_mut("missing").is_none());
+
+        if let Some(col) = tbl.get_column_mut("b") {
+            *col = col.clone().set_type(SQLiteType::Blob);
+        }
+        assert_eq!(tbl.get_column("b"), Some(&col_b.clone().set_type(SQLiteType::Blob)));
+
+        assert_eq!(tbl.remove_column("missing"), None);
+        assert_eq!(tbl.remove_column("a"), Some(col_a.clone()));
+        assert_eq!(tbl.remove_column("a"), None);
+        assert_eq!(tbl.column_count(), 1);
+        assert_eq!(tbl.build(false, false)?, "CREATE TABLE test (b BLOB);");
+
+        let mut tbl = tbl.insert_column(0, col_a).insert_column(2, Column::new_typed(SQLiteType::Real, "c".to_string()));
+        assert_eq!(tbl.build(false, false)?, "CREATE TABLE test (a INTEGER,b BLOB,c REAL);");
+        tbl = tbl.replace_column(col_b.clone())?;
+        assert_eq!(tbl.build(false, false)?, "CREATE TABLE test (a INTEGER,b TEXT,c REAL);");
+        assert_eq!(tbl.replace_column(Column::new_default("missing".to_string())), Err(Error::ColumnNotFound("missing".to_string())));
+        Ok(())
+    }
+
+    #[test]
+    fn test_column_at() -> Result<()> {
+        let mut tbl = Table::new_default("test".to_string());
+        assert_eq!(tbl.column_at(0), None);
+        assert_eq!(tbl.column_at_mut(0), None);
+        assert_eq!(tbl.first_column(), None);
+        assert_eq!(tbl.last_column(), None);
+
+        let col_a = Column::new_typed(SQLiteType::Integer, "a".to_string());
+        let col_b = Column::new_typed(SQLiteType::Text, "b".to_string());
+        tbl = tbl.add_column(col_a.clone());
+        assert_eq!(tbl.first_column(), Some(&col_a));
+        assert_eq!(tbl.last_column(), Some(&col_a));
+
+        tbl = tbl.add_column(col_b.clone());
+        assert_eq!(tbl.column_at(0), Some(&col_a));
+        assert_eq!(tbl.column_at(1), Some(&col_b));
+        assert_eq!(tbl.column_at(2), None);
+        assert_eq!(tbl.column_at(usize::MAX), None);
+        assert_eq!(tbl.first_column(), Some(&col_a));
+        assert_eq!(tbl.last_column(), Some(&col_b));
+
+        if let Some(col) = tbl.column_at_mut(1) {
+            *col = col.clone().set_type(SQLiteType::Blob);
+        }
+        assert_eq!(tbl.column_at(1), Some(&col_b.set_type(SQLiteType::Blob)));
+        assert_eq!(tbl.column_at_mut(2), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_column_position() -> Result<()> {
+        let col = |name: &str| Column::new_typed(SQLiteType::Integer, name.to_string());
+        let mut tbl = Table::new_default("test".to_string()).add_column(col("a")).add_column(col("c"));
+
+        tbl = tbl.add_column_after(col("b"), "a")?.add_column_after(col("d"), "c")?;
+        assert_eq!(tbl.build(false, false)?, "CREATE TABLE test (a INTEGER,b INTEGER,c INTEGER,d INTEGER);");
+        tbl = tbl.add_column_before(col("x"), "a")?.add_column_before(col("y"), "d")?;
+        assert_eq!(tbl.build(false, false)?, "CREATE TABLE test (x INTEGER,a INTEGER,b INTEGER,c INTEGER,y INTEGER,d INTEGER);");
+        tbl = tbl.add_column_at(col("first"), 0).add_column_at(col("last"), 100).add_column_at(col("mid"), 4);
+        assert_eq!(tbl.columns().iter().map(Column::name).collect::<Vec<&str>>(), vec!["first", "x", "a", "b", "mid", "c", "y", "d", "last"]);
+
+        assert_eq!(tbl.clone().add_column_after(col("z"), "missing"), Err(Error::ColumnNotFound("missing".to_string())));
+        assert_eq!(tbl.add_column_before(col("z"), "missing"), Err(Error::ColumnNotFound("missing".to_string())));
+        Ok(())
+    }
+
+    #[test]
+    fn test_drop() -> Result<()> {
+        for possible in DropTable::possibilities(true) {
+            if possible.name.is_empty() {
+                assert_eq!(possible.part_len(), Err(Error::EmptyTableName));
+            } else {
+                test_sql_part(&*possible)?;
+            }
+        }
+        for possible in DropIndex::possibilities(true) {
+            if possible.name.is_empty() {
+                assert_eq!(possible.part_len(), Err(Error::EmptyIndexName));
+            } else {
+                test_sql_part(&*possible)?;
+            }
+        }
+
+        let mut drop_tbl = DropTable::new("tbl".to_string());
+        assert_eq!(drop_tbl.build(false, false)?, "DROP TABLE tbl;");
+        assert_eq!(drop_tbl.build(true, true)?, "BEGIN;\nDROP TABLE IF EXISTS tbl;\nEND;");
+        assert_eq!(drop_tbl.len(true, true)?, drop_tbl.build(true, true)?.len());
+        let mut drop_idx = DropIndex::new("idx".to_string());
+        assert_eq!(drop_idx.build(false, false)?, "DROP INDEX idx;");
+        assert_eq!(drop_idx.build(false, true)?, "DROP INDEX IF EXISTS idx;");
+        assert_eq!(drop_idx.len(false, true)?, drop_idx.build(false, true)?.len());
+
+        let mut drop_tbl = DropTable::new("my tbl".to_string()).set_quote(Quote::DoubleQuote).set_schema(Some("aux".to_string()));
+        assert_eq!(drop_tbl.build(false, true)?, "DROP TABLE IF EXISTS aux.\"my tbl\";");
+        assert_eq!(drop_tbl.len(false, true)?, drop_tbl.build(false, true)?.len());
+        assert_ne!(drop_tbl, DropTable::new("my tbl".to_string()));
+        let mut drop_idx = DropIndex::new("idx".to_string()).set_schema(Some("my db".to_string()));
+        assert_eq!(drop_idx.build(false, false)?, "DROP INDEX \"my db\".idx;");
+        assert_eq!(drop_idx.len(false, false)?, drop_idx.build(false, false)?.len());
+        Ok(())
+    }
+
+    #[test]
+    fn test_trigger() -> Result<()> {
+        for event in TriggerEvent::possibilities(false) {
+            test_sql_part(&*event)?;
+            assert_eq!(event.to_string().parse::<TriggerEvent>()?, *event);
+        }
+        assert_eq!("Bogus".parse::<TriggerEvent>(), Err(Error::UnknownKeyword("Bogus".to_string())));
+
+        for possible in Trigger::possibilities(true) {
+            if possible.name.is_empty() {
+                assert_eq!(possible.part_len(), Err(Error::EmptyTriggerName));
+            } else if possible.table_name.is_empty() {
+                assert_eq!(possible.part_len(), Err(Error::EmptyTableName));
+            } else if possible.body.is_empty() {
+                assert_eq!(possible.part_len(), Err(Error::EmptyTriggerBody));
+            } else {
+                test_sql_part(&*possible)?;
+            }
+        }
+
+        let mut trigger = Trigger::new("ins".to_string(), TriggerEvent::Insert, "view".to_string(), "INSERT INTO test (a) VALUES (NEW.a)".to_string());
+        assert_eq!(trigger.build(false, false)?, "CREATE TRIGGER ins INSTEAD OF INSERT ON view BEGIN INSERT INTO test (a) VALUES (NEW.a); END;");
+        assert_eq!(trigger.build(false, true)?, "CREATE TRIGGER IF NOT EXISTS ins INSTEAD OF INSERT ON view BEGIN INSERT INTO test (a) VALUES (NEW.a); END;");
+        assert_eq!(trigger.len(true, true)?, trigger.build(true, true)?.len());
+
+        let mut quoted = Trigger::new("my trigger".to_string(), TriggerEvent::Delete, "order".to_string(), "SELECT 1".to_string()).set_quote(Quote::DoubleQuote);
+        assert_eq!(quoted.build(false, false)?, r#"CREATE TRIGGER "my trigger" INSTEAD OF DELETE ON "order" BEGIN SELECT 1; END;"#);
+        assert_eq!(quoted.len(false, true)?, quoted.build(false, true)?.len());
+        Ok(())
+    }
+
+    #[test]
+    fn test_alter_table_rename_column() -> Result<()> {
+        for possible in AlterTableRenameColumn::possibilities(true) {
+            if possible.table.is_empty() {
+                assert_eq!(possible.part_len(), Err(Error::EmptyTableName));
+            } else if possible.old_column.is_empty() || possible.new_column.is_empty() {
+                assert_eq!(possible.part_len(), Err(Error::EmptyColumnName));
+            } else {
+                test_sql_part(&*possible)?;
+            }
+        }
+
+        let mut rename = AlterTableRenameColumn::new("test".to_string(), "old".to_string(), "new".to_string());
+        assert_eq!(rename.build(false, true)?, "ALTER TABLE test RENAME COLUMN old TO new;");
+        assert_eq!(rename.len(true, false)?, rename.build(true, false)?.len());
+        assert!(rename.check_version_compatibility(SQLiteVersion::new(3, 25, 0)).is_ok());
+        assert_eq!(rename.check_version_compatibility(SQLiteVersion::new(3, 24, 0)), Err(vec![CompatibilityWarning::RENAME_COLUMN]));
+        let mut quoted = AlterTableRenameColumn::new("order".to_string(), "old col".to_string(), "select".to_string())
+            .set_quote(Quote::DoubleQuote)
+            .set_column_quote(Quote::DoubleQuote);
+        assert_eq!(quoted.build(false, false)?, r#"ALTER TABLE "order" RENAME COLUMN "old col" TO "select";"#);
+        assert_eq!(quoted.len(false, false)?, quoted.build(false, false)?.len());
+
+        let tbl = Table::new_default("test".to_string())
+            .add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()))
+            .add_column(Column::new_typed(SQLiteType::Integer, "b".to_string()).set_fk(Some(ForeignKey::new_default("test".to_string(), "a".to_string()))))
+            .add_unique_constraint(vec!["a".to_string(), "b".to_string()], OnConflict::Abort);
+        assert_eq!(tbl.clone().rename_column("missing", "c".to_string()), Err(Error::ColumnNotFound("missing".to_string())));
+        assert_eq!(tbl.clone().rename_column("a", "B".to_string()), Err(Error::DuplicateColumnName("B".to_string())));
+        assert_eq!(tbl.clone().rename_column("a", "A".to_string())?.columns()[0].name(), "A");
+        assert_eq!(
+            tbl.rename_column("a", "c".to_string())?,
+            Table::new_default("test".to_string())
+                .add_column(Column::new_typed(SQLiteType::Integer, "c".to_string()))
+                .add_column(Column::new_typed(SQLiteType::Integer, "b".to_string()).set_fk(Some(ForeignKey::new_default("test".to_string(), "c".to_string()))))
+                .add_unique_constraint(vec!["c".to_string(), "b".to_string()], OnConflict::Abort)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_alter_table_rename() -> Result<()> {
+        for possible in AlterTableRename::possibilities(true) {
+            if possible.old_name.is_empty() || possible.new_name.is_empty() {
+                assert_eq!(possible.part_len(), Err(Error::EmptyTableName));
+            } else {
+                test_sql_part(&*possible)?;
+            }
+        }
+
+        let mut rename = AlterTableRename::new("old".to_string(), "new".to_string());
+        assert_eq!(rename.build(false, false)?, "ALTER TABLE old RENAME TO new;");
+        assert_eq!(rename.build(false, true)?, "ALTER TABLE old RENAME TO new;");
+        assert_eq!(rename.build(true, false)?, "BEGIN;\nALTER TABLE old RENAME TO new;\nEND;");
+        assert_eq!(rename.len(true, false)?, rename.build(true, false)?.len());
+        assert!(rename.check_version_compatibility(SQLiteVersion::new(3, 1, 0)).is_ok());
+        assert_eq!(rename.check_version_compatibility(SQLiteVersion::new(3, 0, 8)), Err(vec![CompatibilityWarning::RENAME_TABLE]));
+
+        let mut quoted = AlterTableRename::new("order".to_string(), "group".to_string()).set_quote(Quote::DoubleQuote);
+        assert_eq!(quoted.build(false, false)?, r#"ALTER TABLE "order" RENAME TO "group";"#);
+        assert_eq!(quoted.len(false, false)?, quoted.build(false, false)?.len());
+        Ok(())
+    }
+
+    #[test]
+    fn test_alter_table_add_column() -> Result<()> {
+        for possible in AlterTableAddColumn::possibilities(true) {
+            if possible.table.is_empty() {
+                assert_eq!(possible.part_len(), Err(Error::EmptyTableName));
+            } else if possible.column.pk.is_some() {
+                assert_eq!(possible.part_len(), Err(Error::AddColumnPrimaryKey));
+            } else if let Err(err) = possible.column.part_len() {
+                assert_eq!(possible.part_len(), Err(err));
+            } else {
+                test_sql_part(&*possible)?;
+            }
+        }
+
+        let mut add = AlterTableAddColumn::new("tbl".to_string(), Column::new_typed(SQLiteType::Integer, "a".to_string()).set_default(Some(DefaultValue::Integer(0))));
+        assert_eq!(add.build(false, false)?, "ALTER TABLE tbl ADD COLUMN a INTEGER DEFAULT 0;");
+        assert_eq!(add.build(true, true)?, "BEGIN;\nALTER TABLE tbl ADD COLUMN a INTEGER DEFAULT 0;\nEND;");
+        assert_eq!(add.len(true, true)?, add.build(true, true)?.len());
+        assert!(add.check_version_compatibility(SQLiteVersion::new(3, 2, 0)).is_ok());
+        assert_eq!(add.check_version_compatibility(SQLiteVersion::new(3, 1, 0)), Err(vec![CompatibilityWarning::ADD_COLUMN]));
+        let fk = AlterTableAddColumn::new("tbl".to_string(), Column::new_typed(SQLiteType::Integer, "a".to_string()).set_fk(Some(ForeignKey::new_default("other".to_string(), "id".to_string()))));
+        assert_eq!(fk.check_version_compatibility(SQLiteVersion::new(3, 1, 0)), Err(vec![CompatibilityWarning::ADD_COLUMN, CompatibilityWarning::FOREIGN_KEY]));
+
+        let mut pk = AlterTableAddColumn::new("tbl".to_string(), Column::new_typed(SQLiteType::Integer, "a".to_string()).set_pk(Some(PrimaryKey::default())));
+        assert_eq!(pk.build(false, false), Err(Error::AddColumnPrimaryKey));
+
+        let mut quoted = AlterTableAddColumn::new("order".to_string(), Column::new_typed(SQLiteType::Integer, "my col".to_string()).set_quote(Quote::DoubleQuote))
+            .set_quote(Quote::DoubleQuote);
+        assert_eq!(quoted.build(false, false)?, r#"ALTER TABLE "order" ADD COLUMN "my col" INTEGER;"#);
+        assert_eq!(quoted.len(false, false)?, quoted.build(false, false)?.len());
+        Ok(())
+    }
+
+    #[test]
+    fn test_pragma() -> Result<()> {
+        for possible in Pragma::possibilities(true) {
+            if possible.name.is_empty() {
+                assert_eq!(possible.part_len(), Err(Error::EmptyPragmaName));
+            } else {
+                test_sql_part(&*possible)?;
+            }
+        }
+
+        assert_eq!(Pragma::foreign_keys_on().build(false, false)?, "PRAGMA foreign_keys = ON;");
+        assert_eq!(Pragma::new("encoding".to_string(), PragmaValue::Text("UTF-8".to_string())).build(true, true)?, "BEGIN;\nPRAGMA encoding = 'UTF-8';\nEND;");
+        assert_eq!(Pragma::new("cache_size".to_string(), PragmaValue::Integer(-2000)).build(false, false)?, "PRAGMA cache_size = -2000;");
+
+        let mut schema = Schema::new()
+            .add_table(Table::new_default("test".to_string()).add_column(Column::new_default("a".to_string())))
+            .add_pragma(Pragma::foreign_keys_on())
+            .add_pragma(Pragma::new("user_version".to_string(), PragmaValue::Integer(2)));
+        assert_eq!(schema.build(true, false)?, "PRAGMA foreign_keys = ON;PRAGMA user_version = 2;BEGIN;\nCREATE TABLE test (a BLOB);\nEND;");
+        assert_eq!(schema.len(true, false)?, schema.build(true, false)?.len());
+        test_sql(&mut schema)?;
+        assert_eq!(schema.build_with_mode(BuildMode::PerStatement, false)?, "PRAGMA foreign_keys = ON;\nPRAGMA user_version = 2;\nBEGIN;\nCREATE TABLE test (a BLOB);\nEND;");
+        assert_eq!(schema.len_with_mode(BuildMode::PerStatement, false)?, schema.build_with_mode(BuildMode::PerStatement, false)?.len());
+        assert_eq!(schema.build_pretty(true, false, 2)?, "PRAGMA foreign_keys = ON;\nPRAGMA user_version = 2;\nBEGIN;\nCREATE TABLE test (\n  a BLOB\n);\nEND;");
+
+        assert_ne!(schema, schema.clone().with_pragmas(Vec::new()));
+        assert_eq!(schema.clone().add_pragma(Pragma::new("".to_string(), PragmaValue::Off)).validate(), Err(vec![Error::EmptyPragmaName]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_quote() -> Result<()> {
+        let col = Column::new_typed(SQLiteType::Integer, "order".to_string());
+        assert_eq!(col.quoted_name(), "order");
+        assert_eq!(col.clone().set_quote(Quote::DoubleQuote).quoted_name(), "\"order\"");
+        assert_eq!(col.clone().set_quote(Quote::Backtick).quoted_name(), "`order`");
+        assert_eq!(col.clone().set_name("a\"b`c".to_string()).set_quote(Quote::DoubleQuote).quoted_name(), "\"a\"\"b`c\"");
+        assert_eq!(col.clone().set_name("a\"b`c".to_string()).set_quote(Quote::Backtick).quoted_name(), "`a\"b``c`");
+        for quote in [Quote::None, Quote::DoubleQuote, Quote::Backtick] {
+            test_sql_part(&col.clone().set_name("a\"b`c".to_string()).set_quote(quote))?;
+        }
+
+        assert!(is_sqlite_keyword("order"));
+        assert!(is_sqlite_keyword("Current_Timestamp"));
+        assert!(!is_sqlite_keyword("orders"));
+        assert!(!is_sqlite_keyword(""));
+        assert!(needs_quoting("order"));
+        assert!(needs_quoting("my tbl"));
+        assert!(needs_quoting("1st"));
+        assert!(needs_quoting("a\"b"));
+        assert!(!needs_quoting("orders_2"));
+        assert!(!needs_quoting("größe"));
+        assert_eq!(Quote::for_name("group"), Quote::DoubleQuote);
+        assert_eq!(Quote::for_name("grouping"), Quote::None);
+
+        let mut schema = Schema::new()
+            .add_table(Table::new_default("group".to_string())
+                .add_column(col.clone())
+                .add_column(Column::new_typed(SQLiteType::Text, "name".to_string())))
+            .auto_quote();
+        assert_eq!(schema.tables()[0].quote(), Quote::DoubleQuote);
+        assert_eq!(schema.tables()[0].quoted_name(), "\"group\"");
+        assert_eq!(schema.build(false, false)?, "CREATE TABLE \"group\" (\"order\" INTEGER,name TEXT);");
+        test_sql(&mut schema)?;
+
+        let mut schema = schema
+            .add_table(Table::new_default("child".to_string())
+                .add_column(Column::new_typed(SQLiteType::Integer, "key".to_string()).set_fk(Some(ForeignKey::new_default("group".to_string(), "order".to_string()))))
+                .add_unique_constraint(vec!["key".to_string()], OnConflict::Abort))
+            .add_index(Index::new_default("idx".to_string(), "group".to_string()).add_column("order".to_string(), Order::Ascending))
+            .auto_quote();
+        assert_eq!(
+            schema.build(false, false)?,
+            "CREATE TABLE \"group\" (\"order\" INTEGER,name TEXT);\
+            CREATE TABLE child (\"key\" INTEGER REFERENCES \"group\" (\"order\"),UNIQUE (\"key\") ON CONFLICT ABORT);\
+            CREATE INDEX \"idx\" ON \"group\" (\"order\" ASC);"
+        );
+        assert_eq!(schema.to_drop_stmt(false)?, "DROP INDEX \"idx\";DROP TABLE child;DROP TABLE \"group\";");
+        test_sql(&mut schema)?;
+        assert_ne!(Table::new_default("a".to_string()), Table::new_default("a".to_string()).set_quote(Quote::Backtick));
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_pretty() -> Result<()> {
+        let mut tbl = Table::new("test".to_string(), vec![
+            Column::new_typed(SQLiteType::Integer, "a".to_string()),
+            Column::new_typed(SQLiteType::Text, "b".to_string()),
+        ], false, true).add_unique_constraint(vec!["a".to_string(), "b".to_string()], OnConflict::Abort);
+        assert_eq!(tbl.build_pretty(false, false, 2)?, "CREATE TABLE test (\n  a INTEGER,\n  b TEXT,\n  UNIQUE (a, b) ON CONFLICT ABORT\n) STRICT;");
+        assert_eq!(tbl.build_pretty(true, true, 4)?, "BEGIN;\nCREATE TABLE IF NOT EXISTS test (\n    a INTEGER,\n    b TEXT,\n    UNIQUE (a, b) ON CONFLICT ABORT\n) STRICT;\nEND;");
+        assert_eq!(tbl.build_pretty(false, false, 0)?, "CREATE TABLE test (\na INTEGER,\nb TEXT,\nUNIQUE (a, b) ON CONFLICT ABORT\n) STRICT;");
+
+        let mut idx = Index::new_default("test_idx".to_string(), "test".to_string()).add_column("a".to_string(), Order::Ascending);
+        assert_eq!(idx.build_pretty(false, false, 2)?, idx.build(false, false)?);
+
+        let mut schema = Schema::new()
+            .add_table(Table::new_default("one".to_string()).add_column(Column::new_default("a".to_string())))
+            .add_table(Table::new_default("two".to_string()).add_column(Column::new_default("b".to_string())))
+            .add_index(idx);
+        assert_eq!(schema.build_pretty(true, false, 2)?, "BEGIN;\nCREATE TABLE one (\n  a BLOB\n);\nCREATE TABLE two (\n  b BLOB\n);\nCREATE INDEX test_idx ON test (a ASC);\nEND;");
+        assert_eq!(Schema::new().build_pretty(false, false, 2), Err(Error::SchemaWithoutTables));
+        Ok(())
+    }
+
+    #[test]
+    fn test_strict_types() -> Result<()> {
+        for typ in SQLiteType::possibilities(false) {
+            let mut tbl = Table::new_default("test".to_string())
+                .add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()))
+                .add_column(Column::new_typed(*typ, "b".to_string()))
+                .set_strict(true);
+            if *typ == SQLiteType::Numeric {
+                assert_eq!(tbl.build(false, false), Err(Error::StrictTableInvalidType { column: "b".to_string(), typ: SQLiteType::Numeric }));
+                // allowed in non-strict tables
+                tbl = tbl.set_strict(false);
+            }
+            test_sql_part(&tbl)?;
+            test_sql(&mut tbl)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_comments() -> Result<()> {
+        let tbl = Table::new_default("test".to_string())
+            .add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()).set_comment(Some("first".to_string())))
+            .add_column(Column::new_typed(SQLiteType::Text, "b".to_string()).set_comment(Some("last".to_string())))
+            .set_comment(Some("a table\nwith two lines".to_string()));
+        assert_eq!(tbl.comment(), Some("a table\nwith two lines"));
+        assert_eq!(tbl.get_column("a").and_then(Column::comment), Some("first"));
+
+        let mut schema = Schema::new().add_table(tbl.clone()).set_comment(Some("schema".to_string()));
+        assert_eq!(schema.comment(), Some("schema"));
+        assert_eq!(
+            schema.build_pretty(true, false, 2)?,
+            "-- schema\nBEGIN;\n-- a table\n-- with two lines\nCREATE TABLE test (\n  a INTEGER, -- first\n  b TEXT -- last\n);\nEND;"
+        );
+        // stripped from the compact format
+        assert_eq!(schema.build(false, false)?, "CREATE TABLE test (a INTEGER,b TEXT);");
+        test_sql(&mut schema)?;
+        #[cfg(feature = "rusqlite")]
+        Connection::open_in_memory()?.execute_batch(schema.build_pretty(true, true, 2)?.as_str())?;
+
+        let mut constrained = tbl.clone().add_unique_constraint(vec!["a".to_string()], OnConflict::Abort);
+        assert_eq!(
+            constrained.build_pretty(false, false, 2)?,
+            "-- a table\n-- with two lines\nCREATE TABLE test (\n  a INTEGER, -- first\n  b TEXT, -- last\n  UNIQUE (a) ON CONFLICT ABORT\n);"
+        );
+
+        assert_ne!(tbl, tbl.clone().set_comment(None));
+        // comment-only changes need no ALTER TABLE
+        let changed = tbl.clone().replace_column(Column::new_typed(SQLiteType::Integer, "a".to_string()))?;
+        assert_eq!(Schema::from(tbl).diff(&Schema::from(changed)).to_alter_statements(false)?, Vec::<String>::new());
+        Ok(())
+    }
+
+    #[test]
+    fn test_table_key_columns() -> Result<()> {
+        let pk_col = Column::new_typed(SQLiteType::Integer, "id".to_string()).set_pk(Some(PrimaryKey::default()));
+        let fk_col = Column::new_typed(SQLiteType::Integer, "parent".to_string()).set_fk(Some(ForeignKey::new_default("test".to_string(), "id".to_string())));
+        let mut tbl = Table::new_default("test".to_string()).add_column(Column::new_default("plain".to_string()));
+        assert_eq!(tbl.primary_key_column(), None);
+        assert!(tbl.primary_key_column_mut().is_none());
+        assert_eq!(tbl.foreign_key_columns().count(), 0);
+        assert!(!tbl.has_primary_key());
+        assert!(!tbl.has_foreign_keys());
+        assert!(tbl.has_column("plain"));
+        assert!(!tbl.has_column("id"));
+
+        let composite = tbl.clone()
+            .add_constraint(TableConstraint::CompositePrimaryKey(vec!["plain".to_string()], OnConflict::Abort))
+            .add_composite_fk(CompositeForeignKey::new_default(vec!["plain".to_string()], "other".to_string(), vec!["id".to_string()]));
+        assert!(composite.has_primary_key());
+        assert!(composite.has_foreign_keys());
+
+        tbl = tbl.add_column(pk_col.clone()).add_column(fk_col.clone()).add_column(fk_col.clone().set_name("other".to_string()));
+        assert_eq!(tbl.primary_key_column(), Some(&pk_col));
+        assert_eq!(tbl.foreign_key_columns().map(Column::name).collect::<Vec<&str>>(), vec!["parent", "other"]);
+        assert!(tbl.has_primary_key());
+        assert!(tbl.has_foreign_keys());
+        assert!(tbl.has_column("id"));
+
+        if let Some(col) = tbl.primary_key_column_mut() {
+            *col = col.clone().set_name("key".to_string());
+        }
+        assert_eq!(tbl.primary_key_column().map(Column::name), Some("key"));
+        for col in tbl.foreign_key_columns_mut() {
+            *col = col.clone().set_fk(None);
+        }
+        assert_eq!(tbl.foreign_key_columns().count(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_table_duplicate_column() -> Result<()> {
+        let mut tbl = Table::new_default("t".to_string())
+            .add_column(Column::new_default("id".to_string()))
+            .add_column(Column::new_default("id".to_string()));
+        assert_eq!(tbl.build(false, false), Err(Error::DuplicateColumnName("id".to_string())));
+
+        let mut tbl = Table::new_default("t".to_string())
+            .add_column(Column::new_default("id".to_string()))
+            .add_column(Column::new_typed(SQLiteType::Text, "name".to_string()))
+            .add_column(Column::new_typed(SQLiteType::Integer, "name".to_string()));
+        assert_eq!(tbl.part_len(), Err(Error::DuplicateColumnName("name".to_string())));
+        assert_eq!(tbl.build(true, true), Err(Error::DuplicateColumnName("name".to_string())));
+
+        let mut tbl = Table::new_default("t".to_string())
+            .add_column(Column::new_default("id".to_string()))
+            .add_column(Column::new_default("ID".to_string()));
+        assert_eq!(tbl.build(false, false), Err(Error::DuplicateColumnName("ID".to_string())));
+        Ok(())
+    }
+
+    #[test]
+    fn test_table_column_lookup_case_insensitive() -> Result<()> {
+        let col = |name: &str| Column::new_typed(SQLiteType::Integer, name.to_string());
+        let mut tbl = Table::new_default("t".to_string())
+            .add_column(col("id"))
+            .add_column(col("Name"))
+            .add_constraint(TableConstraint::CompositePrimaryKey(vec!["ID".to_string(), "name".to_string()], OnConflict::Abort));
+        assert_eq!(tbl.build(false, false)?, "CREATE TABLE t (id INTEGER,Name INTEGER,PRIMARY KEY (ID, name) ON CONFLICT ABORT);");
+        assert!(tbl.has_column("ID"));
+        assert_eq!(tbl.get_column("NAME").map(Column::name), Some("Name"));
+        assert_eq!(tbl.get_column_mut("Id").map(|col| col.name()), Some("id"));
+
+        tbl = tbl.add_column_after(col("after"), "ID")?.add_column_before(col("before"), "NAME")?;
+        assert_eq!(tbl.columns().iter().map(Column::name).collect::<Vec<&str>>(), vec!["id", "after", "before", "Name"]);
+        tbl = tbl.rename_column("NAME", "full_name".to_string())?;
+        assert_eq!(tbl.get_column("full_name").map(Column::name), Some("full_name"));
+        assert_eq!(tbl.constraints(), &[TableConstraint::CompositePrimaryKey(vec!["ID".to_string(), "full_name".to_string()], OnConflict::Abort)]);
+        assert_eq!(tbl.remove_column("AFTER").map(|col| col.name), Some("after".to_string()));
+        assert!(!tbl.has_column("after"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_into() -> Result<()> {
+        let mut tbl = Table::new_default("test".to_string()).add_column(Column::new_default("testcol".to_string()));
+        let mut idx = Index::new_default("test_idx".to_string(), "test".to_string()).add_column("testcol".to_string(), Order::Ascending);
+        let mut schema = Schema::new().add_table(tbl.clone()).add_index(idx.clone());
+
+        for transaction in [true, false] {
+            for if_exists in [true, false] {
+                let mut buf: String = "-- prefix\n".to_string();
+                tbl.build_into(&mut buf, transaction, if_exists)?;
+                idx.build_into(&mut buf, transaction, if_exists)?;
+                assert_eq!(buf, format!("-- prefix\n{}{}", tbl.build(transaction, if_exists)?, idx.build(transaction, if_exists)?));
+
+                let mut buf: String = String::new();
+                schema.build_into(&mut buf, transaction, if_exists)?;
+                assert_eq!(buf, schema.build(transaction, if_exists)?);
+
+                let mut written: String = String::new();
+                schema.write_to(&mut written, transaction, if_exists)?;
+                assert_eq!(written, buf);
+            }
+        }
+
+        let mut buf: String = String::new();
+        assert_eq!(Schema::new().build_into(&mut buf, false, false), Err(Error::SchemaWithoutTables));
+        Ok(())
+    }
+
+    #[test]
+    fn test_schema_len() -> Result<()> {
+        let tbl = Table::new_default("test".to_string()).add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()));
+        let idx = Index::new_default("idx".to_string(), "test".to_string()).add_column("a".to_string(), Order::Ascending);
+        let schemas: [Schema; 4] = [
+            Schema::new().add_table(tbl.clone()),
+            Schema::new().add_table(tbl.clone()).add_table(tbl.clone().set_name("other".to_string())).add_index(idx.clone()),
+            Schema::new().add_table(tbl.clone()).add_index(idx).add_pragma(Pragma::new("foreign_keys".to_string(), PragmaValue::On)),
+            Schema::new().set_name("reporting".to_string()).add_table(tbl),
+        ];
+        for mut schema in schemas {
+            for transaction in [true, false] {
+                for if_exists in [true, false] {
+                    let sql: String = schema.build(transaction, if_exists)?;
+                    // "BEGIN;\n" and "\nEND;" include the newlines between the Statements and the Transaction
+                    assert_eq!(sql.len(), schema.len(transaction, if_exists)?, "{}", sql);
+                    assert_eq!(sql.ends_with("\nEND;"), transaction);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_with_mode() -> Result<()> {
+        let tbl = Table::new_default("test".to_string()).add_column(Column::new_default("testcol".to_string()));
+        let idx = Index::new_default("test_idx".to_string(), "test".to_string()).add_column("testcol".to_string(), Order::Ascending);
+        let mut schema = Schema::new().add_table(tbl.clone()).add_table(tbl.clone().set_name("other".to_string())).add_index(idx);
+
+        for if_exists in [true, false] {
+            let single: String = schema.build_with_mode(BuildMode::Single, if_exists)?;
+            assert_eq!(single, schema.build(true, if_exists)?);
+            assert_eq!(single.len(), schema.len_with_mode(BuildMode::Single, if_exists)?);
+
+            let per_stmt: String = schema.build_with_mode(BuildMode::PerStatement, if_exists)?;
+            assert_eq!(per_stmt.len(), schema.len_with_mode(BuildMode::PerStatement, if_exists)?);
+            assert_eq!(per_stmt.matches("BEGIN;\n").count(), 3);
+            assert_eq!(per_stmt.matches("\nEND;").count(), 3);
+            #[cfg(feature = "rusqlite")]
+            Connection::open_in_memory()?.execute_batch(per_stmt.as_str())?;
+
+            let mut tbl = tbl.clone();
+            assert_eq!(tbl.build_with_mode(BuildMode::PerStatement, if_exists)?, tbl.build(true, if_exists)?);
+        }
+
+        assert_eq!(
+            Schema::new().add_table(tbl.clone()).add_table(tbl.set_name("other".to_string())).build_with_mode(BuildMode::PerStatement, false)?,
+            "BEGIN;\nCREATE TABLE test (testcol BLOB);\nEND;\nBEGIN;\nCREATE TABLE other (testcol BLOB);\nEND;"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_separate() -> Result<()> {
+        let tbl = Table::new_default("test".to_string()).add_column(Column::new_default("testcol".to_string()));
+        let idx = Index::new_default("test_idx".to_string(), "test".to_string()).add_column("testcol".to_string(), Order::Ascending);
+        let mut schema = Schema::new()
+            .add_table(tbl.clone())
+            .add_table(tbl.set_name("other".to_string()))
+            .add_index(idx)
+            .add_pragma(Pragma::foreign_keys_on());
+
+        let stmts: Vec<String> = schema.build_separate(true)?;
+        assert_eq!(stmts.len(), schema.pragmas().len() + schema.table_count() + schema.indexes().len());
+        assert_eq!(stmts, vec![
+            "PRAGMA foreign_keys = ON;".to_string(),
+            "CREATE TABLE IF NOT EXISTS test (testcol BLOB);".to_string(),
+            "CREATE TABLE IF NOT EXISTS other (testcol BLOB);".to_string(),
+            "CREATE INDEX IF NOT EXISTS test_idx ON test (testcol ASC);".to_string(),
+        ]);
+        assert_eq!(stmts.concat(), schema.build(false, true)?);
+
+        #[cfg(feature = "rusqlite")]
+        {
+            let conn: Connection = Connection::open_in_memory()?;
+            for stmt in &stmts {
+                conn.execute_batch(stmt.as_str())?;
+            }
+        }
+
+        assert_eq!(Schema::new().build_separate(false), Err(Error::SchemaWithoutTables));
+        Ok(())
+    }
+
+    #[test]
+    fn test_schema_duplicate_table() -> Result<()> {
+        let tbl = Table::new_default("test".to_string()).add_column(Column::new_default("testcol".to_string()));
+        let mut schema = Schema::new()
+            .add_table(tbl.clone())
+            .add_table(tbl.clone().set_name("other".to_string()))
+            .add_table(tbl);
+        assert_eq!(schema.build(false, false), Err(Error::DuplicateTableName("test".to_string())));
+        assert_eq!(schema.build(true, true), Err(Error::DuplicateTableName("test".to_string())));
+        assert_eq!(schema.validate(), Err(vec![Error::DuplicateTableName("test".to_string())]));
+
+        let tbl = Table::new_default("test".to_string()).add_column(Column::new_default("testcol".to_string()));
+        let mut schema = Schema::new()
+            .add_table(tbl.clone())
+            .add_table(tbl.set_name("TeSt".to_string()));
+        assert_eq!(schema.build(false, false), Err(Error::DuplicateTableName("TeSt".to_string())));
+        Ok(())
+    }
+
+    #[test]
+    fn test_schema_get_remove_table() -> Result<()> {
+        let tbl = Table::new_default("test".to_string()).add_column(Column::new_default("testcol".to_string()));
+        let other = tbl.clone().set_name("other".to_string());
+        let mut schema = Schema::new().add_table(tbl.clone()).add_table(other.clone());
+
+        assert_eq!(schema.get_table("test"), Some(&tbl));
+        assert_eq!(schema.get_table("missing"), None);
+
+        assert_eq!(schema.remove_table("missing"), None);
+        assert_eq!(schema.remove_table("test"), Some(tbl));
+        assert_eq!(schema.remove_table("test"), None);
+        assert_eq!(schema.get_table("test"), None);
+        assert_eq!(schema.tables(), &[other]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_schema_merge() -> Result<()> {
+        let tbl = Table::new_default("test".to_string()).add_column(Column::new_default("testcol".to_string()));
+        let idx = Index::new_default("test_idx".to_string(), "test".to_string()).add_column("testcol".to_string(), Order::Ascending);
+        let first = Schema::new().add_table(tbl.clone()).add_index(idx.clone());
+        let second = Schema::new().add_table(tbl.clone().set_name("other".to_string()));
+
+        let merged = first.clone().merge(second.clone())?;
+        assert_eq!(merged, Schema::new().add_table(tbl.clone()).add_table(tbl.clone().set_name("other".to_string())).add_index(idx.clone()));
+
+        assert_eq!(merged.clone().merge(second.clone()), Err(Error::DuplicateTableName("other".to_string())));
+
+        let mut extended = first.clone();
+        extended.extend(&second);
+        assert_eq!(extended, merged);
+        extended.extend(&second);
+        assert_eq!(extended.tables().len(), 3);
+        assert_eq!(extended.build(false, false), Err(Error::DuplicateTableName("other".to_string())));
+        Ok(())
+    }
+
+    #[test]
+    fn test_schema_from() -> Result<()> {
+        let tbl = Table::new_default("test".to_string()).add_column(Column::new_default("testcol".to_string()));
+        let schema: Schema = tbl.clone().into();
+        assert_eq!(schema, Schema::new().add_table(tbl.clone()));
+        assert_eq!(schema.validate(), Ok(()));
+
+        let other = tbl.clone().set_name("other".to_string());
+        let schema = Schema::from(vec![tbl.clone(), other.clone()]);
+        assert_eq!(schema, Schema::new().add_table(tbl.clone()).add_table(other.clone()));
+        assert_eq!(schema.validate(), Ok(()));
+        assert_eq!(Schema::from_tables([tbl.clone(), other].into_iter().rev()), Schema::from(vec![tbl.clone().set_name("other".to_string()), tbl.clone()]));
+
+        let cols = ["a", "b"].into_iter().map(|name| Column::new_typed(SQLiteType::Integer, name.to_string()));
+        assert_eq!(
+            Table::new_from_columns("test".to_string(), cols),
+            Table::new_default("test".to_string()).add_column(Column::new_typed(SQLiteType::Integer, "a".to_string())).add_column(Column::new_typed(SQLiteType::Integer, "b".to_string()))
+        );
+
+        assert_eq!(Schema::from(Vec::new()).validate(), Err(vec![Error::SchemaWithoutTables]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_schema_drop_stmt() -> Result<()> {
+        let tbl = Table::new_default("test".to_string()).add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()));
+        let schema = Schema::new()
+            .add_table(tbl.clone())
+            .add_table(tbl.clone().set_name("other".to_string()))
+            .add_index(Index::new_default("idx".to_string(), "test".to_string()).add_column("a".to_string(), Order::Ascending))
+            .add_index(Index::new_default("idx2".to_string(), "other".to_string()).add_column("a".to_string(), Order::Ascending));
+        assert_eq!(schema.to_drop_stmt(false)?, "DROP INDEX idx2;DROP INDEX idx;DROP TABLE other;DROP TABLE test;");
+        assert_eq!(schema.to_drop_stmt(true)?, "DROP INDEX IF EXISTS idx2;DROP INDEX IF EXISTS idx;DROP TABLE IF EXISTS other;DROP TABLE IF EXISTS test;");
+        assert_eq!(Schema::new().to_drop_stmt(false), Err(Error::SchemaWithoutTables));
+        assert_eq!(Schema::new().add_table(tbl.set_name("".to_string())).to_drop_stmt(false), Err(Error::EmptyTableName));
+        Ok(())
+    }
+
+    #[test]
+    fn test_schema_name() -> Result<()> {
+        let tbl = Table::new_default("test".to_string()).add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()));
+        let mut schema = Schema::new()
+            .add_table(tbl)
+            .add_index(Index::new_default("idx".to_string(), "test".to_string()).add_column("a".to_string(), Order::Ascending));
+        assert_eq!(schema.name(), None);
+        assert_eq!(schema.to_attach_stmt("test.db"), Err(Error::SchemaWithoutName));
+
+        schema = schema.set_name("reporting".to_string());
+        assert_eq!(schema.name(), Some("reporting"));
+        assert_eq!(schema.to_attach_stmt("it's.db")?, "ATTACH DATABASE 'it''s.db' AS reporting;");
+        assert_eq!(schema.build(false, false)?, "CREATE TABLE reporting.test (a INTEGER);CREATE INDEX reporting.idx ON test (a ASC);");
+        assert_eq!(schema.len(true, true)?, schema.build(true, true)?.len());
+        assert_eq!(schema.to_drop_stmt(false)?, "DROP INDEX reporting.idx;DROP TABLE reporting.test;");
+        assert_ne!(schema, schema.clone().set_name("other".to_string()));
+        // building does not leave the name in the Tables and Indexes
+        assert_eq!(schema.tables()[0].clone().build(false, false)?, "CREATE TABLE test (a INTEGER);");
+        assert_eq!(schema.indexes()[0].clone().build(false, false)?, "CREATE INDEX idx ON test (a ASC);");
+        assert_eq!(schema.clone().into_iter().next().map(|mut stmt| stmt.build(false, false)).transpose()?, Some("CREATE TABLE test (a INTEGER);".to_string()));
+
+        schema = schema.set_name("my db".to_string());
+        assert_eq!(schema.to_attach_stmt("test.db")?, "ATTACH DATABASE 'test.db' AS \"my db\";");
+        assert_eq!(schema.build(false, true)?, "CREATE TABLE IF NOT EXISTS \"my db\".test (a INTEGER);CREATE INDEX IF NOT EXISTS \"my db\".idx ON test (a ASC);");
+        assert_eq!(schema.len(false, true)?, schema.build(false, true)?.len());
+        assert_eq!(schema.build_separate(false)?, vec!["CREATE TABLE \"my db\".test (a INTEGER);".to_string(), "CREATE INDEX \"my db\".idx ON test (a ASC);".to_string()]);
+        assert_eq!(schema.to_drop_stmt(true)?, "DROP INDEX IF EXISTS \"my db\".idx;DROP TABLE IF EXISTS \"my db\".test;");
+        assert_eq!(schema.clone().set_name("order".to_string()).to_attach_stmt("test.db")?, "ATTACH DATABASE 'test.db' AS \"order\";");
+
+        schema = schema.set_name("".to_string());
+        assert_eq!(schema.validate(), Err(vec![Error::EmptySchemaName]));
+        assert_eq!(schema.build(false, false), Err(Error::EmptySchemaName));
+        assert_eq!(schema.to_attach_stmt("test.db"), Err(Error::EmptySchemaName));
+        Ok(())
+    }
+
+    #[test]
+    fn test_schema_display() -> Result<()> {
+        let schema = Schema::new()
+            .set_name("reporting".to_string())
+            .add_table(Table::new_default("test".to_string())
+                .add_column(Column::new_typed(SQLiteType::Integer, "id".to_string()).set_pk(Some(PrimaryKey::default())).set_not_null(Some(NotNull::default())))
+                .add_column(Column::new_typed(SQLiteType::Text, "name".to_string()))
+                .add_unique_constraint(vec!["id".to_string(), "name".to_string()], OnConflict::Abort))
+            .add_index(Index::new_default("idx".to_string(), "test".to_string()).add_column("name".to_string(), Order::Ascending));
+        let expected: &str = "schema reporting\n  table test\n    id INTEGER: PRIMARY KEY ASC ON CONFLICT ABORT, NOT NULL ON CONFLICT ABORT\n    name TEXT\n    UNIQUE (id, name) ON CONFLICT ABORT\n  CREATE INDEX idx ON test (name ASC)";
+        assert_eq!(schema.display().to_string(), expected);
+        assert_eq!(format!("{:?}", schema.display()), expected);
+        assert_eq!(Schema::new().display().to_string(), "schema");
+        Ok(())
+    }
+
+    #[test]
+    fn test_sort_columns() -> Result<()> {
+        let tbl = Table::new_default("test".to_string())
+            .add_column(Column::new_typed(SQLiteType::Text, "b".to_string()))
+            .add_column(Column::new_typed(SQLiteType::Text, "c".to_string()))
+            .add_column(Column::new_typed(SQLiteType::Integer, "id".to_string()).set_pk(Some(PrimaryKey::default())))
+            .add_column(Column::new_typed(SQLiteType::Text, "a".to_string()));
+
+        let sorted = tbl.clone().sort_columns_pk_first();
+        assert_eq!(sorted.primary_key_column(), Some(&sorted.columns()[0]));
+        assert_eq!(sorted.columns().iter().map(Column::name).collect::<Vec<&str>>(), vec!["id", "b", "c", "a"]);
+
+        let sorted = tbl.clone().sort_columns_by(|a, b| a.name().cmp(b.name()));
+        assert_eq!(sorted.columns().iter().map(Column::name).collect::<Vec<&str>>(), vec!["a", "b", "c", "id"]);
+
+        let mut no_pk = tbl.clone();
+        no_pk.remove_column("id");
+        assert_eq!(no_pk.clone().sort_columns_pk_first(), no_pk);
+        Ok(())
+    }
+
+    #[test]
+    fn test_create_drop_stmt() -> Result<()> {
+        let mut tbl = Table::new_default("test".to_string()).add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()));
+        assert_eq!(tbl.to_create_stmt(false, true)?, tbl.build(false, true)?);
+        assert_eq!(tbl.to_drop_stmt(false), DropTable::new("test".to_string()).build(false, false)?);
+        assert_eq!(tbl.to_drop_stmt(true), "DROP TABLE IF EXISTS test;");
+        assert_eq!(tbl.set_name("order".to_string()).set_quote(Quote::DoubleQuote).to_drop_stmt(false), "DROP TABLE \"order\";");
+        Ok(())
+    }
+
+    #[test]
+    fn test_typed_column() -> Result<()> {
+        assert_eq!(TextColumn::new("a".to_string()), Column::new_typed(SQLiteType::Text, "a".to_string()));
+        assert_eq!(IntegerColumn::new("a".to_string()), Column::new_typed(SQLiteType::Integer, "a".to_string()));
+        assert_eq!(RealColumn::new("a".to_string()), Column::new_typed(SQLiteType::Real, "a".to_string()));
+        assert_eq!(BlobColumn::new("a".to_string()), Column::new_typed(SQLiteType::Blob, "a".to_string()));
+        assert_eq!(NumericColumn::new("a".to_string()), Column::new_typed(SQLiteType::Numeric, "a".to_string()));
+
+        assert_eq!(TypedColumn::<Option<u8>>::TYPE, SQLiteType::Integer);
+        assert_eq!(TypedColumn::<str>::TYPE, SQLiteType::Text);
+        assert_eq!(TypedColumn::<[u8]>::TYPE, SQLiteType::Blob);
+        assert_eq!(<TypedColumn<f32> as IntoColumn>::into_column("a".to_string()), RealColumn::new("a".to_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_strip_constraints() -> Result<()> {
+        for tbl in Table::possibilities(false) {
+            let mut stripped: Table = tbl.strip_constraints();
+            assert!(stripped.build(false, false).is_ok());
+            assert!(!stripped.has_primary_key() && !stripped.has_foreign_keys());
+            assert!(stripped.constraints().is_empty() && !stripped.is_strict() && !stripped.is_without_rowid());
+            test_sql(&mut stripped)?;
+        }
+
+        let col = Column::new_typed(SQLiteType::Integer, "a".to_string())
+            .set_pk(Some(PrimaryKey::default()))
+            .set_unique(Some(Unique::default()))
+            .set_fk(Some(ForeignKey::new_default("other".to_string(), "b".to_string())))
+            .set_not_null(Some(NotNull::default()))
+            .set_check(Some(Check::new("a > 0".to_string())))
+            .set_default(Some(DefaultValue::Integer(1)));
+        assert_eq!(col.strip_constraints(), Column::new_typed(SQLiteType::Integer, "a".to_string()).set_default(Some(DefaultValue::Integer(1))));
+
+        let mut tbl = Table::new_default("test".to_string())
+            .add_column(Column::new_typed(SQLiteType::Numeric, "a".to_string()).set_pk(Some(PrimaryKey::default())))
+            .add_column(Column::new_typed(SQLiteType::Integer, "b".to_string()))
+            .add_unique_constraint(vec!["a".to_string(), "b".to_string()], OnConflict::Abort)
+            .set_without_rowid(true)
+            .set_strict(true);
+        assert!(tbl.build(false, false).is_err());
+        tbl = tbl.strip_constraints();
+        assert_eq!(tbl.build(false, false)?, "CREATE TABLE test (a NUMERIC,b INTEGER);");
+        Ok(())
+    }
+
+    #[test]
+    fn test_schema_builder() -> Result<()> {
+        assert_eq!(Schema::default(), Schema::new());
+        assert_eq!(Schema::named("app".to_string()).comment(), Some("app"));
+
+        let tbl = Table::new_default("test".to_string()).add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()));
+        let idx = Index::new_default("idx".to_string(), "test".to_string()).add_column("a".to_string(), Order::Ascending);
+        let schema: Schema = SchemaBuilder::new()
+            .add_table(tbl.clone())
+            .add_index(idx.clone())
+            .add_pragma(Pragma::foreign_keys_on())
+            .set_comment(Some("app".to_string()))
+            .build()?;
+        assert_eq!(schema, Schema::named("app".to_string()).add_table(tbl.clone()).add_index(idx).add_pragma(Pragma::foreign_keys_on()));
+
+        assert_eq!(SchemaBuilder::new().build(), Err(Error::SchemaWithoutTables));
+        assert_eq!(SchemaBuilder::new().add_table(tbl.clone()).add_table(tbl).build(), Err(Error::DuplicateTableName("test".to_string())));
+        Ok(())
+    }
+
+    #[test]
+    fn test_schema_statements() -> Result<()> {
+        let tbl = Table::new_default("test".to_string()).add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()));
+        let idx = Index::new_default("idx".to_string(), "test".to_string()).add_column("a".to_string(), Order::Ascending);
+        let pragma = Pragma::foreign_keys_on();
+        let schema = Schema::new()
+            .add_table(tbl.clone())
+            .add_index(idx.clone())
+            .add_pragma(pragma.clone())
+            .add_table(tbl.clone().set_name("other".to_string()));
+
+        let stmts: Vec<AnySqlStatement> = schema.clone().into_iter().collect();
+        assert_eq!(stmts, vec![
+            AnySqlStatement::Pragma(pragma.clone()),
+            AnySqlStatement::Table(tbl.clone()),
+            AnySqlStatement::Table(tbl.clone().set_name("other".to_string())),
+            AnySqlStatement::Index(idx.clone()),
+        ]);
+        assert_eq!(stmts.into_iter().collect::<Schema>(), schema);
+
+        let mut stmt: AnySqlStatement = tbl.clone().into();
+        assert_eq!(stmt.build(true, true)?, tbl.clone().build(true, true)?);
+        assert_eq!(stmt.len(false, false)?, stmt.build(false, false)?.len());
+        assert_eq!(stmt.build_pretty(false, false, 2)?, tbl.clone().build_pretty(false, false, 2)?);
+        let mut stmt: AnySqlStatement = idx.clone().into();
+        assert_eq!(stmt.build(false, false)?, idx.clone().build(false, false)?);
+        let mut stmt: AnySqlStatement = pragma.clone().into();
+        assert_eq!(stmt.build(false, false)?, "PRAGMA foreign_keys = ON;");
+        Ok(())
+    }
+
+    #[test]
+    fn test_iterators() -> Result<()> {
+        let tbl = Table::new_default("test".to_string()).add_column(Column::new_default("a".to_string())).add_column(Column::new_default("b".to_string()));
+        let mut schema = Schema::new().add_table(tbl.clone()).add_table(tbl.set_name("other".to_string()));
+        assert_eq!(schema.table_count(), 2);
+        assert_eq!(schema.tables_iter().map(Table::name).collect::<Vec<&str>>(), vec!["test", "other"]);
+
+        for tbl in schema.tables_mut() {
+            for col in tbl.columns_mut() {
+                *col = col.clone().set_type(SQLiteType::Integer);
+            }
+        }
+        assert!(schema.tables_iter().flat_map(Table::columns_iter).all(|col| col.col_type() == SQLiteType::Integer));
+        assert_eq!(schema.tables_iter().flat_map(Table::columns_iter).count(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_version_compatibility() -> Result<()> {
+        assert!(SQLiteVersion::new(3, 37, 0) > SQLiteVersion::new(3, 8, 2));
+        assert!(SQLiteVersion::new(3, 8, 2) > SQLiteVersion::new(3, 8, 0));
+        assert_eq!(SQLiteVersion::new(3, 37, 2).to_string(), "3.37.2");
+
+        let plain = Table::new_default("plain".to_string()).add_column(Column::new_default("a".to_string()));
+        let strict = Table::new_default("strict".to_string())
+            .add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()).set_pk(Some(PrimaryKey::default())))
+            .set_without_rowid(true)
+            .set_strict(true);
+        let idx = Index::new_default("idx".to_string(), "plain".to_string()).add_column("a".to_string(), Order::Ascending);
+
+        let schema = Schema::new().add_table(plain.clone()).add_index(idx.clone());
+        assert_eq!(schema.min_required_version(), SQLiteVersion::new(3, 0, 0));
+        assert_eq!(schema.check_version_compatibility(SQLiteVersion::new(3, 0, 0)), Ok(()));
+
+        let schema = schema.add_table(strict.clone()).add_index(idx.clone().set_name("partial".to_string()).set_where_expr(Some("a > 0".to_string())));
+        assert_eq!(schema.min_required_version(), SQLiteVersion::new(3, 37, 0));
+        assert_eq!(schema.check_version_compatibility(SQLiteVersion::new(3, 37, 0)), Ok(()));
+        let warnings = schema.check_version_compatibility(SQLiteVersion::new(3, 8, 1)).unwrap_err();
+        assert_eq!(warnings.iter().map(CompatibilityWarning::feature).collect::<Vec<&str>>(), vec!["WITHOUT ROWID", "STRICT"]);
+        assert_eq!(warnings[1].to_string(), "STRICT requires SQLite 3.37.0");
+
+        assert_eq!(strict.check_version_compatibility(SQLiteVersion::new(3, 36, 0)), Err(vec![CompatibilityWarning::STRICT]));
+        assert_eq!(plain.check_version_compatibility(SQLiteVersion::new(3, 0, 0)), Ok(()));
+        assert_eq!(DropTable::new("plain".to_string()).check_version_compatibility(SQLiteVersion::new(3, 0, 0)), Ok(()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_schema_diff() -> Result<()> {
+        let kept = Table::new_default("kept".to_string()).add_column(Column::new_default("a".to_string()));
+        let changed = Table::new_default("changed".to_string()).add_column(Column::new_default("a".to_string()));
+        let removed = Table::new_default("removed".to_string()).add_column(Column::new_default("a".to_string()));
+        let added = Table::new_default("added".to_string()).add_column(Column::new_default("a".to_string()));
+        let changed_new = changed.clone().add_column(Column::new_default("b".to_string()));
+
+        let old = Schema::new().add_table(kept.clone()).add_table(changed.clone()).add_table(removed.clone());
+        let new = Schema::new().add_table(added.clone()).add_table(changed_new.clone()).add_table(kept.clone());
+
+        assert!(old.diff(&old).is_empty());
+        assert!(old.diff(&old.clone().add_index(Index::new_default("idx".to_string(), "kept".to_string()))).is_empty());
+
+        let diff = old.diff(&new);
+        assert!(!diff.is_empty());
+        assert_eq!(diff.added_tables(), std::slice::from_ref(&added));
+        assert_eq!(diff.removed_tables(), std::slice::from_ref(&removed));
+        assert_eq!(diff.modified_tables(), &[(changed.clone(), changed_new.clone())]);
+
+        let reverse = new.diff(&old);
+        assert_eq!(reverse.added_tables(), &[removed]);
+        assert_eq!(reverse.removed_tables(), &[added]);
+        assert_eq!(reverse.modified_tables(), &[(changed_new, changed)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_schema_table_lookup_case_insensitive() -> Result<()> {
+        let users = Table::new_default("Users".to_string()).add_column(Column::new_typed(SQLiteType::Integer, "id".to_string()));
+        let mut schema = Schema::new()
+            .add_table(users.clone())
+            .add_index(Index::new_default("idx".to_string(), "USERS".to_string()).add_column("ID".to_string(), Order::Ascending));
+        assert_eq!(schema.get_table("users"), Some(&users));
+        assert_eq!(schema.validate(), Ok(()));
+
+        // only the case of the name changed, which is no change to SQLite
+        let renamed = Schema::new().add_table(users.clone().set_name("users".to_string()));
+        assert!(schema.diff(&renamed).is_empty());
+        let altered = Schema::new().add_table(users.clone().set_name("users".to_string()).add_column(Column::new_default("name".to_string())));
+        assert_eq!(schema.diff(&altered).to_alter_statements(false)?, vec!["ALTER TABLE users ADD COLUMN name BLOB;".to_string()]);
+
+        assert_eq!(schema.remove_table("USERS"), Some(users));
+        assert_eq!(schema.get_table("Users"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_schema_diff_alter() -> Result<()> {
+        let base = Table::new_default("base".to_string()).add_column(Column::new_typed(SQLiteType::Integer, "a".to_string())).add_column(Column::new_typed(SQLiteType::Text, "b".to_string()));
+        let old = Schema::new().add_table(base.clone()).add_table(Table::new_default("removed".to_string()).add_column(Column::new_default("a".to_string())));
+        let mut altered = base.clone();
+        altered.remove_column("b");
+        let new = Schema::new()
+            .add_table(altered.add_column(Column::new_typed(SQLiteType::Real, "c".to_string()).set_default(Some(DefaultValue::Real(1.5)))))
+            .add_table(Table::new_default("added".to_string()).add_column(Column::new_typed(SQLiteType::Integer, "a".to_string())));
+
+        assert_eq!(old.diff(&old).to_alter_statements(false)?, Vec::<String>::new());
+        assert_eq!(old.diff(&new).to_alter_statements(false)?, vec![
+            "CREATE TABLE added (a INTEGER);".to_string(),
+            "ALTER TABLE base ADD COLUMN c REAL DEFAULT 1.5;".to_string(),
+            "ALTER TABLE base DROP COLUMN b;".to_string(),
+            "DROP TABLE removed;".to_string(),
+        ]);
+        assert_eq!(old.diff(&new).to_alter_statements(true)?[0], "CREATE TABLE IF NOT EXISTS added (a INTEGER);");
+        assert_eq!(old.diff(&new).to_alter_statements(true)?[3], "DROP TABLE IF EXISTS removed;");
+        assert!(old.diff(&new).check_version_compatibility(SQLiteVersion::new(3, 35, 0)).is_ok());
+        assert_eq!(old.diff(&new).check_version_compatibility(SQLiteVersion::new(3, 34, 1)), Err(vec![CompatibilityWarning::DROP_COLUMN]));
+        assert_eq!(old.diff(&new).check_version_compatibility(SQLiteVersion::new(3, 1, 0)), Err(vec![CompatibilityWarning::ADD_COLUMN, CompatibilityWarning::DROP_COLUMN]));
+        assert!(old.diff(&old).check_version_compatibility(SQLiteVersion::new(3, 0, 0)).is_ok());
+
+        let retyped = Schema::new().add_table(Table::new_default("base".to_string()).add_column(Column::new_typed(SQLiteType::Text, "a".to_string())).add_column(Column::new_typed(SQLiteType::Text, "b".to_string())));
+        assert_eq!(old.diff(&retyped).to_alter_statements(false), Err(Error::UnsupportedAlteration("base".to_string())));
+        let strict = Schema::new().add_table(base.clone().set_strict(true));
+        assert_eq!(old.diff(&strict).to_alter_statements(false), Err(Error::UnsupportedAlteration("base".to_string())));
+        let unique = Schema::new().add_table(base.clone().add_column(Column::new_typed(SQLiteType::Integer, "u".to_string()).set_unique(Some(Unique::default()))));
+        assert_eq!(old.diff(&unique).to_alter_statements(false), Err(Error::UnsupportedAlteration("base".to_string())));
+
+        let add = |col: Column| Schema::new().add_table(base.clone().add_column(col));
+        let not_null = Column::new_typed(SQLiteType::Integer, "n".to_string()).set_not_null(Some(NotNull::default()));
+        assert_eq!(old.diff(&add(not_null.clone())).to_alter_statements(false), Err(Error::UnsupportedAlteration("base".to_string())));
+        assert_eq!(old.diff(&add(not_null.clone().set_default(Some(DefaultValue::Null)))).to_alter_statements(false), Err(Error::UnsupportedAlteration("base".to_string())));
+        assert_eq!(old.diff(&add(not_null.set_default(Some(DefaultValue::Integer(0))))).to_alter_statements(false)?[0], "ALTER TABLE base ADD COLUMN n INTEGER NOT NULL ON CONFLICT ABORT DEFAULT 0;");
+        for default in [DefaultValue::CurrentTime, DefaultValue::CurrentDate, DefaultValue::CurrentTimestamp, DefaultValue::Expr("1 + 2".to_string())] {
+            let col = Column::new_typed(SQLiteType::Text, "t".to_string()).set_default(Some(default));
+            assert_eq!(old.diff(&add(col)).to_alter_statements(false), Err(Error::UnsupportedAlteration("base".to_string())));
+        }
+        let fk = Column::new_typed(SQLiteType::Integer, "f".to_string()).set_fk(Some(ForeignKey::new_default("base".to_string(), "a".to_string())));
+        assert_eq!(old.diff(&add(fk.clone().set_default(Some(DefaultValue::Integer(1))))).to_alter_statements(false), Err(Error::UnsupportedAlteration("base".to_string())));
+        assert!(old.diff(&add(fk.set_default(Some(DefaultValue::Null)))).to_alter_statements(false).is_ok());
+
+        let dropped = Schema::new().add_table(Table::new_default("base".to_string()).add_column(Column::new_typed(SQLiteType::Integer, "a".to_string())));
+        let drop_from = |tbl: Table| Schema::new().add_table(tbl);
+        assert!(drop_from(base.clone()).diff(&dropped).to_alter_statements(false).is_ok());
+        let b = Column::new_typed(SQLiteType::Text, "b".to_string());
+        let base_a = Table::new_default("base".to_string()).add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()));
+        for tbl in [
+            base_a.clone().add_column(b.clone().set_pk(Some(PrimaryKey::default()))),
+            base_a.clone().add_column(b.clone().set_unique(Some(Unique::default()))),
+            base_a.clone().add_column(b.clone().set_fk(Some(ForeignKey::new_default("base".to_string(), "a".to_string())))),
+        ] {
+            assert_eq!(drop_from(tbl).diff(&dropped).to_alter_statements(false), Err(Error::UnsupportedAlteration("base".to_string())));
+        }
+        let indexed = drop_from(base.clone()).add_index(Index::new_default("idx".to_string(), "base".to_string()).add_column("b".to_string(), Order::Ascending));
+        assert_eq!(indexed.diff(&dropped).to_alter_statements(false), Err(Error::UnsupportedAlteration("base".to_string())));
+        let referenced = drop_from(base).add_table(Table::new_default("child".to_string())
+            .add_column(Column::new_typed(SQLiteType::Text, "b".to_string()).set_fk(Some(ForeignKey::new_default("base".to_string(), "b".to_string())))));
+        assert_eq!(referenced.diff(&dropped.add_table(referenced.tables()[1].clone())).to_alter_statements(false), Err(Error::UnsupportedAlteration("base".to_string())));
+        Ok(())
+    }
+
+    #[test]
+    fn test_schema_diff_alter_quoted() -> Result<()> {
+        let order = Table::new_default("order".to_string()).add_column(Column::new_typed(SQLiteType::Integer, "my col".to_string()));
+        let old = Schema::new().add_table(order.clone()).add_table(Table::new_default("group".to_string()).add_column(Column::new_default("a".to_string()))).auto_quote();
+        let new = Schema::new().add_table(order.add_column(Column::new_typed(SQLiteType::Text, "select".to_string()))).auto_quote();
+        assert_eq!(old.diff(&new).to_alter_statements(false)?, vec![
+            r#"ALTER TABLE "order" ADD COLUMN "select" TEXT;"#.to_string(),
+            r#"DROP TABLE "group";"#.to_string(),
+        ]);
+        assert_eq!(new.diff(&old).to_alter_statements(false)?, vec![
+            r#"CREATE TABLE "group" (a BLOB);"#.to_string(),
+            r#"ALTER TABLE "order" DROP COLUMN "select";"#.to_string(),
+        ]);
+        let named = |schema: Schema| schema.set_name("main".to_string());
+        assert_eq!(named(old.clone()).diff(&named(new.clone())).to_alter_statements(false)?, vec![
+            r#"ALTER TABLE main."order" ADD COLUMN "select" TEXT;"#.to_string(),
+            r#"DROP TABLE main."group";"#.to_string(),
+        ]);
+        assert_eq!(named(new).diff(&named(old)).to_alter_statements(false)?, vec![
+            r#"CREATE TABLE main."group" (a BLOB);"#.to_string(),
+            r#"ALTER TABLE main."order" DROP COLUMN "select";"#.to_string(),
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_schema_validate() -> Result<()> {
+        let parent = Table::new_default("parent".to_string())
+            .add_column(Column::new_typed(SQLiteType::Integer, "id".to_string()).set_pk(Some(PrimaryKey::default())));
+        let child = Table::new_default("child".to_string())
+            .add_column(Column::new_typed(SQLiteType::Integer, "parent_id".to_string()).set_fk(Some(ForeignKey::new_default("parent".to_string(), "id".to_string()))));
+        assert_eq!(Schema::new().add_table(parent.clone()).add_table(child.clone()).validate(), Ok(()));
+
+        assert_eq!(Schema::new().validate(), Err(vec![Error::SchemaWithoutTables]));
+
+        let broken_child = child
+            .add_column(Column::new_typed(SQLiteType::Integer, "other_id".to_string()).set_fk(Some(ForeignKey::new_default("other".to_string(), "id".to_string()))))
+            .add_column(Column::new_typed(SQLiteType::Integer, "parent_name".to_string()).set_fk(Some(ForeignKey::new_default("parent".to_string(), "name".to_string()))));
+        let schema = Schema::new().add_table(parent).add_table(broken_child).add_table(Table::new_default("empty".to_string()));
+        assert_eq!(schema.validate(), Err(vec![
+            Error::UnresolvedForeignKey { from_table: "child".to_string(), from_column: "other_id".to_string(), to_table: "other".to_string(), to_column: "id".to_string() },
+            Error::UnresolvedForeignKey { from_table: "child".to_string(), from_column: "parent_name".to_string(), to_table: "parent".to_string(), to_column: "name".to_string() },
+            Error::NoColumns,
+        ]));
+
+        let schema = Schema::new()
+            .add_table(Table::new_default("test".to_string()).add_column(Column::new_typed(SQLiteType::Integer, "a".to_string())))
+            .add_index(Index::new_default("idx".to_string(), "test".to_string()).add_column("b".to_string(), Order::Ascending))
+            .add_index(Index::new_default("idx2".to_string(), "missing".to_string()).add_column("a".to_string(), Order::Ascending));
+        assert_eq!(schema.validate(), Err(vec![Error::ColumnNotFound("b".to_string()), Error::TableNotFound("missing".to_string())]));
+
+        let tbl = Table::new_default("test".to_string())
+            .add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()))
+            .add_unique_constraint(vec!["a".to_string(), "b".to_string()], OnConflict::Abort);
+        assert_eq!(tbl.part_len(), Err(Error::ColumnNotFound("b".to_string())));
+        Ok(())
+    }
+
+    #[cfg(feature = "xml-config")]
+    mod xml_tests {
+        use super::*;
+
+        #[test]
+        fn test_serialize_deserialize() -> Result<()> {
+            let tbl = Table::new_default("TestName".to_string()).add_column(Column::new_default("TestCol".to_string()));
+            let tbl2  = tbl.clone().set_name("TestName2".to_string());
+            let schema = Schema::new().add_table(tbl).add_table(tbl2);
+            // todo: this is bullshit
+            let serialized: &'static str = Box::leak(xml::to_string(&schema)?.into_boxed_str());
+            println!("Serialized XML: \n{}", serialized);
+            let deserialized: Schema = xml::from_str(serialized)?;
+            assert_eq!(schema, deserialized);
+            Ok(())
+        }
+
+        #[test]
+        fn test_from_str_namespaced() -> Result<()> {
+            let raw: &str = r#"<?xml version="1.0"?><schema xmlns="https://crates.io/crates/sqlayout"><table name="test"><column name="a" type="text"/></table></schema>"#;
+            assert_eq!(from_str_namespaced(raw)?, from_str::<Schema>(raw)?);
+
+            let wrong: String = raw.replace("https://crates.io/crates/sqlayout", "https://example.com");
+            assert_eq!(
+                from_str_namespaced(wrong.as_str()).unwrap_err().to_string(),
+                "expected namespace 'https://crates.io/crates/sqlayout', got Some(\"https://example.com\")"
+            );
+            assert!(from_str::<Schema>(wrong.as_str()).is_ok());
+
+            let missing: String = raw.replace(r#" xmlns="https://crates.io/crates/sqlayout""#, "");
+            assert!(matches!(from_str_namespaced(missing.as_str()), Err(DeError::Custom(_))));
+            assert!(matches!(from_str_namespaced(""), Err(DeError::UnexpectedEof)));
+            Ok(())
+        }
+
+        #[test]
+        fn test_serialize_deserialize_fk() -> Result<()> {
+            let col = Column::new_typed(SQLiteType::Integer, "user".to_string())
+                .set_fk(Some(ForeignKey::references("users", "id").with_on_delete(FKOnAction::Cascade).with_on_update(FKOnAction::Restrict)));
+            let serialized: String = xml::to_string(&col)?;
+            assert!(serialized.contains(r#"foreign_table="users""#));
+            assert_eq!(from_str::<Column>(serialized.as_str())?, col);
+            Ok(())
+        }
+
+        #[test]
+        fn test_serialize_deserialize_constraint_name() -> Result<()> {
+            let col = Column::new_typed(SQLiteType::Integer, "a".to_string())
+                .set_pk_named(Some(PrimaryKey::default().with_name("pk_a".to_string())))
+                .set_check_named(Some(Check::new("a > 0".to_string()).with_name("chk a".to_string()).set_name_quote(Quote::DoubleQuote)));
+            let serialized: String = xml::to_string(&col)?;
+            assert!(serialized.contains(r#"constraint_name="pk_a""#));
+            assert!(serialized.contains(r#"constraint_quote="double_quote""#));
+            assert_eq!(from_str::<Column>(serialized.as_str())?, col);
+            Ok(())
+        }
+
+        #[test]
+        fn test_from_xml_reader() -> Result<()> {
+            let tbl = Table::new_default("test".to_string()).add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()));
+            let schema = Schema::new().add_table(tbl.clone());
+
+            let xml: String = xml::to_string(&schema)?;
+            assert_eq!(Schema::from_xml_reader(xml.as_bytes())?, schema);
+            let xml: String = xml::to_string_with_root("table", &tbl)?;
+            assert_eq!(Table::from_xml_reader(std::io::BufReader::new(xml.as_bytes()))?, tbl);
+            assert!(Schema::from_xml_reader("<schema>".as_bytes()).is_err());
+            Ok(())
+        }
+
+        #[test]
+        fn test_serialize_deserialize_index() -> Result<()> {
+            let tbl = Table::new_default("test".to_string()).add_column(Column::new_default("a".to_string())).add_column(Column::new_default("b".to_string()));
+            let schema = Schema::new().add_table(tbl).add_index(Index::new_default("idx".to_string(), "test".to_string())
+                .add_collated_column("a".to_string(), Order::Descending, Collation::NoCase)
+                .add_column("b".to_string(), Order::Ascending)
+                .set_unique(true)
+                .set_where("a IS NOT NULL".to_string()));
+            let serialized: String = xml::to_string(&schema)?;
+            let deserialized: Schema = xml::from_str(serialized.as_str())?;
+            assert_eq!(schema, deserialized);
+
+            let raw: &str = r#"<schema><table name="test"><column name="a" type="text"/></table><index name="idx" table="test" unique="true"><column name="a"/></index></schema>"#;
+            let deserialized: Schema = xml::from_str(raw)?;
+            assert_eq!(deserialized.indexes(), &[Index::new_default("idx".to_string(), "test".to_string()).add_column("a".to_string(), Order::Ascending).set_unique(true)]);
+            Ok(())
+        }
+
+        #[test]
+        fn test_serialize_deserialize_constraints_pragmas() -> Result<()> {
+            let schema = constrained_schema();
+            let serialized: String = xml::to_string(&schema)?;
+            assert!(serialized.contains(r#"<constraint type="unique" on_conflict="replace"><column>a</column><column>b</column></constraint>"#));
+            assert!(serialized.contains(r#"<pragma name="cache_size"><integer>-2000</integer></pragma>"#));
+            assert_eq!(from_str::<Schema>(serialized.as_str())?, schema);
+
+            let raw: &str = r#"<table name="test"><column name="a" type="integer"/><constraint type="foreign_key" foreign_table="other"><column>a</column><foreign_column>b</foreign_column></constraint></table>"#;
+            assert_eq!(from_str::<Table>(raw)?.constraints(), &[TableConstraint::CompositeForeignKey(CompositeForeignKey::new_default(vec!["a".to_string()], "other".to_string(), vec!["b".to_string()]))]);
+            let raw: &str = r#"<table name="test"><column name="a" type="integer"/><constraint type="foreign_key"><column>a</column></constraint></table>"#;
+            assert!(from_str::<Table>(raw).is_err());
+            Ok(())
+        }
+
+        #[test]
+        fn test_serialize_deserialize_comment() -> Result<()> {
+            let tbl = Table::new_default("test".to_string())
+                .add_column(Column::new_default("a".to_string()).set_comment(Some("column".to_string())))
+                .set_comment(Some("table".to_string()));
+            let schema = Schema::new().add_table(tbl).set_comment(Some("schema".to_string()));
+            let serialized: String = xml::to_string(&schema)?;
+            let deserialized: Schema = xml::from_str(serialized.as_str())?;
+            assert_eq!(schema, deserialized);
+
+            let raw: &str = r#"<table name="test" comment="table"><column name="test" type="text" comment="column"/></table>"#;
+            let deserialized: Table = xml::from_str(raw)?;
+            assert_eq!(deserialized.comment(), Some("table"));
+            assert_eq!(deserialized.get_column("test").and_then(Column::comment), Some("column"));
+            Ok(())
+        }
+
+        #[test]
+        fn test_serialize_deserialize_temp() -> Result<()> {
+            let tbl = Table::new_default("TestName".to_string()).add_column(Column::new_default("TestCol".to_string())).set_temp(true);
+            let schema = Schema::new().add_table(tbl.clone()).add_table(tbl.set_name("TestName2".to_string()).set_temp(false));
+            let serialized: &'static str = Box::leak(xml::to_string(&schema)?.into_boxed_str());
+            let deserialized: Schema = xml::from_str(serialized)?;
+            assert_eq!(schema, deserialized);
+
+            let raw: &str = r#"<table name="test" temp="true"><column name="test" type="text"/></table>"#;
+            let deserialized: Table = xml::from_str(raw)?;
+            assert!(deserialized.is_temp());
+            Ok(())
+        }
+
+        #[test]
+        fn test_serialize_deserialize_deferrable() -> Result<()> {
+            let mut tbl = Table::new_default("TestName".to_string()).add_column(Column::new_default("TestCol".to_string()));
+            for (num, defer) in FKDeferrable::possibilities(false).into_iter().enumerate() {
+                tbl = tbl.add_column(Column::new_default(format!("TestCol{}", num)).set_fk(Some(ForeignKey::new_default("TestName".to_string(), "TestCol".to_string()).set_deferrable(*defer))));
+            }
+            let schema = Schema::new().add_table(tbl);
+            let serialized: &'static str = Box::leak(xml::to_string(&schema)?.into_boxed_str());
+            let deserialized: Schema = xml::from_str(serialized)?;
+            assert_eq!(schema, deserialized);
+
+            // legacy boolean values
+            for (raw_defer, defer) in [("true", FKDeferrable::InitiallyDeferred), ("false", FKDeferrable::NotDeferrable), ("initially_immediate", FKDeferrable::InitiallyImmediate)] {
+                let raw: String = format!(r#"<column name="test" type="integer"><fk foreign_table="test" foreign_column="test" deferrable="{}"/></column>"#, raw_defer);
+                let deserialized: Column = xml::from_str(raw.as_str())?;
+                assert_eq!(deserialized, Column::new_typed(SQLiteType::Integer, "test".to_string()).set_fk(Some(ForeignKey::new_default("test".to_string(), "test".to_string()).set_deferrable(defer))));
+            }
+            Ok(())
+        }
+
+        #[test]
+        fn test_serialize_deserialize_check() -> Result<()> {
+            let schema = Schema::new().add_table(Table::new_default("TestName".to_string()).add_column(Column::new_default("TestCol".to_string()).set_check(Some(Check::new("TestCol > 0".to_string())))));
+            let serialized: &'static str = Box::leak(xml::to_string(&schema)?.into_boxed_str());
+            let deserialized: Schema = xml::from_str(serialized)?;
+            assert_eq!(schema, deserialized);
+            Ok(())
+        }
+
+        #[test]
+        fn test_serialize_deserialize_collation() -> Result<()> {
+            let mut tbl = Table::new_default("TestName".to_string());
+            for (num, collation) in Collation::possibilities(false).into_iter().enumerate() {
+                tbl = tbl.add_column(Column::new_default(format!("TestCol{}", num)).set_collation(Some(*collation)));
+            }
+            let schema = Schema::new().add_table(tbl);
+            let serialized: &'static str = Box::leak(xml::to_string(&schema)?.into_boxed_str());
+            let deserialized: Schema = xml::from_str(serialized)?;
+            assert_eq!(schema, deserialized);
+
+            let raw: &str = r#"<schema xmlns="https://crates.io/crates/sqlayout"><table name="test"><column name="test" type="text"><collation><nocase/></collation></column></table></schema>"#;
+            let deserialized: Schema = xml::from_str(raw)?;
+            assert_eq!(deserialized, Schema::new().add_table(Table::new_default("test".to_string()).add_column(Column::new_typed(SQLiteType::Text, "test".to_string()).set_collation(Some(Collation::NoCase)))));
+            Ok(())
+        }
+
+        #[test]
+        fn test_serialize_deserialize_default() -> Result<()> {
+            let mut tbl = Table::new_default("TestName".to_string());
+            for (num, default) in DefaultValue::possibilities(false).into_iter().enumerate() {
+                tbl = tbl.add_column(Column::new_default(format!("TestCol{}", num)).set_default(Some(*default)));
+            }
+            let schema = Schema::new().add_table(tbl);
+            let serialized: &'static str = Box::leak(xml::to_string(&schema)?.into_boxed_str());
+            let deserialized: Schema = xml::from_str(serialized)?;
+            assert_eq!(schema, deserialized);
+
+            let raw: &str = r#"<schema xmlns="https://crates.io/crates/sqlayout"><table name="test"><column name="test" type="text"><default><text>unknown</text></default></column></table></schema>"#;
+            let deserialized: Schema = xml::from_str(raw)?;
+            assert_eq!(deserialized, Schema::new().add_table(Table::new_default("test".to_string()).add_column(Column::new_typed(SQLiteType::Text, "test".to_string()).set_default(Some(DefaultValue::Text("unknown".to_string()))))));
+            Ok(())
+        }
+
+        #[test]
+        fn some_test() -> Result<()> {
+            let raw: &str = r#"
+<?xml version="1.0" encoding="UTF-8" standalone="yes" ?>
+<schema xmlns="https://crates.io/crates/sqlayout">
+
+  <!-- Card data -->
+  <table name="updates" strict="true">
+    <column name="ID" type="integer">
+      <pk/>
+      <not_null/>
+    </column>
+    <column name="timestamp" type="integer">
+      <not_null/>
+    </column>
+    <column name="guid" type="text">
+      <not_null/>
+      <unique/>
+    </column>
+  </table>
+
+  <table name="migrations" strict="true">
+    <column name="ID" type="integer">
+      <pk/>
+      <not_null/>
+    </column>
+    <column name="timestamp" type="integer">
+      <not_null/>
+    </column>
+    <column name="GUID" type="text">
+      <not_null/>
+      <unique/>
+    </column>
+  </table>
+
+  <table name="card_data" strict="true">
+    <column name="ID" type="integer">
+      <pk/>
+      <not_null/>
+    </column>
+  </table>
+
+  <!-- Collection Data -->
+  <table name="card_location" strict="true">
+    <column name="ID" type="integer">
+      <pk/>
       <not_null/>
     </column>
     <column name="name" type="text">
@@ -1483,40 +8537,1219 @@ mod tests {
     <column name="description" type="text"/>
   </table>
 
-  <table name="card_collection" strict="true">
-    <column name="ID" type="integer">
-      <pk/>
-      <not_null/>
-    </column>
-    <column name="card_ID" type="integer">
-      <fk foreign_table="card_data" foreign_column="ID"/>
-      <not_null/>
-    </column>
-    <column name="count" type="integer">
-      <not_null/>
-    </column>
-    <column name="finish" type="integer">
-      <!-- enum -->
-      <not_null/>
-    </column>
-    <column name="condition" type="integer">
-      <!-- enum -->
-    </column>
-    <column name="location" type="integer">
-      <fk foreign_table="card_location" foreign_column="ID"/>
-      <not_null/>
-    </column>
-    <column name="location_page" type="integer"/>
-  </table>
-</schema>
+  <table name="card_collection" strict="true">
+    <column name="ID" type="integer">
+      <pk/>
+      <not_null/>
+    </column>
+    <column name="card_ID" type="integer">
+      <fk foreign_table="card_data" foreign_column="ID"/>
+      <not_null/>
+    </column>
+    <column name="count" type="integer">
+      <not_null/>
+    </column>
+    <column name="finish" type="integer">
+      <!-- enum -->
+      <not_null/>
+    </column>
+    <column name="condition" type="integer">
+      <!-- enum -->
+    </column>
+    <column name="location" type="integer">
+      <fk foreign_table="card_location" foreign_column="ID"/>
+      <not_null/>
+    </column>
+    <column name="location_page" type="integer"/>
+  </table>
+</schema>
+"#;
+            let _: Schema = xml::from_str(raw)?;
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "xml-config")]
+    mod xml_file_tests {
+        use super::*;
+        use std::path::PathBuf;
+
+        fn temp_path(name: &str) -> PathBuf {
+            std::env::temp_dir().join(format!("sqlayout_{}_{}.xml", std::process::id(), name))
+        }
+
+        #[test]
+        fn test_schema_xml_file() -> Result<()> {
+            let tbl = Table::new_default("TestName".to_string()).add_column(Column::new_default("TestCol".to_string()));
+            let schema = Schema::new().add_table(tbl.clone()).add_table(tbl.clone().set_name("Other".to_string()));
+            let path: PathBuf = temp_path("schema");
+            schema.to_xml_file(&path)?;
+            let deserialized = Schema::from_xml_file(&path);
+            std::fs::remove_file(&path)?;
+            assert_eq!(deserialized?, schema);
+
+            let path: PathBuf = temp_path("table");
+            tbl.to_xml_file(&path)?;
+            let deserialized = Table::from_xml_file(&path);
+            std::fs::remove_file(&path)?;
+            assert_eq!(deserialized?, tbl);
+
+            assert!(matches!(Schema::from_xml_file(temp_path("missing")), Err(DeError::InvalidXml(quick_xml::Error::Io(_)))));
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "json-config")]
+    mod json_tests {
+        use super::*;
+
+        fn full_table() -> Table {
+            Table::new_default("TestName".to_string())
+                .add_column(Column::new_typed(SQLiteType::Integer, "id".to_string()).set_pk(Some(PrimaryKey::new(Order::Descending, OnConflict::Replace, true))))
+                .add_column(Column::new_typed(SQLiteType::Text, "name".to_string())
+                    .set_unique_named(Some(Unique::default().with_name("uq name".to_string()).set_name_quote(Quote::DoubleQuote)))
+                    .set_check(Some(Check::new("length(name) > 0".to_string())))
+                    .set_default(Some(DefaultValue::Text("unknown".to_string())))
+                    .set_collation(Some(Collation::NoCase)))
+                .add_column(Column::new_typed(SQLiteType::Integer, "parent".to_string())
+                    .set_fk(Some(ForeignKey::new_default("TestName".to_string(), "id".to_string()).set_deferrable(FKDeferrable::InitiallyDeferred))))
+                .set_temp(true)
+        }
+
+        #[test]
+        fn test_error_json() -> Result<()> {
+            assert_eq!(serde_json::to_string(&Error::EmptyColumnName)?, r#"{"type":"EmptyColumnName"}"#);
+            assert_eq!(serde_json::to_string(&Error::DuplicateTableName("test".to_string()))?, r#"{"type":"DuplicateTableName","detail":"test"}"#);
+            assert_eq!(serde_json::to_string(&Error::BufferTooSmall { needed: 2, available: 1 })?, r#"{"type":"BufferTooSmall","detail":{"needed":2,"available":1}}"#);
+            assert_eq!(serde_json::to_string(&Error::FmtError(fmt::Error))?, r#"{"type":"FmtError","detail":"an error occurred when formatting an argument"}"#);
+
+            for err in [
+                Error::EmptyColumnName,
+                Error::DuplicateTableName("test".to_string()),
+                Error::StrictTableInvalidType { column: "a".to_string(), typ: SQLiteType::Numeric },
+                Error::UnresolvedForeignKey { from_table: "a".to_string(), from_column: "b".to_string(), to_table: "c".to_string(), to_column: "d".to_string() },
+            ] {
+                assert_eq!(serde_json::from_str::<Error>(&serde_json::to_string(&err)?)?, err);
+            }
+            assert!(serde_json::from_str::<Error>(r#"{"type":"FmtError","detail":"error"}"#).is_err());
+            Ok(())
+        }
+
+        #[test]
+        fn test_table_json() -> Result<()> {
+            let tbl = full_table();
+            let deserialized = Table::from_json(&tbl.to_json()?)?;
+            assert_eq!(tbl, deserialized);
+            Ok(())
+        }
+
+        #[test]
+        fn test_schema_json() -> Result<()> {
+            let schema = Schema::new().add_table(full_table()).add_table(full_table().set_name("Other".to_string()).set_temp(false));
+            let deserialized = Schema::from_json(&schema.to_json()?)?;
+            assert_eq!(schema, deserialized);
+
+            let schema = schema.add_index(Index::new_default("idx".to_string(), "Other".to_string())
+                .add_collated_column("name".to_string(), Order::Descending, Collation::NoCase)
+                .set_where("id > 0".to_string()));
+            assert_eq!(Schema::from_json(&schema.to_json()?)?, schema);
+
+            let schema = constrained_schema();
+            assert_eq!(Schema::from_json(&schema.to_json()?)?, schema);
+            Ok(())
+        }
+
+        #[test]
+        fn test_table_json_raw() -> Result<()> {
+            let raw: &str = r#"{"name": "test", "column": [{"type": "integer", "name": "id", "pk": {"autoincrement": true}}], "strict": true}"#;
+            let tbl = Table::from_json(raw)?;
+            assert_eq!(tbl, Table::new("test".to_string(), vec![
+                Column::new_typed(SQLiteType::Integer, "id".to_string()).set_pk(Some(PrimaryKey::new(Order::Ascending, OnConflict::Abort, true))),
+            ], false, true));
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "toml-config")]
+    mod toml_tests {
+        use super::*;
+
+        #[test]
+        fn test_schema_toml() -> Result<()> {
+            let tbl = Table::new_default("TestName".to_string())
+                .add_column(Column::new_typed(SQLiteType::Integer, "id".to_string()).set_pk(Some(PrimaryKey::new(Order::Descending, OnConflict::Replace, true))))
+                .add_column(Column::new_typed(SQLiteType::Text, "name".to_string())
+                    .set_unique(Some(Unique::default()))
+                    .set_check(Some(Check::new("length(name) > 0".to_string())))
+                    .set_default(Some(DefaultValue::Integer(3)))
+                    .set_collation(Some(Collation::Custom("custom".to_string()))))
+                .add_column(Column::new_typed(SQLiteType::Integer, "parent".to_string())
+                    .set_fk(Some(ForeignKey::new_default("TestName".to_string(), "id".to_string()).set_deferrable(FKDeferrable::InitiallyDeferred))));
+            let schema = Schema::new().add_table(tbl.clone()).add_table(tbl.clone().set_name("Other".to_string()).set_temp(true))
+                .add_index(Index::new_default("idx".to_string(), "Other".to_string()).add_column("name".to_string(), Order::Descending).set_unique(true));
+
+            let serialized = schema.to_toml()?;
+            assert!(serialized.contains("[[schema.table]]"));
+            assert!(serialized.contains("[[schema.index]]"));
+            assert_eq!(Schema::from_toml(&serialized)?, schema);
+            assert_eq!(Table::from_toml(&tbl.to_toml()?)?, tbl);
+
+            let schema = constrained_schema();
+            let serialized = schema.to_toml()?;
+            assert!(serialized.contains("[[schema.table.constraint]]"));
+            assert!(serialized.contains("[[schema.pragma]]"));
+            assert_eq!(Schema::from_toml(&serialized)?, schema);
+            Ok(())
+        }
+
+        #[test]
+        fn test_schema_toml_fixture() -> Result<()> {
+            let raw: &str = r#"
+[[schema.table]]
+name = "parent"
+strict = true
+
+[[schema.table.column]]
+name = "id"
+type = "integer"
+pk = { autoincrement = true }
+
+[[schema.table]]
+name = "child"
+
+[[schema.table.column]]
+name = "parent_id"
+type = "integer"
+fk = { foreign_table = "parent", foreign_column = "id", on_delete = "Cascade" }
+
+[[schema.table.column]]
+name = "label"
+type = "text"
+default = { text = "none" }
 "#;
-            let _: Schema = quick_xml::de::from_str(raw)?;
+            let schema = Schema::from_toml(raw)?;
+            assert_eq!(schema, Schema::new()
+                .add_table(Table::new("parent".to_string(), vec![
+                    Column::new_typed(SQLiteType::Integer, "id".to_string()).set_pk(Some(PrimaryKey::new(Order::Ascending, OnConflict::Abort, true))),
+                ], false, true))
+                .add_table(Table::new_default("child".to_string())
+                    .add_column(Column::new_typed(SQLiteType::Integer, "parent_id".to_string()).set_fk(Some(ForeignKey::new("parent".to_string(), "id".to_string(), Some(FKOnAction::Cascade), None, FKDeferrable::NotDeferrable))))
+                    .add_column(Column::new_typed(SQLiteType::Text, "label".to_string()).set_default(Some(DefaultValue::Text("none".to_string()))))));
+            assert_eq!(Schema::from_toml(&schema.to_toml()?)?, schema);
+            assert_eq!(schema.validate(), Ok(()));
             Ok(())
         }
     }
 
     #[cfg(feature = "rusqlite")]
     mod rusqlite {
-        // todo
+        use super::*;
+        use std::error::Error as StdError;
+
+        #[test]
+        fn test_partial_index() -> Result<()> {
+            let mut schema = Schema::new()
+                .add_table(Table::new_default("t".to_string()).add_column(Column::new_typed(SQLiteType::Integer, "col".to_string())))
+                .add_index(Index::new_default("idx".to_string(), "t".to_string())
+                    .add_column("col".to_string(), Order::Ascending)
+                    .set_unique(true)
+                    .set_where("col IS NOT NULL".to_string()));
+            assert_eq!(schema.indexes()[0].clone().build(false, false)?, "CREATE UNIQUE INDEX idx ON t (col ASC) WHERE col IS NOT NULL;");
+
+            let conn: Connection = Connection::open_in_memory()?;
+            schema.execute(true, false, &conn)?;
+            assert!(conn.query_row("SELECT partial FROM pragma_index_list('t');", (), |row| row.get::<_, bool>(0))?);
+            // NULLs are not part of the Index, so they are not unique-checked
+            conn.execute_batch("INSERT INTO t (col) VALUES (NULL), (NULL), (1);")?;
+            assert!(conn.execute("INSERT INTO t (col) VALUES (1);", ()).is_err());
+            Ok(())
+        }
+
+        #[test]
+        fn test_apply() -> Result<()> {
+            let mut schema = Schema::new()
+                .add_table(Table::new_default("parent".to_string())
+                    .add_column(Column::new_typed(SQLiteType::Integer, "id".to_string()).set_pk(Some(PrimaryKey::default()))))
+                .add_table(Table::new_default("child".to_string())
+                    .add_column(Column::new_typed(SQLiteType::Integer, "parent_id".to_string()).set_fk(Some(ForeignKey::new_default("parent".to_string(), "id".to_string())))))
+                .with_foreign_keys_enabled(true);
+
+            let conn: Connection = Connection::open_in_memory()?;
+            schema.apply_to(&conn)?;
+            conn.execute("INSERT INTO parent (id) VALUES (1);", ())?;
+            conn.execute("INSERT INTO child (parent_id) VALUES (1);", ())?;
+            // IF NOT EXISTS keeps the data
+            schema.apply_to(&conn)?;
+            assert_eq!(conn.query_row("SELECT COUNT(*) FROM child;", (), |row| row.get::<_, i64>(0))?, 1);
+
+            conn.execute_batch(r#"CREATE TABLE stale (x TEXT); CREATE INDEX stale_idx ON stale (x); CREATE TABLE "order" (x TEXT); CREATE TABLE "my ""tbl""" (x TEXT);"#)?;
+            schema.apply_fresh(&conn)?;
+            assert_eq!(conn.query_row("SELECT COUNT(*) FROM child;", (), |row| row.get::<_, i64>(0))?, 0);
+            assert_eq!(conn.query_row("SELECT COUNT(*) FROM sqlite_master;", (), |row| row.get::<_, i64>(0))?, 2);
+            assert_eq!(schema.check_db(&conn)?, None);
+
+            let mut invalid = Schema::new().add_table(Table::new_default("other".to_string())
+                .add_column(Column::new_typed(SQLiteType::Integer, "x".to_string()).set_fk(Some(ForeignKey::new_default("missing".to_string(), "id".to_string())))));
+            let unresolved = || Error::UnresolvedForeignKey { from_table: "other".to_string(), from_column: "x".to_string(), to_table: "missing".to_string(), to_column: "id".to_string() };
+            assert_eq!(invalid.apply_to(&conn), Err(ExecError::Error(unresolved())));
+            assert_eq!(invalid.apply_fresh(&conn), Err(ExecError::Error(unresolved())));
+            // nothing dropped
+            assert_eq!(conn.query_row("SELECT COUNT(*) FROM sqlite_master;", (), |row| row.get::<_, i64>(0))?, 2);
+            Ok(())
+        }
+
+        #[test]
+        fn test_execute_with_pragmas() -> Result<()> {
+            let mut schema = Schema::new()
+                .add_table(Table::new_default("parent".to_string())
+                    .add_column(Column::new_typed(SQLiteType::Integer, "id".to_string()).set_pk(Some(PrimaryKey::default()))))
+                .add_table(Table::new_default("child".to_string())
+                    .add_column(Column::new_typed(SQLiteType::Integer, "parent_id".to_string()).set_fk(Some(ForeignKey::new_default("parent".to_string(), "id".to_string())))));
+
+            let conn: Connection = Connection::open_in_memory()?;
+            schema.execute_default(&conn)?;
+            assert_eq!(conn.query_row("PRAGMA foreign_keys;", (), |row| row.get::<_, i64>(0))?, 1);
+            assert!(conn.execute("INSERT INTO child (parent_id) VALUES (1);", ()).is_err());
+
+            let conn: Connection = Connection::open_in_memory()?;
+            schema.execute_with_pragmas(&[Pragma::new("user_version".to_string(), PragmaValue::Integer(3))], false, true, &conn)?;
+            assert_eq!(conn.query_row("PRAGMA user_version;", (), |row| row.get::<_, i64>(0))?, 3);
+
+            assert_eq!(
+                schema.execute_with_pragmas(&[Pragma::new("".to_string(), PragmaValue::On)], true, false, &conn),
+                Err(ExecError::Error(Error::EmptyPragmaName))
+            );
+            assert_eq!(Schema::new().execute_default(&conn), Err(ExecError::Error(Error::SchemaWithoutTables)));
+            Ok(())
+        }
+
+        #[test]
+        fn test_keyword_to_from_sql() -> Result<()> {
+            let conn: Connection = Connection::open_in_memory()?;
+            conn.execute_batch("CREATE TABLE meta (typ TEXT, ord TEXT, conflict TEXT);")?;
+            conn.execute("INSERT INTO meta VALUES (?1, ?2, ?3);", (SQLiteType::Integer, Order::Descending, OnConflict::Replace))?;
+            assert_eq!(
+                conn.query_row("SELECT typ, ord, conflict FROM meta;", [], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?)))?,
+                ("INTEGER".to_string(), "DESC".to_string(), "REPLACE".to_string())
+            );
+            assert_eq!(
+                conn.query_row("SELECT typ, ord, conflict FROM meta;", [], |row| Ok((row.get::<_, SQLiteType>(0)?, row.get::<_, Order>(1)?, row.get::<_, OnConflict>(2)?)))?,
+                (SQLiteType::Integer, Order::Descending, OnConflict::Replace)
+            );
+
+            assert_eq!(conn.query_row("SELECT 'text', 'ascending', 'ignore';", [], |row| Ok((row.get::<_, SQLiteType>(0)?, row.get::<_, Order>(1)?, row.get::<_, OnConflict>(2)?)))?, (SQLiteType::Text, Order::Ascending, OnConflict::Ignore));
+            assert!(conn.query_row("SELECT 'varchar';", [], |row| row.get::<_, SQLiteType>(0)).is_err());
+            assert!(conn.query_row("SELECT 1;", [], |row| row.get::<_, Order>(0)).is_err());
+            Ok(())
+        }
+
+        #[test]
+        fn test_from_db() -> Result<()> {
+            let parent = Table::new_default("parent".to_string())
+                .add_column(Column::new_typed(SQLiteType::Integer, "id".to_string()).set_pk(Some(PrimaryKey::default())))
+                .add_column(Column::new_typed(SQLiteType::Text, "name".to_string())
+                    .set_unique(Some(Unique::default()))
+                    .set_not_null(Some(NotNull::default()))
+                    .set_default(Some(DefaultValue::Text("it's".to_string()))))
+                .add_column(Column::new_typed(SQLiteType::Real, "score".to_string()).set_default(Some(DefaultValue::Real(-1.5))))
+                .add_column(Column::new_typed(SQLiteType::Integer, "num".to_string()).set_default(Some(DefaultValue::Expr("1 + 1".to_string()))))
+                .add_column(Column::new_typed(SQLiteType::Text, "created".to_string()).set_default(Some(DefaultValue::CurrentTimestamp)));
+            let child = Table::new_default("child".to_string())
+                .add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()))
+                .add_column(Column::new_typed(SQLiteType::Integer, "b".to_string()))
+                .add_column(Column::new_typed(SQLiteType::Integer, "parent".to_string())
+                    .set_fk(Some(ForeignKey::new_default("parent".to_string(), "id".to_string()).set_on_delete(Some(FKOnAction::Cascade)))))
+                .add_constraint(TableConstraint::CompositePrimaryKey(vec!["a".to_string(), "b".to_string()], OnConflict::default()))
+                .add_unique_constraint(vec!["b".to_string(), "parent".to_string()], OnConflict::default())
+                .add_composite_fk(CompositeForeignKey::new_default(vec!["a".to_string(), "b".to_string()], "other".to_string(), vec!["x".to_string(), "y".to_string()]))
+                .set_strict(true);
+            let other = Table::new_default("other".to_string())
+                .add_column(Column::new_typed(SQLiteType::Integer, "x".to_string()).set_pk(Some(PrimaryKey::default().set_sort_order(Order::Descending))))
+                .add_column(Column::new_typed(SQLiteType::Integer, "y".to_string()))
+                .set_without_rowid(true);
+            let mut schema = Schema::new()
+                .add_table(parent)
+                .add_table(child)
+                .add_table(other)
+                .add_index(Index::new_default("idx".to_string(), "parent".to_string())
+                    .add_collated_column("name".to_string(), Order::Descending, Collation::NoCase)
+                    .add_column("score".to_string(), Order::Ascending)
+                    .set_unique(true));
+
+            let conn: Connection = Connection::open_in_memory()?;
+            schema.execute(true, false, &conn)?;
+            assert_eq!(Schema::from_db(&conn)?, schema);
+            assert_eq!(schema.diff_from_db(&conn)?, SchemaDiff::default());
+
+            conn.execute_batch("DROP TABLE other; ALTER TABLE parent ADD COLUMN extra TEXT; CREATE TABLE new (a);")?;
+            let diff: SchemaDiff = schema.diff_from_db(&conn)?;
+            assert_eq!(diff.removed_tables().iter().map(Table::name).collect::<Vec<&str>>(), vec!["other"]);
+            assert_eq!(diff.added_tables().iter().map(Table::name).collect::<Vec<&str>>(), vec!["new"]);
+            assert_eq!(diff.modified_tables().iter().map(|(old, _)| old.name()).collect::<Vec<&str>>(), vec!["parent"]);
+
+            assert_eq!(Schema::from_db(&Connection::open_in_memory()?)?, Schema::new());
+            Ok(())
+        }
+
+        #[test]
+        fn test_from_db_handwritten() -> Result<()> {
+            let conn: Connection = Connection::open_in_memory()?;
+            conn.execute_batch(concat!(
+                "CREATE TABLE users (id INTEGER PRIMARY KEY, name VARCHAR(20) NOT NULL, balance DECIMAL(10, 2), avatar, rating DOUBLE);",
+                "CREATE TABLE posts (id INTEGER PRIMARY KEY, author INTEGER REFERENCES users ON UPDATE SET NULL);",
+            ))?;
+
+            let schema: Schema = Schema::from_db(&conn)?;
+            assert_eq!(schema.validate(), Ok(()));
+            assert_eq!(schema, Schema::new()
+                .add_table(Table::new_default("users".to_string())
+                    .add_column(Column::new_typed(SQLiteType::Integer, "id".to_string()).set_pk(Some(PrimaryKey::default())))
+                    .add_column(Column::new_typed(SQLiteType::Text, "name".to_string()).set_not_null(Some(NotNull::default())))
+                    .add_column(Column::new_typed(SQLiteType::Numeric, "balance".to_string()))
+                    .add_column(Column::new_typed(SQLiteType::Blob, "avatar".to_string()))
+                    .add_column(Column::new_typed(SQLiteType::Real, "rating".to_string())))
+                .add_table(Table::new_default("posts".to_string())
+                    .add_column(Column::new_typed(SQLiteType::Integer, "id".to_string()).set_pk(Some(PrimaryKey::default())))
+                    .add_column(Column::new_typed(SQLiteType::Integer, "author".to_string())
+                        .set_fk(Some(ForeignKey::new_default("users".to_string(), "id".to_string()).set_on_update(Some(FKOnAction::SetNull))))))
+            );
+
+            let conn: Connection = Connection::open_in_memory()?;
+            conn.execute_batch(concat!(
+                r#"CREATE TABLE "group" ("order" INTEGER PRIMARY KEY, a TEXT DEFAULT 'it''s', b TEXT DEFAULT ('x' || 'y'));"#,
+                r#"CREATE TABLE "my tbl" (g INTEGER REFERENCES "group" ("order"));"#,
+                r#"CREATE INDEX "my idx" ON "my tbl" (g);"#,
+            ))?;
+            let mut schema: Schema = Schema::from_db(&conn)?;
+            let group: &Table = &schema.tables()[0];
+            assert_eq!((group.quote(), group.columns()[0].quote()), (Quote::DoubleQuote, Quote::DoubleQuote));
+            assert_eq!(group.columns()[1].default_value(), Some(&DefaultValue::Text("it's".to_string())));
+            assert!(matches!(group.columns()[2].default_value(), Some(DefaultValue::Expr(_))));
+            assert_eq!(DefaultValue::from_db("'x' || 'y'"), DefaultValue::Expr("'x' || 'y'".to_string()));
+            assert_eq!(DefaultValue::from_db("'x'' || ''y'"), DefaultValue::Text("x' || 'y".to_string()));
+            assert_eq!(schema.tables()[1].columns()[0].foreign_key().map(ForeignKey::quote), Some(Quote::DoubleQuote));
+            assert_eq!(schema.indexes()[0].quote(), Quote::DoubleQuote);
+            // the read Schema can be rebuilt
+            let other: Connection = Connection::open_in_memory()?;
+            schema.execute_default(&other)?;
+            assert_eq!(Schema::from_db(&other)?, schema);
+
+            let conn: Connection = Connection::open_in_memory()?;
+            conn.execute_batch("CREATE TABLE anything (a ANY) STRICT;")?;
+            assert_eq!(Schema::from_db(&conn), Err(CheckError::Error(Error::StrictTableAnyColumn { table: "anything".to_string(), column: "a".to_string() })));
+            Ok(())
+        }
+
+        #[test]
+        fn test_explain_query_plan() -> Result<()> {
+            let mut tbl = Table::new_default("test".to_string())
+                .add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()))
+                .add_column(Column::new_typed(SQLiteType::Integer, "b".to_string()));
+            let conn: Connection = Connection::open_in_memory()?;
+            assert_eq!(tbl.explain_query_plan("SELECT * FROM test WHERE a = 1", &conn)?, "SCAN test");
+
+            let mut schema = Schema::new()
+                .add_table(tbl)
+                .add_index(Index::new_default("idx".to_string(), "test".to_string()).add_column("a".to_string(), Order::Ascending));
+            schema.execute(false, true, &conn)?;
+            let plans = schema.explain_all_query_plans(&[("test", "SELECT * FROM test WHERE a = 1"), ("test", "SELECT * FROM test WHERE a IN (SELECT b FROM test)")], &conn)?;
+            assert_eq!(plans[0], ("test".to_string(), "SEARCH test USING INDEX idx (a=?)".to_string()));
+            assert!(plans[1].1.contains("\n  "), "{}", plans[1].1);
+
+            assert_eq!(schema.explain_all_query_plans(&[("missing", "SELECT 1")], &conn), Err(CheckError::Error(Error::TableNotFound("missing".to_string()))));
+            assert!(schema.explain_all_query_plans(&[("test", "NOT SQL")], &conn).is_err());
+            assert_eq!(schema.explain_all_query_plans(&[("TEST", "SELECT * FROM test WHERE a = 1")], &conn)?[0].1, plans[0].1);
+            Ok(())
+        }
+
+        #[test]
+        fn test_execute_drop() -> Result<()> {
+            let mut schema = Schema::new()
+                .add_table(Table::new_default("parent".to_string()).add_column(Column::new_typed(SQLiteType::Integer, "id".to_string()).set_pk(Some(PrimaryKey::default()))))
+                .add_table(Table::new_default("child".to_string())
+                    .add_column(Column::new_typed(SQLiteType::Integer, "parent".to_string()).set_fk(Some(ForeignKey::new_default("parent".to_string(), "id".to_string())))))
+                .add_index(Index::new_default("idx".to_string(), "child".to_string()).add_column("parent".to_string(), Order::Ascending));
+
+            let conn: Connection = Connection::open_in_memory()?;
+            schema.execute_default(&conn)?;
+            conn.execute_batch("INSERT INTO parent VALUES (1); INSERT INTO child VALUES (1);")?;
+            schema.execute_drop(false, &conn)?;
+            assert_eq!(conn.query_row("SELECT count(*) FROM sqlite_master", [], |row| row.get::<_, i64>(0))?, 0);
+
+            assert!(schema.execute_drop(false, &conn).is_err());
+            schema.execute_drop(true, &conn)?;
+            Ok(())
+        }
+
+        #[test]
+        fn test_auto_quote_execute() -> Result<()> {
+            let mut schema = Schema::new()
+                .add_table(Table::new_default("group".to_string()).add_column(Column::new_typed(SQLiteType::Integer, "order".to_string()).set_pk(Some(PrimaryKey::default()))))
+                .add_table(Table::new_default("my tbl".to_string())
+                    .add_column(Column::new_typed(SQLiteType::Integer, "from".to_string()).set_fk(Some(ForeignKey::new_default("group".to_string(), "order".to_string()))))
+                    .add_column(Column::new_typed(SQLiteType::Integer, "where".to_string()))
+                    .add_unique_constraint(vec!["from".to_string(), "where".to_string()], OnConflict::Abort)
+                    .add_composite_fk(CompositeForeignKey::new_default(vec!["where".to_string()], "group".to_string(), vec!["order".to_string()])))
+                .add_index(Index::new_default("index".to_string(), "group".to_string()).add_column("order".to_string(), Order::Descending))
+                .add_index(Index::new_default("my idx".to_string(), "my tbl".to_string()).add_column("where".to_string(), Order::Ascending))
+                .with_foreign_keys_enabled(true)
+                .auto_quote();
+
+            let conn: Connection = Connection::open_in_memory()?;
+            schema.execute_default(&conn)?;
+            conn.execute_batch(r#"INSERT INTO "group" VALUES (1); INSERT INTO "my tbl" VALUES (1, 1);"#)?;
+            assert!(conn.execute_batch(r#"INSERT INTO "my tbl" VALUES (2, 1);"#).is_err());
+            schema.execute_drop(false, &conn)?;
+            assert_eq!(conn.query_row("SELECT count(*) FROM sqlite_master", [], |row| row.get::<_, i64>(0))?, 0);
+            Ok(())
+        }
+
+        #[test]
+        fn test_schema_statements_execute() -> Result<()> {
+            let mut schema = Schema::new()
+                .add_table(Table::new_default("test".to_string()).add_column(Column::new_typed(SQLiteType::Integer, "a".to_string())))
+                .add_index(Index::new_default("idx".to_string(), "test".to_string()).add_column("a".to_string(), Order::Ascending))
+                .add_pragma(Pragma::new("user_version".to_string(), PragmaValue::Integer(3)));
+
+            let conn: Connection = Connection::open_in_memory()?;
+            for mut stmt in schema.clone() {
+                stmt.execute(false, false, &conn)?;
+            }
+            assert_eq!(schema.check_db(&conn)?, None);
+            assert_eq!(conn.query_row("PRAGMA user_version", [], |row| row.get::<_, i64>(0))?, 3);
+            Ok(())
+        }
+
+        #[test]
+        fn test_dyn_statements() -> Result<()> {
+            let tbl = Table::new_default("test".to_string()).add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()));
+            let mut stmts: Vec<Box<dyn SQLStatement>> = vec![
+                Box::new(Schema::new().add_table(tbl.clone()).add_pragma(Pragma::foreign_keys_on())),
+                Box::new(tbl.clone().set_name("other".to_string())),
+                Box::new(Index::new_default("test_idx".to_string(), "test".to_string()).add_column("a".to_string(), Order::Ascending)),
+                Box::new(AlterTableAddColumn::new("other".to_string(), Column::new_typed(SQLiteType::Text, "b".to_string()))),
+            ];
+
+            let conn: Connection = Connection::open_in_memory()?;
+            for stmt in &mut stmts {
+                assert_eq!(stmt.len(true, true)?, stmt.build(true, true)?.len());
+                stmt.execute(true, true, &conn)?;
+            }
+            assert_eq!(stmts[1].build_pretty(false, false, 2)?, tbl.set_name("other".to_string()).build_pretty(false, false, 2)?);
+            assert!(stmts[0].check_version_compatibility(SQLiteVersion::new(3, 0, 0)).is_ok());
+            Ok(())
+        }
+
+        #[test]
+        fn test_error_display() -> Result<()> {
+            let conn: Connection = Connection::open_in_memory()?;
+
+            let err: ExecError = Table::new_default("test".to_string()).add_column(Column::new_default("".to_string())).execute(false, false, &conn).unwrap_err();
+            assert_eq!(format!("{}", err), "SQL build failed: Column Name cannot be Empty");
+            assert_eq!(err.source().map(|src| src.to_string()), Some("Column Name cannot be Empty".to_string()));
+
+            let err: ExecError = conn.execute_batch("NOT SQL;").unwrap_err().into();
+            assert!(format!("{}", err).starts_with("SQL execution failed: near \"NOT\""));
+            assert!(err.source().is_some());
+
+            let err: ExecError = CheckError::Error(Error::SchemaWithoutTables).into();
+            assert_eq!(format!("{}", err), "Schema check failed: Schema is invalid: Schema must contain Tables");
+            assert_eq!(err.source().and_then(|src| src.source()).map(|src| src.to_string()), Some("Schema must contain Tables".to_string()));
+
+            let err: CheckError = Error::SchemaWithoutTables.into();
+            assert_eq!(format!("{}", err), "Schema is invalid: Schema must contain Tables");
+            assert_eq!(err.source().map(|src| src.to_string()), Some("Schema must contain Tables".to_string()));
+
+            let err: CheckError = conn.execute_batch("NOT SQL;").unwrap_err().into();
+            assert!(format!("{}", err).starts_with("Reading the Database failed: near \"NOT\""));
+            assert!(err.source().is_some());
+
+            let err: CheckError = std::fmt::Error.into();
+            assert_eq!(format!("{}", err), "Formatting the Report failed: an error occurred when formatting an argument");
+            assert!(err.source().is_some());
+            Ok(())
+        }
+
+        #[test]
+        fn test_composite_foreign_key() -> Result<()> {
+            let mut schema = Schema::new()
+                .add_table(Table::new_default("parent".to_string())
+                    .add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()))
+                    .add_column(Column::new_typed(SQLiteType::Integer, "b".to_string()))
+                    .add_constraint(TableConstraint::CompositePrimaryKey(vec!["a".to_string(), "b".to_string()], OnConflict::Abort)))
+                .add_table(Table::new_default("child".to_string())
+                    .add_column(Column::new_typed(SQLiteType::Integer, "pa".to_string()))
+                    .add_column(Column::new_typed(SQLiteType::Integer, "pb".to_string()))
+                    .add_composite_fk(CompositeForeignKey::new_default(vec!["pa".to_string(), "pb".to_string()], "parent".to_string(), vec!["a".to_string(), "b".to_string()])
+                        .set_on_delete(Some(FKOnAction::Cascade))))
+                .with_foreign_keys_enabled(true);
+
+            let conn: Connection = Connection::open_in_memory()?;
+            schema.execute(true, false, &conn)?;
+            conn.execute("INSERT INTO parent (a, b) VALUES (1, 2);", ())?;
+            conn.execute("INSERT INTO child (pa, pb) VALUES (1, 2);", ())?;
+            assert!(conn.execute("INSERT INTO child (pa, pb) VALUES (2, 1);", ()).is_err());
+            conn.execute("DELETE FROM parent;", ())?;
+            assert_eq!(conn.query_row("SELECT COUNT(*) FROM child;", (), |row| row.get::<_, i64>(0))?, 0);
+            Ok(())
+        }
+
+        #[test]
+        fn test_error_conversions() -> Result<()> {
+            assert_eq!(ExecError::from(Error::EmptyTableName), ExecError::Error(Error::EmptyTableName));
+            assert_eq!(CheckError::from(Error::SchemaWithoutTables), CheckError::Error(Error::SchemaWithoutTables));
+            assert_eq!(ExecError::from(CheckError::FmtError(std::fmt::Error)), ExecError::CheckError(CheckError::FmtError(std::fmt::Error)));
+
+            let conn: Connection = Connection::open_in_memory()?;
+            assert_eq!(Table::new_default("".to_string()).execute(false, false, &conn), Err(ExecError::Error(Error::EmptyTableName)));
+            let invalid_sql: ExecError = conn.execute_batch("NOT SQL;").unwrap_err().into();
+            assert!(matches!(invalid_sql, ExecError::RusqliteError(_)));
+
+            let chained = || -> Result<(), ExecError> {
+                Err(CheckError::Error(Error::NoColumns))?;
+                Ok(())
+            };
+            assert_eq!(chained(), Err(ExecError::CheckError(CheckError::Error(Error::NoColumns))));
+            Ok(())
+        }
+
+        #[test]
+        fn test_foreign_keys_enabled() -> Result<()> {
+            let mut schema = Schema::new()
+                .add_table(Table::new_default("parent".to_string())
+                    .add_column(Column::new_typed(SQLiteType::Integer, "id".to_string()).set_pk(Some(PrimaryKey::default()))))
+                .add_table(Table::new_default("child".to_string())
+                    .add_column(Column::new_typed(SQLiteType::Integer, "parent_id".to_string()).set_fk(Some(ForeignKey::new_default("parent".to_string(), "id".to_string())))))
+                .with_foreign_keys_enabled(false)
+                .with_foreign_keys_enabled(true);
+            assert_eq!(schema.pragmas(), &[Pragma::foreign_keys_on()]);
+            assert!(schema.build(true, false)?.starts_with("PRAGMA foreign_keys = ON;BEGIN;\n"));
+
+            let conn: Connection = Connection::open_in_memory()?;
+            schema.execute(true, false, &conn)?;
+            conn.execute("INSERT INTO parent (id) VALUES (1);", ())?;
+            conn.execute("INSERT INTO child (parent_id) VALUES (1);", ())?;
+            assert!(conn.execute("INSERT INTO child (parent_id) VALUES (2);", ()).is_err());
+
+            let conn: Connection = Connection::open_in_memory()?;
+            schema.clone().with_foreign_keys_enabled(false).execute(true, false, &conn)?;
+            conn.execute("INSERT INTO child (parent_id) VALUES (2);", ())?;
+            Ok(())
+        }
+
+        #[test]
+        fn test_alter_table_add_column_execute() -> Result<()> {
+            let mut tbl = Table::new_default("test".to_string()).add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()));
+
+            let conn: Connection = Connection::open_in_memory()?;
+            tbl.execute(false, false, &conn)?;
+            AlterTableAddColumn::new("test".to_string(), Column::new_typed(SQLiteType::Text, "b".to_string())).execute(true, false, &conn)?;
+            conn.execute("INSERT INTO test (a, b) VALUES (1, 'new');", ())?;
+            assert_eq!(conn.query_row("SELECT b FROM test;", (), |row| row.get::<usize, String>(0))?, "new");
+
+            let mut keyword = Table::new_default("order".to_string()).set_quote(Quote::DoubleQuote).add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()));
+            keyword.execute(false, false, &conn)?;
+            AlterTableAddColumn::new("order".to_string(), Column::new_typed(SQLiteType::Text, "my col".to_string()).set_quote(Quote::DoubleQuote))
+                .set_quote(Quote::DoubleQuote)
+                .execute(false, false, &conn)?;
+            conn.execute(r#"INSERT INTO "order" (a, "my col") VALUES (1, 'new');"#, ())?;
+            Ok(())
+        }
+
+        #[test]
+        fn test_trigger_execute() -> Result<()> {
+            let conn: Connection = Connection::open_in_memory()?;
+            conn.execute_batch("CREATE TABLE test (a INTEGER); CREATE VIEW test_view AS SELECT a FROM test;")?;
+            Trigger::new("ins".to_string(), TriggerEvent::Insert, "test_view".to_string(), "INSERT INTO test (a) VALUES (NEW.a)".to_string()).execute(true, false, &conn)?;
+            Trigger::new("del".to_string(), TriggerEvent::Delete, "test_view".to_string(), "DELETE FROM test WHERE a = OLD.a".to_string()).execute(false, true, &conn)?;
+
+            // changes made by INSTEAD OF Triggers are not counted
+            conn.execute("INSERT INTO test_view (a) VALUES (1), (2);", ())?;
+            conn.execute("DELETE FROM test_view WHERE a = 1;", ())?;
+            assert_eq!(conn.query_row("SELECT group_concat(a) FROM test;", (), |row| row.get::<usize, String>(0))?, "2");
+
+            conn.execute_batch(r#"CREATE VIEW "order" AS SELECT a FROM test;"#)?;
+            Trigger::new("my trigger".to_string(), TriggerEvent::Insert, "order".to_string(), "INSERT INTO test (a) VALUES (NEW.a)".to_string())
+                .set_quote(Quote::DoubleQuote)
+                .execute(false, false, &conn)?;
+            conn.execute(r#"INSERT INTO "order" (a) VALUES (3);"#, ())?;
+            assert_eq!(conn.query_row("SELECT group_concat(a) FROM test;", (), |row| row.get::<usize, String>(0))?, "2,3");
+            Ok(())
+        }
+
+        #[test]
+        fn test_alter_table_rename_column_execute() -> Result<()> {
+            let mut schema = Schema::from(Table::new_default("test".to_string())
+                .add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()))
+                .add_column(Column::new_typed(SQLiteType::Integer, "b".to_string()))
+                .add_unique_constraint(vec!["a".to_string(), "b".to_string()], OnConflict::Abort));
+
+            let conn: Connection = Connection::open_in_memory()?;
+            schema.execute(false, false, &conn)?;
+            conn.execute("INSERT INTO test (a, b) VALUES (1, 2);", ())?;
+            AlterTableRenameColumn::new("test".to_string(), "a".to_string(), "c".to_string()).execute(true, false, &conn)?;
+            assert_eq!(conn.query_row("SELECT c FROM test;", (), |row| row.get::<usize, i64>(0))?, 1);
+
+            let renamed: Table = schema.tables()[0].clone().rename_column("a", "c".to_string())?;
+            assert_eq!(Schema::from(renamed).check_db(&conn)?, None);
+            assert!(AlterTableRenameColumn::new("test".to_string(), "a".to_string(), "c".to_string()).execute(false, false, &conn).is_err());
+
+            conn.execute_batch(r#"CREATE TABLE "order" ("my col" INTEGER);"#)?;
+            AlterTableRenameColumn::new("order".to_string(), "my col".to_string(), "select".to_string())
+                .set_quote(Quote::DoubleQuote)
+                .set_column_quote(Quote::DoubleQuote)
+                .execute(false, false, &conn)?;
+            conn.execute(r#"INSERT INTO "order" ("select") VALUES (1);"#, ())?;
+            Ok(())
+        }
+
+        #[test]
+        fn test_alter_table_rename_execute() -> Result<()> {
+            let mut tbl = Table::new_default("old".to_string()).add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()));
+
+            let conn: Connection = Connection::open_in_memory()?;
+            tbl.execute(false, false, &conn)?;
+            conn.execute("INSERT INTO old (a) VALUES (1);", ())?;
+            AlterTableRename::new("old".to_string(), "new".to_string()).execute(true, false, &conn)?;
+            assert_eq!(conn.query_row("SELECT a FROM new;", (), |row| row.get::<usize, i64>(0))?, 1);
+            assert!(conn.prepare("SELECT a FROM old;").is_err());
+            assert!(AlterTableRename::new("old".to_string(), "new".to_string()).execute(false, true, &conn).is_err());
+
+            AlterTableRename::new("new".to_string(), "order".to_string()).set_quote(Quote::DoubleQuote).execute(false, false, &conn)?;
+            assert_eq!(conn.query_row(r#"SELECT a FROM "order";"#, (), |row| row.get::<usize, i64>(0))?, 1);
+            Ok(())
+        }
+
+        #[test]
+        fn test_drop_execute() -> Result<()> {
+            let mut tbl = Table::new_default("test".to_string()).add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()));
+            let mut idx = Index::new_default("test_idx".to_string(), "test".to_string()).add_column("a".to_string(), Order::Ascending);
+
+            let conn: Connection = Connection::open_in_memory()?;
+            tbl.execute(false, false, &conn)?;
+            idx.execute(false, false, &conn)?;
+            DropIndex::new("test_idx".to_string()).execute(true, false, &conn)?;
+            assert!(DropIndex::new("test_idx".to_string()).execute(false, false, &conn).is_err());
+            DropIndex::new("test_idx".to_string()).execute(false, true, &conn)?;
+
+            DropTable::new("test".to_string()).execute(true, false, &conn)?;
+            assert!(conn.prepare("SELECT a FROM test;").is_err());
+            assert!(DropTable::new("test".to_string()).execute(false, false, &conn).is_err());
+            DropTable::new("test".to_string()).execute(false, true, &conn)?;
+            Ok(())
+        }
+
+        #[test]
+        fn test_alter_statements_execute() -> Result<()> {
+            let base = Table::new_default("base".to_string()).add_column(Column::new_typed(SQLiteType::Integer, "a".to_string())).add_column(Column::new_typed(SQLiteType::Text, "b".to_string()));
+            let mut old = Schema::new().add_table(base.clone()).add_table(Table::new_default("removed".to_string()).add_column(Column::new_default("a".to_string())));
+            let mut altered = base;
+            altered.remove_column("b");
+            let new = Schema::new()
+                .add_table(altered.add_column(Column::new_typed(SQLiteType::Integer, "c".to_string()).set_default(Some(DefaultValue::Integer(3)))))
+                .add_table(Table::new_default("added".to_string()).add_column(Column::new_typed(SQLiteType::Integer, "a".to_string())));
+
+            let conn: Connection = Connection::open_in_memory()?;
+            old.execute(true, false, &conn)?;
+            conn.execute("INSERT INTO base (a, b) VALUES (1, 'x');", ())?;
+            for stmt in old.diff(&new).to_alter_statements(true)? {
+                conn.execute_batch(stmt.as_str())?;
+            }
+
+            assert_eq!(conn.query_row("SELECT a, c FROM base;", (), |row| Ok((row.get::<usize, i64>(0)?, row.get::<usize, i64>(1)?)))?, (1, 3));
+            assert!(conn.prepare("SELECT b FROM base;").is_err());
+            assert!(conn.prepare("SELECT a FROM removed;").is_err());
+            assert!(conn.prepare("SELECT a FROM added;").is_ok());
+            Ok(())
+        }
+
+        #[test]
+        fn test_build_pretty_execute() -> Result<()> {
+            let mut schema = Schema::new()
+                .add_table(Table::new_default("parent".to_string())
+                    .add_column(Column::new_typed(SQLiteType::Integer, "id".to_string()).set_pk(Some(PrimaryKey::default())))
+                    .add_column(Column::new_typed(SQLiteType::Text, "name".to_string()).set_check(Some(Check::new("length(name) > 0".to_string())))))
+                .add_table(Table::new_default("child".to_string())
+                    .add_column(Column::new_typed(SQLiteType::Integer, "parent_id".to_string()).set_fk(Some(ForeignKey::new_default("parent".to_string(), "id".to_string())))))
+                .add_index(Index::new_default("child_idx".to_string(), "child".to_string()).add_column("parent_id".to_string(), Order::Ascending));
+
+            let conn: Connection = Connection::open_in_memory()?;
+            conn.execute_batch(schema.build_pretty(true, false, 2)?.as_str())?;
+            conn.execute_batch(schema.build_pretty(false, true, 4)?.as_str())?;
+            assert_eq!(conn.query_row("SELECT count(*) FROM sqlite_master;", (), |row| row.get::<usize, i64>(0))?, 3);
+            Ok(())
+        }
+
+        #[test]
+        fn test_table_execute() -> Result<()> {
+            let mut tbl = Table::new_default("test".to_string())
+                .add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()).set_pk(Some(PrimaryKey::default())))
+                .add_column(Column::new_typed(SQLiteType::Text, "b".to_string()));
+
+            let conn: Connection = Connection::open_in_memory()?;
+            tbl.execute(true, false, &conn)?;
+            assert_eq!(conn.execute(r#"INSERT INTO test (a, b) VALUES (1, "x");"#, ())?, 1);
+
+            // table exists now
+            assert!(matches!(tbl.execute(false, false, &conn), Err(ExecError::RusqliteError(_))));
+            tbl.execute(false, true, &conn)?;
+
+            let mut empty = Table::new_default("empty".to_string());
+            assert_eq!(empty.execute(false, false, &conn), Err(ExecError::Error(Error::NoColumns)));
+            Ok(())
+        }
+
+        #[test]
+        fn test_schema_execute() -> Result<()> {
+            let mut schema = Schema::new()
+                .add_table(Table::new_default("test1".to_string()).add_column(Column::new_typed(SQLiteType::Integer, "a".to_string())))
+                .add_table(Table::new_default("test2".to_string()).add_column(Column::new_typed(SQLiteType::Text, "b".to_string())));
+
+            let conn: Connection = Connection::open_in_memory()?;
+            schema.execute(true, true, &conn)?;
+            assert_eq!(conn.execute("INSERT INTO test1 (a) VALUES (1);", ())?, 1);
+            assert_eq!(conn.execute(r#"INSERT INTO test2 (b) VALUES ("x");"#, ())?, 1);
+            assert_eq!(schema.check_db(&conn)?, None);
+            Ok(())
+        }
+
+        #[test]
+        fn test_schema_attach_execute() -> Result<()> {
+            let mut schema = Schema::new()
+                .set_name("reporting".to_string())
+                .add_table(Table::new_default("test".to_string()).add_column(Column::new_typed(SQLiteType::Integer, "a".to_string())))
+                .add_index(Index::new_default("idx".to_string(), "test".to_string()).add_column("a".to_string(), Order::Ascending));
+
+            let conn: Connection = Connection::open_in_memory()?;
+            conn.execute_batch(schema.to_attach_stmt(":memory:")?.as_str())?;
+            schema.execute(true, false, &conn)?;
+            assert_eq!(conn.execute("INSERT INTO reporting.test (a) VALUES (1);", ())?, 1);
+            assert!(conn.execute("INSERT INTO main.test (a) VALUES (1);", ()).is_err());
+            assert_eq!(conn.query_row("SELECT count(*) FROM reporting.sqlite_schema WHERE name = 'idx';", (), |row| row.get::<usize, i64>(0))?, 1);
+
+            schema.execute_drop(false, &conn)?;
+            assert_eq!(conn.query_row("SELECT count(*) FROM reporting.sqlite_schema;", (), |row| row.get::<usize, i64>(0))?, 0);
+            Ok(())
+        }
+
+        #[test]
+        fn test_check_db_foreign_keys() -> Result<()> {
+            let mut schema = Schema::new()
+                .add_table(Table::new_default("parent".to_string())
+                    .add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()))
+                    .add_column(Column::new_typed(SQLiteType::Integer, "b".to_string())))
+                .add_table(Table::new_default("child".to_string())
+                    .add_column(Column::new_typed(SQLiteType::Integer, "x".to_string()).set_fk(Some(ForeignKey::new_default("parent".to_string(), "a".to_string()))))
+                    .add_column(Column::new_typed(SQLiteType::Integer, "y".to_string()))
+                    .add_column(Column::new_typed(SQLiteType::Integer, "z".to_string()))
+                    .add_composite_fk(CompositeForeignKey::new_default(vec!["y".to_string(), "z".to_string()], "parent".to_string(), vec!["a".to_string(), "b".to_string()])));
+
+            let conn: Connection = Connection::open_in_memory()?;
+            schema.execute(true, false, &conn)?;
+            assert_eq!(schema.check_db(&conn)?, None);
+
+            let conn: Connection = Connection::open_in_memory()?;
+            conn.execute_batch(concat!(
+                "CREATE TABLE parent (a INTEGER, b INTEGER);",
+                "CREATE TABLE child (x INTEGER REFERENCES parent (b), y INTEGER, z INTEGER REFERENCES parent (b));",
+            ))?;
+            assert_eq!(
+                schema.check_db(&conn)?,
+                Some(concat!(
+                    "Table 0: expected foreign key 'x' -> 'parent.a', got nothing; ",
+                    "Table 0: expected foreign key 'y' -> 'parent.a', got nothing; ",
+                    "Table 0: expected nothing, got foreign key 'x' -> 'parent.b'; ",
+                ).to_string())
+            );
+
+            let conn: Connection = Connection::open_in_memory()?;
+            conn.execute_batch(concat!(
+                "CREATE TABLE parent (a INTEGER PRIMARY KEY, b INTEGER);",
+                "CREATE TABLE child (x INTEGER REFERENCES parent (a), y INTEGER REFERENCES parent, z INTEGER, FOREIGN KEY (y, z) REFERENCES parent (a, b));",
+            ))?;
+            assert_eq!(schema.check_db(&conn)?, Some("Table 0: expected nothing, got foreign key 'y' -> 'parent.'; ".to_string()));
+            Ok(())
+        }
+
+        #[test]
+        fn test_check_db_indexes() -> Result<()> {
+            let mut schema = Schema::new()
+                .add_table(Table::new_default("test".to_string())
+                    .add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()).set_unique(Some(Unique::default())))
+                    .add_column(Column::new_typed(SQLiteType::Integer, "b".to_string())))
+                .add_index(Index::new_default("idx_a".to_string(), "test".to_string()).add_column("a".to_string(), Order::Ascending).set_unique(true))
+                .add_index(Index::new_default("idx_b".to_string(), "test".to_string()).add_column("b".to_string(), Order::Ascending).set_where("b > 0".to_string()));
+
+            let conn: Connection = Connection::open_in_memory()?;
+            schema.execute(true, false, &conn)?;
+            assert_eq!(schema.check_db(&conn)?, None);
+
+            let conn: Connection = Connection::open_in_memory()?;
+            conn.execute_batch(concat!(
+                "CREATE TABLE test (a INTEGER UNIQUE, b INTEGER);",
+                "CREATE INDEX idx_b ON test (b);",
+                "CREATE INDEX idx_c ON test (a, b);",
+            ))?;
+            assert_eq!(
+                schema.check_db(&conn)?,
+                Some(concat!(
+                    "Index 0: expected index 'idx_a' on 'test', got nothing; ",
+                    "Index 1: expected partial true, got false; ",
+                    "Index: expected nothing, got index 'idx_c'; ",
+                ).to_string())
+            );
+            assert_eq!(schema.check_db_with(&conn, false)?, None);
+
+            let conn: Connection = Connection::open_in_memory()?;
+            conn.execute_batch(concat!(
+                "CREATE TABLE test (a INTEGER UNIQUE, b INTEGER);",
+                "CREATE UNIQUE INDEX idx_a ON test (a DESC, b);",
+                "CREATE INDEX idx_b ON test (a) WHERE b > 0;",
+            ))?;
+            assert_eq!(
+                schema.check_db(&conn)?,
+                Some(concat!(
+                    "Index 0 Column 0: expected order ASC, got DESC; ",
+                    "Index 0 Column 1: expected nothing, got column 'b'; ",
+                    "Index 1 Column 0: expected name 'b', got 'a'; ",
+                ).to_string())
+            );
+            Ok(())
+        }
+
+        #[test]
+        fn test_check_db_columns() -> Result<()> {
+            let mut schema = Schema::new()
+                .add_table(Table::new_default("test".to_string())
+                    .add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()).set_pk(Some(PrimaryKey::default())))
+                    .add_column(Column::new_typed(SQLiteType::Text, "b".to_string()).set_not_null(Some(NotNull::default())))
+                    .set_without_rowid(true))
+                .add_table(Table::new_default("other".to_string()).add_column(Column::new_typed(SQLiteType::Real, "c".to_string())));
+
+            let conn: Connection = Connection::open_in_memory()?;
+            schema.execute(true, false, &conn)?;
+            assert_eq!(schema.check_db(&conn)?, None);
+
+            let conn: Connection = Connection::open_in_memory()?;
+            conn.execute_batch("CREATE TABLE test (a INTEGER PRIMARY KEY, x BLOB) WITHOUT ROWID; CREATE TABLE other (c REAL NOT NULL);")?;
+            assert_eq!(
+                schema.check_db(&conn)?,
+                Some(concat!(
+                    "Table 0 Column 0: expected not_null false, got true; ",
+                    "Table 1 Column 1: expected name 'b', got 'x'; ",
+                    "Table 1 Column 1: expected type 'TEXT', got 'BLOB'; ",
+                    "Table 1 Column 1: expected not_null true, got false; ",
+                ).to_string())
+            );
+
+            // other type names with the same affinity are accepted
+            let conn: Connection = Connection::open_in_memory()?;
+            conn.execute_batch("CREATE TABLE test (a INT PRIMARY KEY, b VARCHAR(10) NOT NULL) WITHOUT ROWID; CREATE TABLE other (c DOUBLE);")?;
+            assert_eq!(schema.check_db(&conn)?, None);
+            Ok(())
+        }
+
+        #[test]
+        fn test_check_db_columns_implied_not_null() -> Result<()> {
+            let pk = |typ: SQLiteType, ord: Order| Column::new_typed(typ, "a".to_string()).set_pk(Some(PrimaryKey::default().set_sort_order(ord)));
+            let composite = |name: &str| Table::new_default(name.to_string())
+                .add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()))
+                .add_column(Column::new_typed(SQLiteType::Text, "b".to_string()))
+                .add_constraint(TableConstraint::CompositePrimaryKey(vec!["a".to_string(), "b".to_string()], OnConflict::default()));
+            let mut schema = Schema::new()
+                // the rowid alias may be NULL, every other Primary Key Column of a STRICT Table not
+                .add_table(Table::new_default("alias".to_string()).add_column(pk(SQLiteType::Integer, Order::Ascending)).set_strict(true))
+                .add_table(Table::new_default("text".to_string()).add_column(pk(SQLiteType::Text, Order::Ascending)).set_strict(true))
+                .add_table(Table::new_default("desc".to_string()).add_column(pk(SQLiteType::Integer, Order::Descending)).set_strict(true))
+                .add_table(Table::new_default("single".to_string())
+                    .add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()))
+                    .add_constraint(TableConstraint::CompositePrimaryKey(vec!["a".to_string()], OnConflict::default()))
+                    .set_strict(true))
+                .add_table(composite("strict").set_strict(true))
+                .add_table(composite("without_rowid").set_without_rowid(true));
+
+            let conn: Connection = Connection::open_in_memory()?;
+            schema.execute(true, false, &conn)?;
+            assert_eq!(schema.check_db(&conn)?, None);
+            let mut stmt: Statement = conn.prepare(r#"SELECT "notnull" FROM pragma_table_info(?1) ORDER BY cid;"#)?;
+            for (tbl, expected) in [("alias", vec![false]), ("text", vec![true]), ("desc", vec![true]), ("single", vec![false]), ("strict", vec![true, true]), ("without_rowid", vec![true, true])] {
+                assert_eq!(stmt.query_map([tbl], |row| row.get::<usize, bool>(0))?.collect::<Result<Vec<bool>, _>>()?, expected);
+            }
+            // the NOT NULL implied by the Primary Key is not read back as a NotNull constraint
+            assert!(Schema::from_db(&conn)?.tables().iter().flat_map(Table::columns).all(|col| col.not_null().is_none()));
+
+            let conn: Connection = Connection::open_in_memory()?;
+            conn.execute_batch(concat!(
+                "CREATE TABLE alias (a INTEGER PRIMARY KEY NOT NULL) STRICT; CREATE TABLE text (a TEXT PRIMARY KEY) STRICT;",
+                "CREATE TABLE desc (a INTEGER PRIMARY KEY DESC) STRICT; CREATE TABLE single (a INTEGER, PRIMARY KEY (a)) STRICT;",
+                "CREATE TABLE strict (a INTEGER, b TEXT, PRIMARY KEY (a, b)) STRICT; CREATE TABLE without_rowid (a INTEGER, b TEXT) STRICT;",
+            ))?;
+            assert_eq!(
+                schema.check_db(&conn)?,
+                Some(concat!(
+                    "Table 0 Column 0: expected not_null false, got true; ",
+                    "Table 5: expected without_rowid true, got false; ",
+                    "Table 5: expected strict false, got true; ",
+                    "Table 5 Column 0: expected not_null true, got false; ",
+                    "Table 5 Column 1: expected not_null true, got false; ",
+                ).to_string())
+            );
+            Ok(())
+        }
+
+        #[test]
+        fn test_unique_constraint_conflict() -> Result<()> {
+            let mut tbl = Table::new_default("test".to_string())
+                .add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()))
+                .add_column(Column::new_typed(SQLiteType::Text, "b".to_string()))
+                .add_unique_constraint(vec!["a".to_string(), "b".to_string()], OnConflict::Ignore);
+
+            let conn: Connection = Connection::open_in_memory()?;
+            conn.execute_batch(&tbl.build(false, false)?)?;
+
+            assert_eq!(conn.execute(r#"INSERT INTO test (a, b) VALUES (1, "x");"#, ())?, 1);
+            assert_eq!(conn.execute(r#"INSERT INTO test (a, b) VALUES (1, "y");"#, ())?, 1);
+            // ignored b/c of ON CONFLICT IGNORE
+            assert_eq!(conn.execute(r#"INSERT INTO test (a, b) VALUES (1, "x");"#, ())?, 0);
+            assert_eq!(conn.query_row("SELECT COUNT(*) FROM test;", (), |row| row.get::<usize, usize>(0))?, 2);
+            Ok(())
+        }
+
+        #[test]
+        fn test_temp_table_execute() -> Result<()> {
+            let mut tbl = Table::new_default("test".to_string()).add_column(Column::new_typed(SQLiteType::Integer, "a".to_string())).set_temp(true);
+
+            let conn: Connection = Connection::open_in_memory()?;
+            tbl.execute(false, false, &conn)?;
+            assert_eq!(conn.execute("INSERT INTO temp.test (a) VALUES (1);", ())?, 1);
+            assert_eq!(conn.query_row(r#"SELECT schema FROM pragma_table_list() WHERE name == "test";"#, (), |row| row.get::<usize, String>(0))?, "temp");
+            Ok(())
+        }
+
+        #[test]
+        fn test_unique_index() -> Result<()> {
+            let mut schema = Schema::new()
+                .add_table(Table::new_default("test".to_string()).add_column(Column::new_typed(SQLiteType::Integer, "a".to_string())))
+                .add_index(Index::new_default("test_idx".to_string(), "test".to_string()).add_column("a".to_string(), Order::Ascending).set_unique(true));
+
+            let conn: Connection = Connection::open_in_memory()?;
+            schema.execute(true, false, &conn)?;
+            assert_eq!(conn.execute("INSERT INTO test (a) VALUES (1);", ())?, 1);
+            assert!(conn.execute("INSERT INTO test (a) VALUES (1);", ()).is_err());
+
+            let mut idx = Index::new_default("test_idx".to_string(), "test".to_string()).add_column("a".to_string(), Order::Ascending);
+            assert!(idx.execute(false, false, &conn).is_err());
+            idx.execute(false, true, &conn)?;
+            Ok(())
+        }
+
+        #[test]
+        fn test_check_violation() -> Result<()> {
+            let mut tbl = Table::new_default("test".to_string())
+                .add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()).set_check(Some(Check::new("a > 0".to_string()))));
+
+            let conn: Connection = Connection::open_in_memory()?;
+            tbl.execute(false, false, &conn)?;
+            assert_eq!(conn.execute("INSERT INTO test (a) VALUES (1);", ())?, 1);
+            assert!(conn.execute("INSERT INTO test (a) VALUES (0);", ()).is_err());
+            Ok(())
+        }
+
+        #[test]
+        fn test_default_value_insert() -> Result<()> {
+            let mut tbl = Table::new_default("test".to_string())
+                .add_column(Column::new_typed(SQLiteType::Integer, "a".to_string()))
+                .add_column(Column::new_typed(SQLiteType::Integer, "b".to_string()).set_default(Some(DefaultValue::Integer(-7))))
+                .add_column(Column::new_typed(SQLiteType::Text, "c".to_string()).set_default(Some(DefaultValue::Text("it's".to_string()))));
+
+            let conn: Connection = Connection::open_in_memory()?;
+            conn.execute_batch(&tbl.build(false, false)?)?;
+            conn.execute("INSERT INTO test (a) VALUES (1);", ())?;
+
+            let (b, c) = conn.query_row("SELECT b, c FROM test;", (), |row| Ok((row.get::<usize, i64>(0)?, row.get::<usize, String>(1)?)))?;
+            assert_eq!(b, -7);
+            assert_eq!(c, "it's");
+            Ok(())
+        }
+    }
+
+    mod proptests {
+        use super::*;
+        use proptest::prelude::*;
+
+        /// Prefixed, so the names never collide with SQLite keywords or reserved `sqlite_` names
+        fn name(prefix: &'static str) -> impl Strategy<Value = String> {
+            "[a-z0-9_]{0,6}".prop_map(move |suffix| format!("{}{}", prefix, suffix))
+        }
+
+        impl Arbitrary for SQLiteType {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: ()) -> Self::Strategy {
+                prop_oneof![Just(SQLiteType::Blob), Just(SQLiteType::Numeric), Just(SQLiteType::Integer), Just(SQLiteType::Real), Just(SQLiteType::Text)].boxed()
+            }
+        }
+
+        impl Arbitrary for Order {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: ()) -> Self::Strategy {
+                prop_oneof![Just(Order::Ascending), Just(Order::Descending)].boxed()
+            }
+        }
+
+        impl Arbitrary for OnConflict {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: ()) -> Self::Strategy {
+                prop_oneof![Just(OnConflict::Rollback), Just(OnConflict::Abort), Just(OnConflict::Fail), Just(OnConflict::Ignore), Just(OnConflict::Replace)].boxed()
+            }
+        }
+
+        impl Arbitrary for FKOnAction {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: ()) -> Self::Strategy {
+                prop_oneof![Just(FKOnAction::SetNull), Just(FKOnAction::SetDefault), Just(FKOnAction::Cascade), Just(FKOnAction::Restrict), Just(FKOnAction::NoAction)].boxed()
+            }
+        }
+
+        impl Arbitrary for FKDeferrable {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: ()) -> Self::Strategy {
+                prop_oneof![Just(FKDeferrable::NotDeferrable), Just(FKDeferrable::InitiallyImmediate), Just(FKDeferrable::InitiallyDeferred)].boxed()
+            }
+        }
+
+        impl Arbitrary for PrimaryKey {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: ()) -> Self::Strategy {
+                (any::<Order>(), any::<OnConflict>(), any::<bool>()).prop_map(|(order, on_conflict, autoincrement)| PrimaryKey::new(order, on_conflict, autoincrement)).boxed()
+            }
+        }
+
+        impl Arbitrary for ForeignKey {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: ()) -> Self::Strategy {
+                (name("t_"), name("c_"), any::<Option<FKOnAction>>(), any::<Option<FKOnAction>>(), any::<FKDeferrable>())
+                    .prop_map(|(tbl, col, on_delete, on_update, deferrable)| ForeignKey::new(tbl, col, on_delete, on_update, deferrable))
+                    .boxed()
+            }
+        }
+
+        impl Arbitrary for DefaultValue {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: ()) -> Self::Strategy {
+                prop_oneof![
+                    Just(DefaultValue::Null),
+                    any::<i64>().prop_map(DefaultValue::Integer),
+                    (-1e9..1e9f64).prop_map(DefaultValue::Real),
+                    "[a-z' ]{0,8}".prop_map(DefaultValue::Text),
+                    Just(DefaultValue::CurrentTimestamp),
+                    Just(DefaultValue::CurrentDate),
+                    Just(DefaultValue::CurrentTime),
+                    Just(DefaultValue::Expr("1 + 1".to_string())),
+                ].boxed()
+            }
+        }
+
+        impl Arbitrary for Collation {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<Self>;
+
+            /// Without [Collation::Custom], as SQLite rejects unknown Collations
+            fn arbitrary_with(_: ()) -> Self::Strategy {
+                prop_oneof![Just(Collation::Binary), Just(Collation::NoCase), Just(Collation::RTrim)].boxed()
+            }
+        }
+
+        impl Arbitrary for Column {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: ()) -> Self::Strategy {
+                (
+                    any::<SQLiteType>(),
+                    name("c_"),
+                    any::<Option<PrimaryKey>>(),
+                    any::<Option<OnConflict>>(),
+                    any::<Option<ForeignKey>>(),
+                    any::<Option<OnConflict>>(),
+                    any::<bool>(),
+                    any::<Option<DefaultValue>>(),
+                    any::<Option<Collation>>(),
+                ).prop_map(|(typ, name, pk, unique, fk, not_null, check, default, collation)| {
+                    Column::new(typ, name.clone(), pk, unique.map(Unique::new), fk, not_null.map(NotNull::new))
+                        .set_check(check.then(|| Check::new(format!("{} IS NOT NULL", name))))
+                        .set_default(default)
+                        .set_collation(collation)
+                }).boxed()
+            }
+        }
+
+        impl Arbitrary for Table {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: ()) -> Self::Strategy {
+                (name("t_"), prop::collection::vec(any::<Column>(), 0..5), any::<bool>(), any::<bool>(), any::<bool>())
+                    .prop_map(|(name, columns, without_rowid, strict, temp)| Table::new(name, columns, without_rowid, strict).set_temp(temp))
+                    .boxed()
+            }
+        }
+
+        impl Arbitrary for Schema {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: ()) -> Self::Strategy {
+                prop::collection::vec(any::<Table>(), 0..4).prop_map(Schema::from).boxed()
+            }
+        }
+
+        proptest! {
+            #[test]
+            fn test_column_len(col in any::<Column>()) {
+                let mut str: String = String::new();
+                match col.part_str(&mut str) {
+                    Ok(()) => prop_assert_eq!(col.part_len(), Ok(str.len())),
+                    Err(err) => prop_assert_eq!(col.part_len(), Err(err)),
+                }
+            }
+
+            #[test]
+            fn test_table_len(mut tbl in any::<Table>(), transaction in any::<bool>(), if_exists in any::<bool>()) {
+                match tbl.build(transaction, if_exists) {
+                    Ok(sql) => {
+                        prop_assert_eq!(tbl.len(transaction, if_exists), Ok(sql.len()));
+                        prop_assert!(test_sql(&mut tbl).is_ok(), "{}", sql);
+                    }
+                    Err(err) => prop_assert_eq!(tbl.len(transaction, if_exists), Err(err)),
+                }
+            }
+
+            #[test]
+            fn test_schema_len(mut schema in any::<Schema>(), transaction in any::<bool>(), if_exists in any::<bool>()) {
+                match schema.build(transaction, if_exists) {
+                    Ok(sql) => {
+                        prop_assert_eq!(schema.len(transaction, if_exists), Ok(sql.len()));
+                        prop_assert!(test_sql(&mut schema).is_ok(), "{}", sql);
+                    }
+                    Err(err) => prop_assert_eq!(schema.len(transaction, if_exists), Err(err)),
+                }
+            }
+        }
     }
 }
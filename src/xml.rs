@@ -0,0 +1,469 @@
+//! XML (de-)serialization via quick-xml.
+//!
+//! The XML representation is separate from the serde impls of the data types (which are used for JSON and TOML):
+//! scalar fields are written as attributes (e.g. `<column name="id" type="integer">`), nested types as elements.
+
+use serde::{Serialize, Deserialize, Serializer, Deserializer};
+use quick_xml::DeError;
+
+use crate::{
+    Check, Collation, Column, DefaultValue, FKDeferrable, FKOnAction, ForeignKey, Index, IndexColumn, Named, NotNull,
+    OnConflict, Order, Pragma, PragmaValue, PrimaryKey, Quote, Schema, SQLiteType, Table, TableConstraint, TableConstraintKind,
+    TableConstraintRepr, Unique,
+};
+
+/// The namespace of a sqlayout XML document, see [from_str_namespaced]
+pub(crate) const XMLNS: &str = "https://crates.io/crates/sqlayout";
+
+/// Types with a XML representation: [Schema], [Table], [Column] and [Index].
+pub trait XmlConfig: crate::sealed::Sealed + Sized {
+    #[doc(hidden)]
+    fn serialize_xml<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+
+    #[doc(hidden)]
+    fn deserialize_xml<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
+}
+
+/// Adapter between [XmlConfig] and serde
+struct Xml<T>(T);
+
+impl<T: XmlConfig> Serialize for Xml<&T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_xml(serializer)
+    }
+}
+
+impl<'de, T: XmlConfig> Deserialize<'de> for Xml<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize_xml(deserializer).map(Xml)
+    }
+}
+
+/// Serializes `value` to XML, the root element is named after the type (e.g. `<schema>`).
+pub fn to_string<T: XmlConfig>(value: &T) -> Result<String, DeError> {
+    quick_xml::se::to_string(&Xml(value))
+}
+
+/// Serializes `value` to XML with a custom name for the root element.
+pub fn to_string_with_root<T: XmlConfig>(root_tag: &str, value: &T) -> Result<String, DeError> {
+    quick_xml::se::to_string_with_root(root_tag, &Xml(value))
+}
+
+/// Deserializes a [XmlConfig] type from XML, the name of the root element is not checked. The result is not checked either.
+pub fn from_str<T: XmlConfig>(xml: &str) -> Result<T, DeError> {
+    Ok(quick_xml::de::from_str::<Xml<T>>(xml)?.0)
+}
+
+/// Like [from_str], from any buffered reader (e.g. a `BufReader<File>`).
+pub fn from_reader<T: XmlConfig, R: std::io::BufRead>(reader: R) -> Result<T, DeError> {
+    Ok(quick_xml::de::from_reader::<R, Xml<T>>(reader)?.0)
+}
+
+/// Like [from_str], but first checks that the root element declares the sqlayout namespace
+/// (`xmlns="https://crates.io/crates/sqlayout"`), otherwise fails with [DeError::Custom].
+pub fn from_str_namespaced(xml: &str) -> Result<Schema, DeError> {
+    use quick_xml::events::Event;
+
+    let mut reader = quick_xml::Reader::from_str(xml);
+    let root = loop {
+        match reader.read_event()? {
+            Event::Start(start) | Event::Empty(start) => break start,
+            Event::Eof => return Err(DeError::UnexpectedEof),
+            _ => {}
+        }
+    };
+    let xmlns = root.try_get_attribute("xmlns")?;
+    let xmlns = xmlns.map(|attr| attr.unescape_value()).transpose()?;
+    if xmlns.as_deref() != Some(XMLNS) {
+        return Err(DeError::Custom(format!("expected namespace '{}', got {:?}", XMLNS, xmlns.as_deref())));
+    }
+    from_str(xml)
+}
+
+/// quick-xml can only (de-)serialize enums carrying data as the content of an element,
+/// so optional enum fields are wrapped in one (e.g. `<default><integer>0</integer></default>`).
+/// Usage: `#[serde(default, with = "xml_value", skip_serializing_if = "Option::is_none")]`
+mod xml_value {
+    use serde::{Serialize, Deserialize, Serializer, Deserializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct Wrapper<T> {
+        #[serde(rename = "$value")]
+        value: T,
+    }
+
+    pub(crate) fn serialize<T: Serialize, S: Serializer>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error> {
+        value.as_ref().map(|value| Wrapper { value }).serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(deserializer: D) -> Result<Option<T>, D::Error> {
+        Ok(Option::<Wrapper<T>>::deserialize(deserializer)?.map(|wrapper| wrapper.value))
+    }
+}
+
+/// Converts between a type and its XML representation with the same field names.
+/// `opt` and `vec` fields hold other XML representations, `skip` fields are not part of the XML.
+/// A [Named] constraint is flattened, its name is stored in the `constraint_name` and `constraint_quote` fields.
+macro_rules! xml_repr {
+    ($xml:ident <=> Named<$typ:ident> { $($field:ident),* }) => {
+        impl From<&Named<$typ>> for $xml {
+            fn from(value: &Named<$typ>) -> Self {
+                Self {
+                    $($field: value.$field.clone(),)*
+                    constraint_name: value.name.clone(),
+                    constraint_quote: value.name_quote,
+                }
+            }
+        }
+
+        impl From<$xml> for Named<$typ> {
+            fn from(value: $xml) -> Self {
+                Named::new($typ { $($field: value.$field,)* }, value.constraint_name).set_name_quote(value.constraint_quote)
+            }
+        }
+    };
+    ($xml:ident <=> $typ:ident { $($field:ident),* $(; opt $($opt:ident),+)? $(; vec $($vec:ident),+)? $(; skip $($skip:ident),+)? }) => {
+        impl From<&$typ> for $xml {
+            fn from(value: &$typ) -> Self {
+                Self {
+                    $($field: value.$field.clone(),)*
+                    $($($opt: value.$opt.as_ref().map(Into::into),)+)?
+                    $($($vec: value.$vec.iter().map(Into::into).collect(),)+)?
+                }
+            }
+        }
+
+        impl From<$xml> for $typ {
+            fn from(value: $xml) -> Self {
+                Self {
+                    $($field: value.$field,)*
+                    $($($opt: value.$opt.map(Into::into),)+)?
+                    $($($vec: value.$vec.into_iter().map(Into::into).collect(),)+)?
+                    $($($skip: Default::default(),)+)?
+                }
+            }
+        }
+    };
+}
+
+/// Implements [XmlConfig] via the XML representation
+macro_rules! impl_xml_config {
+    ($($typ:ty => $xml:ty),+) => {
+        $(
+            impl XmlConfig for $typ {
+                fn serialize_xml<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    <$xml>::from(self).serialize(serializer)
+                }
+
+                fn deserialize_xml<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    <$xml>::deserialize(deserializer).map(Into::into)
+                }
+            }
+        )+
+    };
+}
+
+impl_xml_config!(Schema => XmlSchema, Table => XmlTable, Column => XmlColumn, Index => XmlIndex);
+
+#[derive(Serialize, Deserialize)]
+struct XmlPrimaryKey {
+    #[serde(rename = "@order", default)]
+    sort_order: Order,
+    #[serde(rename = "@on_conflict", default)]
+    on_conflict: OnConflict,
+    #[serde(rename = "@autoincrement", default)]
+    autoincrement: bool,
+    #[serde(rename = "@constraint_name", default, skip_serializing_if = "Option::is_none")]
+    constraint_name: Option<String>,
+    #[serde(rename = "@constraint_quote", default)]
+    constraint_quote: Quote,
+}
+
+xml_repr!(XmlPrimaryKey <=> Named<PrimaryKey> { sort_order, on_conflict, autoincrement });
+
+#[derive(Serialize, Deserialize)]
+struct XmlNotNull {
+    #[serde(rename = "@on_conflict", default)]
+    on_conflict: OnConflict,
+    #[serde(rename = "@constraint_name", default, skip_serializing_if = "Option::is_none")]
+    constraint_name: Option<String>,
+    #[serde(rename = "@constraint_quote", default)]
+    constraint_quote: Quote,
+}
+
+xml_repr!(XmlNotNull <=> Named<NotNull> { on_conflict });
+
+#[derive(Serialize, Deserialize)]
+struct XmlUnique {
+    #[serde(rename = "@on_conflict", default)]
+    on_conflict: OnConflict,
+    #[serde(rename = "@constraint_name", default, skip_serializing_if = "Option::is_none")]
+    constraint_name: Option<String>,
+    #[serde(rename = "@constraint_quote", default)]
+    constraint_quote: Quote,
+}
+
+xml_repr!(XmlUnique <=> Named<Unique> { on_conflict });
+
+#[derive(Serialize, Deserialize)]
+struct XmlForeignKey {
+    #[serde(rename = "@foreign_table")]
+    foreign_table: String,
+    #[serde(rename = "@foreign_column")]
+    foreign_column: String,
+    #[serde(rename = "@quote", default)]
+    quote: Quote,
+    #[serde(rename = "@on_delete", default, skip_serializing_if = "Option::is_none")]
+    on_delete: Option<FKOnAction>,
+    #[serde(rename = "@on_update", default, skip_serializing_if = "Option::is_none")]
+    on_update: Option<FKOnAction>,
+    #[serde(rename = "@deferrable", default)]
+    deferrable: FKDeferrable,
+    #[serde(rename = "@constraint_name", default, skip_serializing_if = "Option::is_none")]
+    constraint_name: Option<String>,
+    #[serde(rename = "@constraint_quote", default)]
+    constraint_quote: Quote,
+}
+
+xml_repr!(XmlForeignKey <=> Named<ForeignKey> { foreign_table, foreign_column, quote, on_delete, on_update, deferrable });
+
+#[derive(Serialize, Deserialize)]
+struct XmlCheck {
+    #[serde(rename = "@expr")]
+    expr: String,
+    #[serde(rename = "@constraint_name", default, skip_serializing_if = "Option::is_none")]
+    constraint_name: Option<String>,
+    #[serde(rename = "@constraint_quote", default)]
+    constraint_quote: Quote,
+}
+
+xml_repr!(XmlCheck <=> Named<Check> { expr });
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "column")]
+struct XmlColumn {
+    #[serde(rename = "@type")]
+    typ: SQLiteType,
+    #[serde(rename = "@name")]
+    name: String,
+    #[serde(rename = "@quote", default)]
+    quote: Quote,
+    #[serde(rename = "@comment", default, skip_serializing_if = "Option::is_none")]
+    comment: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pk: Option<XmlPrimaryKey>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    unique: Option<XmlUnique>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fk: Option<XmlForeignKey>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    not_null: Option<XmlNotNull>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    check: Option<XmlCheck>,
+    #[serde(default, with = "xml_value", skip_serializing_if = "Option::is_none")]
+    default: Option<DefaultValue>,
+    #[serde(default, with = "xml_value", skip_serializing_if = "Option::is_none")]
+    collation: Option<Collation>,
+}
+
+xml_repr!(XmlColumn <=> Column { typ, name, quote, comment, default, collation; opt pk, unique, fk, not_null, check });
+
+/// Same as [TableConstraintRepr], with the scalar fields as attributes,
+/// e.g. `<constraint type="unique"><column>a</column><column>b</column></constraint>`
+#[derive(Serialize, Deserialize)]
+struct XmlTableConstraintRepr {
+    #[serde(rename = "@type")]
+    kind: TableConstraintKind,
+    #[serde(rename = "column")]
+    columns: Vec<String>,
+    #[serde(rename = "@on_conflict", default, skip_serializing_if = "Option::is_none")]
+    on_conflict: Option<OnConflict>,
+    #[serde(rename = "@foreign_table", default, skip_serializing_if = "Option::is_none")]
+    foreign_table: Option<String>,
+    #[serde(rename = "foreign_column", default, skip_serializing_if = "Vec::is_empty")]
+    foreign_columns: Vec<String>,
+    #[serde(rename = "@quote", default, skip_serializing_if = "Option::is_none")]
+    quote: Option<Quote>,
+    #[serde(rename = "@on_delete", default, skip_serializing_if = "Option::is_none")]
+    on_delete: Option<FKOnAction>,
+    #[serde(rename = "@on_update", default, skip_serializing_if = "Option::is_none")]
+    on_update: Option<FKOnAction>,
+    #[serde(rename = "@deferrable", default, skip_serializing_if = "Option::is_none")]
+    deferrable: Option<FKDeferrable>,
+}
+
+impl From<TableConstraintRepr> for XmlTableConstraintRepr {
+    fn from(repr: TableConstraintRepr) -> Self {
+        Self {
+            kind: repr.kind,
+            columns: repr.columns,
+            on_conflict: repr.on_conflict,
+            foreign_table: repr.foreign_table,
+            foreign_columns: repr.foreign_columns,
+            quote: repr.quote,
+            on_delete: repr.on_delete,
+            on_update: repr.on_update,
+            deferrable: repr.deferrable,
+        }
+    }
+}
+
+impl From<XmlTableConstraintRepr> for TableConstraintRepr {
+    fn from(repr: XmlTableConstraintRepr) -> Self {
+        Self {
+            kind: repr.kind,
+            columns: repr.columns,
+            on_conflict: repr.on_conflict,
+            foreign_table: repr.foreign_table,
+            foreign_columns: repr.foreign_columns,
+            quote: repr.quote,
+            on_delete: repr.on_delete,
+            on_update: repr.on_update,
+            deferrable: repr.deferrable,
+        }
+    }
+}
+
+/// A [TableConstraint] (de-)serialized via [XmlTableConstraintRepr], which may fail (e.g. a Foreign Key without `foreign_table`)
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(into = "XmlTableConstraintRepr", try_from = "XmlTableConstraintRepr")]
+struct XmlTableConstraint(TableConstraint);
+
+impl From<XmlTableConstraint> for XmlTableConstraintRepr {
+    fn from(constraint: XmlTableConstraint) -> Self {
+        TableConstraintRepr::from(constraint.0).into()
+    }
+}
+
+impl TryFrom<XmlTableConstraintRepr> for XmlTableConstraint {
+    type Error = crate::Error;
+
+    fn try_from(repr: XmlTableConstraintRepr) -> crate::Result<Self> {
+        TableConstraint::try_from(TableConstraintRepr::from(repr)).map(XmlTableConstraint)
+    }
+}
+
+impl From<&TableConstraint> for XmlTableConstraint {
+    fn from(constraint: &TableConstraint) -> Self {
+        Self(constraint.clone())
+    }
+}
+
+impl From<XmlTableConstraint> for TableConstraint {
+    fn from(constraint: XmlTableConstraint) -> Self {
+        constraint.0
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "table")]
+struct XmlTable {
+    #[serde(rename = "@name")]
+    name: String,
+    #[serde(rename = "column")]
+    columns: Vec<XmlColumn>,
+    #[serde(rename = "@without_rowid", default)]
+    without_rowid: bool,
+    #[serde(rename = "@strict", default)]
+    strict: bool,
+    #[serde(rename = "@temp", default)]
+    temp: bool,
+    #[serde(rename = "@quote", default)]
+    quote: Quote,
+    #[serde(rename = "@comment", default, skip_serializing_if = "Option::is_none")]
+    comment: Option<String>,
+    #[serde(rename = "constraint", default, skip_serializing_if = "Vec::is_empty")]
+    constraints: Vec<XmlTableConstraint>,
+}
+
+xml_repr!(XmlTable <=> Table { name, without_rowid, strict, temp, quote, comment; vec columns, constraints; skip if_exists });
+
+#[derive(Serialize, Deserialize)]
+struct XmlIndexColumn {
+    #[serde(rename = "@name")]
+    name: String,
+    #[serde(rename = "@quote", default)]
+    quote: Quote,
+    #[serde(rename = "@order", default)]
+    order: Order,
+    #[serde(default, with = "xml_value", skip_serializing_if = "Option::is_none")]
+    collation: Option<Collation>,
+}
+
+xml_repr!(XmlIndexColumn <=> IndexColumn { name, quote, order, collation });
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "index")]
+struct XmlIndex {
+    #[serde(rename = "@name")]
+    name: String,
+    #[serde(rename = "@table")]
+    table: String,
+    #[serde(rename = "@quote", default)]
+    quote: Quote,
+    #[serde(rename = "column")]
+    columns: Vec<XmlIndexColumn>,
+    #[serde(rename = "@unique", default)]
+    unique: bool,
+    #[serde(rename = "@where", default, skip_serializing_if = "Option::is_none")]
+    where_expr: Option<String>,
+}
+
+xml_repr!(XmlIndex <=> Index { name, table, quote, unique, where_expr; vec columns; skip if_exists });
+
+#[derive(Serialize, Deserialize)]
+struct XmlPragma {
+    #[serde(rename = "@name")]
+    name: String,
+    // the value is a element, e.g. `<pragma name="cache_size"><integer>-2000</integer></pragma>`
+    #[serde(rename = "$value")]
+    value: PragmaValue,
+}
+
+xml_repr!(XmlPragma <=> Pragma { name, value });
+
+fn xmlns() -> &'static str {
+    XMLNS
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "schema")]
+struct XmlSchema {
+    #[serde(rename = "table")]
+    tables: Vec<XmlTable>,
+    #[serde(rename = "index", default, skip_serializing_if = "Vec::is_empty")]
+    indexes: Vec<XmlIndex>,
+    #[serde(rename = "pragma", default, skip_serializing_if = "Vec::is_empty")]
+    pragmas: Vec<XmlPragma>,
+    #[serde(rename = "@comment", default, skip_serializing_if = "Option::is_none")]
+    comment: Option<String>,
+    #[serde(rename = "@name", default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(rename = "@xmlns", skip_deserializing, default = "xmlns")]
+    xmlns: &'static str,
+}
+
+impl From<&Schema> for XmlSchema {
+    fn from(schema: &Schema) -> Self {
+        Self {
+            tables: schema.tables.iter().map(Into::into).collect(),
+            indexes: schema.indexes.iter().map(Into::into).collect(),
+            pragmas: schema.pragmas.iter().map(Into::into).collect(),
+            comment: schema.comment.clone(),
+            name: schema.name.clone(),
+            xmlns: XMLNS,
+        }
+    }
+}
+
+impl From<XmlSchema> for Schema {
+    fn from(schema: XmlSchema) -> Self {
+        Self {
+            tables: schema.tables.into_iter().map(Into::into).collect(),
+            indexes: schema.indexes.into_iter().map(Into::into).collect(),
+            pragmas: schema.pragmas.into_iter().map(Into::into).collect(),
+            comment: schema.comment,
+            name: schema.name,
+        }
+    }
+}